@@ -0,0 +1,42 @@
+use bevy_ecs::prelude::*;
+use glam::*;
+
+#[derive(Component, Copy, Clone)]
+#[component(storage = "SparseSet")]
+struct Transform(Mat4);
+
+#[derive(Component, Copy, Clone)]
+#[component(storage = "SparseSet")]
+struct Position(Vec3);
+
+#[derive(Component, Copy, Clone)]
+#[component(storage = "SparseSet")]
+struct Rotation(Vec3);
+
+#[derive(Component, Copy, Clone)]
+#[component(storage = "SparseSet")]
+struct Velocity(Vec3);
+
+pub struct Benchmark(World, Vec<Entity>);
+
+impl Benchmark {
+    pub fn new() -> Self {
+        let mut world = World::new();
+        let entities = world.spawn_batch((0..10_000).map(|_| ())).collect();
+        Self(world, entities)
+    }
+
+    pub fn run(&mut self) {
+        self.0.insert_batch(self.1.iter().map(|&entity| {
+            (
+                entity,
+                (
+                    Transform(Mat4::from_scale(Vec3::ONE)),
+                    Position(Vec3::X),
+                    Rotation(Vec3::X),
+                    Velocity(Vec3::X),
+                ),
+            )
+        }));
+    }
+}
@@ -6,10 +6,12 @@ mod add_remove_table;
 mod add_remove_very_big_table;
 mod archetype_updates;
 mod insert_simple;
+mod insert_simple_sparse_set;
+mod insert_simple_sparse_set_unbatched;
 mod insert_simple_unbatched;
 
 use archetype_updates::*;
-use criterion::{criterion_group, Criterion};
+use criterion::{criterion_group, BatchSize, Criterion};
 
 criterion_group!(
     benches,
@@ -74,5 +76,20 @@ fn insert_simple(c: &mut Criterion) {
         let mut bench = insert_simple_unbatched::Benchmark::new();
         b.iter(move || bench.run());
     });
+    // The entities are spawned in the setup, so that only the insertions are measured.
+    group.bench_function("sparse_set", |b| {
+        b.iter_batched_ref(
+            insert_simple_sparse_set::Benchmark::new,
+            insert_simple_sparse_set::Benchmark::run,
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("sparse_set_unbatched", |b| {
+        b.iter_batched_ref(
+            insert_simple_sparse_set_unbatched::Benchmark::new,
+            insert_simple_sparse_set_unbatched::Benchmark::run,
+            BatchSize::LargeInput,
+        );
+    });
     group.finish();
 }
@@ -0,0 +1,42 @@
+use criterion::{criterion_group, BatchSize, Criterion};
+
+use bevy_asset::Assets;
+use bevy_ecs::{schedule::Schedule, world::World};
+use bevy_math::primitives::Sphere;
+use bevy_render::{
+    mesh::{Mesh, Mesh3d},
+    view::calculate_bounds,
+};
+use bevy_tasks::{ComputeTaskPool, TaskPool};
+
+const MESH_COUNT: usize = 10_000;
+/// The number of distinct mesh assets shared by the entities.
+const ASSET_COUNT: usize = 100;
+
+fn setup() -> (World, Schedule) {
+    let mut world = World::new();
+    let mut meshes = Assets::<Mesh>::default();
+    let handles: Vec<_> = (0..ASSET_COUNT)
+        .map(|i| meshes.add(Sphere::new(1.0 + i as f32)))
+        .collect();
+    world.insert_resource(meshes);
+    world.spawn_batch((0..MESH_COUNT).map(move |i| Mesh3d(handles[i % ASSET_COUNT].clone())));
+
+    let mut schedule = Schedule::default();
+    schedule.add_systems(calculate_bounds);
+    (world, schedule)
+}
+
+fn calculate_bounds_benchmark(c: &mut Criterion) {
+    ComputeTaskPool::get_or_init(TaskPool::default);
+
+    c.bench_function("calculate_bounds", |b| {
+        b.iter_batched_ref(
+            setup,
+            |(world, schedule)| schedule.run(world),
+            BatchSize::LargeInput,
+        );
+    });
+}
+
+criterion_group!(benches, calculate_bounds_benchmark);
@@ -1,11 +1,13 @@
 use criterion::criterion_main;
 
+mod calculate_bounds;
 mod compute_normals;
 mod render_layers;
 mod torus;
 
 criterion_main!(
     render_layers::benches,
+    calculate_bounds::benches,
     compute_normals::benches,
     torus::benches
 );
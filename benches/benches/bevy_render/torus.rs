@@ -1,8 +1,8 @@
-use core::hint::black_box;
+use core::{f32::consts::PI, hint::black_box};
 
 use criterion::{criterion_group, Criterion};
 
-use bevy_render::mesh::TorusMeshBuilder;
+use bevy_render::mesh::{MeshBuilder, TorusMeshBuilder};
 
 fn torus(c: &mut Criterion) {
     c.bench_function("build_torus", |b| {
@@ -10,4 +10,16 @@ fn torus(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, torus);
+fn partial_torus(c: &mut Criterion) {
+    c.bench_function("build_partial_torus", |b| {
+        b.iter(|| {
+            black_box(
+                TorusMeshBuilder::new(black_box(0.5), black_box(1.0))
+                    .angle_range(black_box(0.0..=1.5 * PI))
+                    .build(),
+            )
+        });
+    });
+}
+
+criterion_group!(benches, torus, partial_torus);
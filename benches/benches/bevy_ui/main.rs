@@ -0,0 +1,5 @@
+use criterion::criterion_main;
+
+mod ui_surface;
+
+criterion_main!(ui_surface::benches);
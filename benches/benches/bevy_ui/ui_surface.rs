@@ -0,0 +1,53 @@
+use core::hint::black_box;
+
+use criterion::{criterion_group, BatchSize, Criterion};
+
+use bevy_ecs::entity::Entity;
+use bevy_ui::{LayoutContext, Node, UiSurface, Val};
+
+const NODE_COUNT: u32 = 10_000;
+
+fn upsert_nodes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("upsert_nodes");
+
+    let nodes: Vec<(Entity, Node)> = (0..NODE_COUNT)
+        .map(|index| {
+            let node = Node {
+                width: Val::Px(index as f32),
+                ..Default::default()
+            };
+            (Entity::from_raw_u32(index).unwrap(), node)
+        })
+        .collect();
+
+    group.bench_function("loop", |b| {
+        b.iter_batched_ref(
+            UiSurface::default,
+            |ui_surface| {
+                for (entity, node) in &nodes {
+                    ui_surface.upsert_node(&LayoutContext::DEFAULT, *entity, node, None);
+                }
+                black_box(ui_surface);
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("batch", |b| {
+        b.iter_batched_ref(
+            UiSurface::default,
+            |ui_surface| {
+                ui_surface.upsert_nodes(
+                    &LayoutContext::DEFAULT,
+                    nodes.iter().map(|(entity, node)| (*entity, node, None)),
+                );
+                black_box(ui_surface);
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, upsert_nodes);
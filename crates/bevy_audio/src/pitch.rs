@@ -2,34 +2,215 @@ use crate::Decodable;
 use bevy_asset::Asset;
 use bevy_reflect::TypePath;
 use rodio::{
-    source::{SineWave, TakeDuration},
+    cpal::SampleRate,
+    source::{Function, SeekError, SignalGenerator, TakeDuration},
     Source,
 };
 
-/// A source of sine wave sound
+/// A source of sound with a simple periodic [`Waveform`], a sine wave by default
+///
+/// Several frequencies can be played at once, as an interval or a chord, by creating the pitch
+/// with [`Pitch::chord`].
 #[derive(Asset, Debug, Clone, TypePath)]
 pub struct Pitch {
-    /// Frequency at which sound will be played
-    pub frequency: f32,
+    /// Frequencies at which sound will be played
+    ///
+    /// The waves of all frequencies are mixed together, at an equal volume.
+    pub frequencies: Vec<f32>,
     /// Duration for which sound will be played
     pub duration: core::time::Duration,
+    /// Shape of the waves
+    pub waveform: Waveform,
 }
 
 impl Pitch {
     /// Creates a new note
     pub fn new(frequency: f32, duration: core::time::Duration) -> Self {
+        Self::chord(&[frequency], duration)
+    }
+
+    /// Creates a new chord, playing all the given frequencies at once
+    pub fn chord(frequencies: &[f32], duration: core::time::Duration) -> Self {
         Pitch {
-            frequency,
+            frequencies: frequencies.to_vec(),
             duration,
+            waveform: Waveform::default(),
         }
     }
+
+    /// Sets the shape of the waves
+    pub fn with_waveform(mut self, waveform: Waveform) -> Self {
+        self.waveform = waveform;
+        self
+    }
 }
 
 impl Decodable for Pitch {
-    type DecoderItem = <SineWave as Iterator>::Item;
-    type Decoder = TakeDuration<SineWave>;
+    type DecoderItem = <ChordWave as Iterator>::Item;
+    type Decoder = TakeDuration<ChordWave>;
 
     fn decoder(&self) -> Self::Decoder {
-        SineWave::new(self.frequency).take_duration(self.duration)
+        ChordWave::new(&self.frequencies, self.waveform).take_duration(self.duration)
+    }
+}
+
+/// The shape of the waves of a [`Pitch`]
+///
+/// All waveforms go between `-1.0` and `1.0`. Except for [`Waveform::Sine`], they're not
+/// band-limited: their harmonics above half the sample rate of 48 kHz alias back into the audible
+/// range as tones that aren't part of the harmonic series. This makes high notes sound harsh or
+/// out of tune, especially with [`Waveform::Square`] and [`Waveform::Saw`], whose harmonics fade
+/// out slowly. [`Waveform::Triangle`] is much less affected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Waveform {
+    /// A pure tone
+    #[default]
+    Sine,
+    /// A wave that is `1.0` for the first half of each period and `-1.0` for the second half
+    Square,
+    /// A wave that rises linearly over each period, then drops back down at once
+    Saw,
+    /// A wave that rises and falls linearly
+    Triangle,
+}
+
+impl From<Waveform> for Function {
+    fn from(waveform: Waveform) -> Self {
+        match waveform {
+            Waveform::Sine => Function::Sine,
+            Waveform::Square => Function::Square,
+            Waveform::Saw => Function::Sawtooth,
+            Waveform::Triangle => Function::Triangle,
+        }
+    }
+}
+
+/// An infinite source that mixes waves of several frequencies, which is the decoder of
+/// [`Pitch`].
+///
+/// The waves are averaged rather than added up, so that the samples stay between `-1.0` and `1.0`
+/// no matter how many frequencies are stacked. Without any frequencies, this is silent.
+#[derive(Clone, Debug)]
+pub struct ChordWave {
+    waves: Vec<SignalGenerator>,
+}
+
+impl ChordWave {
+    /// The sample rate of the generated waves
+    const SAMPLE_RATE: u32 = 48000;
+
+    /// Creates a source playing all the given frequencies at once, with the given waveform
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the frequencies is zero.
+    pub fn new(frequencies: &[f32], waveform: Waveform) -> Self {
+        ChordWave {
+            waves: frequencies
+                .iter()
+                .map(|&frequency| {
+                    SignalGenerator::new(SampleRate(Self::SAMPLE_RATE), frequency, waveform.into())
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Iterator for ChordWave {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.waves.is_empty() {
+            return Some(0.0);
+        }
+        let sum: f32 = self.waves.iter_mut().filter_map(Iterator::next).sum();
+        Some(sum / self.waves.len() as f32)
+    }
+}
+
+impl Source for ChordWave {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        Self::SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<core::time::Duration> {
+        None
+    }
+
+    fn try_seek(&mut self, pos: core::time::Duration) -> Result<(), SeekError> {
+        self.waves
+            .iter_mut()
+            .try_for_each(|wave| wave.try_seek(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    fn samples(pitch: &Pitch) -> Vec<f32> {
+        pitch.decoder().collect()
+    }
+
+    #[test]
+    fn chord_mixes_frequencies() {
+        let duration = Duration::from_millis(100);
+        let low = samples(&Pitch::new(440.0, duration));
+        let high = samples(&Pitch::new(660.0, duration));
+        let fifth = samples(&Pitch::chord(&[440.0, 660.0], duration));
+
+        assert_eq!(fifth.len(), low.len());
+        assert_eq!(fifth.len(), high.len());
+
+        // The chord is the average of both notes, which differs noticeably from either of them.
+        for ((fifth, low), high) in fifth.iter().zip(&low).zip(&high) {
+            assert!((fifth - (low + high) / 2.0).abs() < 1e-6);
+        }
+        let max_difference = |a: &[f32], b: &[f32]| {
+            a.iter()
+                .zip(b)
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0, f32::max)
+        };
+        assert!(max_difference(&fifth, &low) > 0.5);
+        assert!(max_difference(&fifth, &high) > 0.5);
+
+        // Stacking more notes doesn't clip.
+        let chord = samples(&Pitch::chord(&[261.63, 329.63, 392.0, 523.25], duration));
+        assert!(chord.iter().all(|sample| sample.abs() <= 1.0));
+        assert!(chord.iter().any(|sample| sample.abs() > 0.5));
+    }
+
+    #[test]
+    fn square_wave() {
+        let square =
+            samples(&Pitch::new(440.0, Duration::from_millis(100)).with_waveform(Waveform::Square));
+        assert!(square.iter().all(|&sample| sample == 1.0 || sample == -1.0));
+        assert!(square.contains(&1.0));
+        assert!(square.contains(&-1.0));
+    }
+
+    #[test]
+    fn saw_wave() {
+        let saw =
+            samples(&Pitch::new(440.0, Duration::from_millis(100)).with_waveform(Waveform::Saw));
+
+        // The wave only drops once per period, and rises everywhere else.
+        let drops: Vec<usize> = (1..saw.len()).filter(|&i| saw[i] < saw[i - 1]).collect();
+        assert_eq!(drops.len(), 44);
+        for (start, end) in drops.iter().zip(&drops[1..]) {
+            let period = &saw[*start..*end];
+            assert!(period.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(period[0] < -0.95 && period[period.len() - 1] > 0.95);
+        }
     }
 }
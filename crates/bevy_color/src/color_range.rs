@@ -1,6 +1,11 @@
-use core::ops::Range;
+use core::{marker::PhantomData, ops::Range};
 
-use crate::Mix;
+use crate::{Hue, LinearRgba, Mix};
+use bevy_math::ops;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::prelude::*;
+#[cfg(feature = "alloc")]
+use {alloc::vec::Vec, thiserror::Error};
 
 /// Represents a range of colors that can be linearly interpolated, defined by a start and
 /// end point which must be in the same color space. It works for any color type that
@@ -11,6 +16,36 @@ pub trait ColorRange<T: Mix> {
     /// Get the color value at the given interpolation factor, which should be between 0.0 (start)
     /// and 1.0 (end).
     fn at(&self, factor: f32) -> T;
+
+    /// Returns an iterator over `n` evenly spaced colors of the range, including both ends.
+    ///
+    /// If `n` is 1, the only color is the one at the start of the range. If `n` is 0, the
+    /// iterator is empty.
+    fn samples_iter(&self, n: usize) -> impl ExactSizeIterator<Item = T> + '_
+    where
+        Self: Sized,
+    {
+        let step = if n > 1 { 1.0 / (n - 1) as f32 } else { 0.0 };
+        (0..n).map(move |i| self.at(i as f32 * step))
+    }
+
+    /// Returns `n` evenly spaced colors of the range, including both ends.
+    ///
+    /// This is useful for generating palettes. See [`ColorRange::samples_iter`] for a version
+    /// that doesn't allocate.
+    ///
+    /// ```
+    /// # use bevy_color::{palettes::basic::{BLUE, RED}, ColorRange, Srgba};
+    /// let palette = (RED..BLUE).samples(3);
+    /// assert_eq!(palette, [RED, Srgba::new(0.5, 0.0, 0.5, 1.0), BLUE]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn samples(&self, n: usize) -> Vec<T>
+    where
+        Self: Sized,
+    {
+        self.samples_iter(n).collect()
+    }
 }
 
 impl<T: Mix> ColorRange<T> for Range<T> {
@@ -19,10 +54,337 @@ impl<T: Mix> ColorRange<T> for Range<T> {
     }
 }
 
+/// The arc that the hue takes around the color wheel when interpolating between two hues,
+/// as used by [`HueRange`].
+///
+/// The variants match the `<hue-interpolation-method>` of CSS Color Module Level 4.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Clone, PartialEq, Default)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum HueInterpolation {
+    /// Take the shorter arc between the hues. This is what [`Mix`] does for hue-based colors.
+    #[default]
+    Shorter,
+    /// Take the longer arc between the hues. Equal hues go once around the whole color wheel.
+    Longer,
+    /// Go around the color wheel in the direction of increasing hue.
+    Increasing,
+    /// Go around the color wheel in the direction of decreasing hue.
+    Decreasing,
+}
+
+impl HueInterpolation {
+    /// Interpolates between the hues `start` and `end`, in degrees, by `factor`, returning
+    /// a hue in the `[0, 360)` range.
+    pub fn interpolate(self, start: f32, end: f32, factor: f32) -> f32 {
+        // The signed angle of the shorter arc, in the `[-180, 180)` range.
+        let shorter = ops::rem_euclid(end - start + 180.0, 360.0) - 180.0;
+        let arc = match self {
+            HueInterpolation::Shorter => shorter,
+            HueInterpolation::Longer if shorter > 0.0 => shorter - 360.0,
+            HueInterpolation::Longer => shorter + 360.0,
+            HueInterpolation::Increasing => ops::rem_euclid(end - start, 360.0),
+            HueInterpolation::Decreasing => -ops::rem_euclid(start - end, 360.0),
+        };
+        ops::rem_euclid(start + arc * factor, 360.0)
+    }
+}
+
+/// A range of colors with a hue, like [`Hsla`](crate::Hsla) or [`Oklcha`](crate::Oklcha), that
+/// interpolates the hue along the arc of the color wheel chosen by a [`HueInterpolation`].
+///
+/// A plain [`Range`] of such colors always takes the shorter arc, so it can't sweep through
+/// the whole color wheel. All other channels are interpolated like [`Mix`] does.
+///
+/// ```
+/// # use bevy_color::{ColorRange, Hsla, HueInterpolation, HueRange};
+/// // A full rainbow, from red back around to red.
+/// let rainbow = HueRange::new(
+///     Hsla::hsl(0.0, 1.0, 0.5),
+///     Hsla::hsl(0.0, 1.0, 0.5),
+///     HueInterpolation::Longer,
+/// );
+/// assert_eq!(rainbow.at(0.5).hue, 180.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct HueRange<T> {
+    /// The color at the start of the range.
+    pub start: T,
+    /// The color at the end of the range.
+    pub end: T,
+    /// The arc the hue takes from `start` to `end`.
+    pub interpolation: HueInterpolation,
+}
+
+impl<T> HueRange<T> {
+    /// Creates a new [`HueRange`] from `start` to `end`, interpolating the hue along the arc
+    /// chosen by `interpolation`.
+    pub const fn new(start: T, end: T, interpolation: HueInterpolation) -> Self {
+        Self {
+            start,
+            end,
+            interpolation,
+        }
+    }
+}
+
+impl<T: Mix + Hue> ColorRange<T> for HueRange<T> {
+    fn at(&self, factor: f32) -> T {
+        let factor = factor.clamp(0.0, 1.0);
+        let hue = self
+            .interpolation
+            .interpolate(self.start.hue(), self.end.hue(), factor);
+        self.start.mix(&self.end, factor).with_hue(hue)
+    }
+}
+
+/// A [`ColorRange`] adapter that clamps every color sampled from the wrapped range into the
+/// sRGB gamut.
+///
+/// Ranges with HDR or wide-gamut endpoints, or ranges interpolating in spaces like
+/// [`Oklaba`](crate::Oklaba), can produce colors that sRGB displays can't show. Each sampled
+/// color is converted to [`LinearRgba`], its color channels are clamped to `[0, 1]`, and it is
+/// converted back. The alpha channel is left as is.
+///
+/// ```
+/// # use bevy_color::{ColorRange, GamutClampedColorRange, LinearRgba};
+/// let range = GamutClampedColorRange::new(LinearRgba::rgb(4.0, 0.0, 0.0)..LinearRgba::BLUE);
+/// assert_eq!(range.at(0.0), LinearRgba::RED);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GamutClampedColorRange<R> {
+    /// The range whose colors are clamped.
+    pub range: R,
+}
+
+impl<R> GamutClampedColorRange<R> {
+    /// Creates a new [`GamutClampedColorRange`] clamping the colors of `range`.
+    pub const fn new(range: R) -> Self {
+        Self { range }
+    }
+}
+
+impl<T, R> ColorRange<T> for GamutClampedColorRange<R>
+where
+    T: Mix + Into<LinearRgba> + From<LinearRgba>,
+    R: ColorRange<T>,
+{
+    fn at(&self, factor: f32) -> T {
+        let LinearRgba {
+            red,
+            green,
+            blue,
+            alpha,
+        } = self.range.at(factor).into();
+        T::from(LinearRgba::new(
+            red.clamp(0.0, 1.0),
+            green.clamp(0.0, 1.0),
+            blue.clamp(0.0, 1.0),
+            alpha,
+        ))
+    }
+}
+
+/// A gradient with any number of color stops at arbitrary positions.
+///
+/// Sampling the gradient finds the two stops surrounding the sampled position and mixes
+/// between them. Before the first stop and after the last one, the color of that stop is
+/// used. Two stops at the same position create a hard transition between their colors.
+///
+/// ```
+/// # use bevy_color::{palettes::basic::*, ColorRange, Gradient};
+/// let gradient = Gradient::new([(0.0, RED), (0.25, LIME), (1.0, BLUE)]).unwrap();
+/// assert_eq!(gradient.at(0.25), LIME);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient<T: Mix> {
+    stops: Vec<(f32, T)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Mix> Gradient<T> {
+    /// Creates a new [`Gradient`] from `(position, color)` stops, which are sorted by
+    /// position. Stops at the same position keep their relative order.
+    ///
+    /// This fails if there are no stops, or if any position is NaN.
+    pub fn new(stops: impl IntoIterator<Item = (f32, T)>) -> Result<Self, GradientError> {
+        let mut stops: Vec<_> = stops.into_iter().collect();
+        if stops.is_empty() {
+            return Err(GradientError::NoStops);
+        }
+        if stops.iter().any(|(position, _)| position.is_nan()) {
+            return Err(GradientError::NanPosition);
+        }
+        stops.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Ok(Self { stops })
+    }
+
+    /// Returns the stops of the gradient, sorted by position.
+    pub fn stops(&self) -> &[(f32, T)] {
+        &self.stops
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Mix + Clone> ColorRange<T> for Gradient<T> {
+    fn at(&self, factor: f32) -> T {
+        self.sample_with(factor, T::mix)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Mix + Clone> Gradient<T> {
+    /// Samples the gradient like [`ColorRange::at`], mixing the surrounding stops with `mix`.
+    fn sample_with(&self, factor: f32, mix: impl Fn(&T, &T, f32) -> T) -> T {
+        let factor = factor.clamp(0.0, 1.0);
+        // The index of the first stop after `factor`.
+        let next = self
+            .stops
+            .partition_point(|(position, _)| *position <= factor);
+        if next == 0 {
+            return self.stops[0].1.clone();
+        }
+        let Some((end, end_color)) = self.stops.get(next) else {
+            return self.stops[next - 1].1.clone();
+        };
+        let (start, start_color) = &self.stops[next - 1];
+        mix(start_color, end_color, (factor - start) / (end - start))
+    }
+}
+
+/// An error creating a [`Gradient`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum GradientError {
+    /// The gradient has no stops.
+    #[error("A gradient needs at least one stop")]
+    NoStops,
+    /// The position of a stop is NaN.
+    #[error("The position of a gradient stop is NaN")]
+    NanPosition,
+}
+
+/// A [`ColorRange`] adapter that interpolates the colors of the wrapped range in the color
+/// space `S`, regardless of the type of its colors.
+///
+/// Mixing colors in the space they are stored in isn't always desirable. For example, the
+/// midpoint of a range between two [`Srgba`](crate::Srgba) colors is often darker and muddier
+/// than either of them, while mixing in [`Oklaba`](crate::Oklaba) gives perceptually even
+/// results. The colors surrounding the sampled position are converted to `S`, mixed there,
+/// and the result is converted back.
+///
+/// ```
+/// # use bevy_color::{palettes::basic::{LIME, RED}, ColorRange, InSpace, Oklaba};
+/// let range = InSpace::<Oklaba, _>::new(RED..LIME);
+/// let midpoint = range.at(0.5);
+/// ```
+pub struct InSpace<S, R> {
+    /// The range whose colors are interpolated in `S`.
+    pub range: R,
+    space: PhantomData<fn() -> S>,
+}
+
+impl<S, R> InSpace<S, R> {
+    /// Creates a new [`InSpace`] interpolating the colors of `range` in the color space `S`.
+    pub const fn new(range: R) -> Self {
+        Self {
+            range,
+            space: PhantomData,
+        }
+    }
+}
+
+impl<S, R: Clone> Clone for InSpace<S, R> {
+    fn clone(&self) -> Self {
+        Self::new(self.range.clone())
+    }
+}
+
+impl<S, R: core::fmt::Debug> core::fmt::Debug for InSpace<S, R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InSpace")
+            .field("space", &core::any::type_name::<S>())
+            .field("range", &self.range)
+            .finish()
+    }
+}
+
+/// Mixes two colors in the color space `S`.
+fn mix_in<S: Mix, T: Clone + Into<S> + From<S>>(start: &T, end: &T, factor: f32) -> T {
+    let start: S = start.clone().into();
+    T::from(start.mix(&end.clone().into(), factor))
+}
+
+impl<S, T> ColorRange<T> for InSpace<S, Range<T>>
+where
+    S: Mix,
+    T: Mix + Clone + Into<S> + From<S>,
+{
+    fn at(&self, factor: f32) -> T {
+        mix_in::<S, T>(&self.range.start, &self.range.end, factor.clamp(0.0, 1.0))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, T> ColorRange<T> for InSpace<S, Gradient<T>>
+where
+    S: Mix,
+    T: Mix + Clone + Into<S> + From<S>,
+{
+    fn at(&self, factor: f32) -> T {
+        self.range.sample_with(factor, mix_in::<S, T>)
+    }
+}
+
+/// A two-dimensional color field, defined by blending between two [`ColorRange`]s.
+///
+/// The first parameter `u` selects a color along each range, and the second parameter `v`
+/// mixes between the color sampled from `low` (at 0.0) and from `high` (at 1.0). This is
+/// useful for things like biome colors that depend on two inputs, such as temperature and
+/// humidity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BilinearColorField<T: Mix> {
+    /// The range sampled when `v` is 0.0.
+    pub low: Range<T>,
+    /// The range sampled when `v` is 1.0.
+    pub high: Range<T>,
+}
+
+impl<T: Mix> BilinearColorField<T> {
+    /// Creates a new [`BilinearColorField`] blending from the `low` range to the `high` range.
+    pub const fn new(low: Range<T>, high: Range<T>) -> Self {
+        Self { low, high }
+    }
+
+    /// Creates a new [`BilinearColorField`] from the colors at its four corners.
+    pub const fn from_corners(low_start: T, low_end: T, high_start: T, high_end: T) -> Self {
+        Self::new(low_start..low_end, high_start..high_end)
+    }
+
+    /// Get the color value at the given interpolation factors, which should both be between
+    /// 0.0 and 1.0.
+    ///
+    /// Both ranges are sampled at `u`, and the results are mixed by `v`.
+    pub fn at2(&self, u: f32, v: f32) -> T {
+        self.low.at(u).mix(&self.high.at(u), v.clamp(0.0, 1.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{palettes::basic, LinearRgba, Srgba};
+    use crate::{
+        palettes::basic, testing::assert_approx_eq, Hsla, LinearRgba, Oklaba, Oklcha, Srgba,
+    };
 
     #[test]
     fn test_color_range() {
@@ -43,4 +405,203 @@ mod tests {
         assert_eq!(range.at(1.0), lblue);
         assert_eq!(range.at(1.5), lblue);
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_samples() {
+        let range = basic::RED..basic::BLUE;
+        assert!(range.samples(0).is_empty());
+        assert_eq!(range.samples_iter(0).len(), 0);
+
+        assert_eq!(range.samples(1), [basic::RED]);
+
+        assert_eq!(range.samples(2), [basic::RED, basic::BLUE]);
+        assert_eq!(
+            range.samples(5),
+            [
+                basic::RED,
+                Srgba::new(0.75, 0.0, 0.25, 1.0),
+                Srgba::new(0.5, 0.0, 0.5, 1.0),
+                Srgba::new(0.25, 0.0, 0.75, 1.0),
+                basic::BLUE,
+            ]
+        );
+        assert!(range.samples_iter(5).eq(range.samples(5)));
+    }
+
+    #[test]
+    fn test_hue_range() {
+        let hue_at = |interpolation, factor| {
+            HueRange::new(
+                Hsla::hsl(350.0, 1.0, 0.5),
+                Hsla::hsl(10.0, 1.0, 0.5),
+                interpolation,
+            )
+            .at(factor)
+            .hue
+        };
+
+        // The shorter arc passes through 0°, not 180°.
+        assert_approx_eq!(hue_at(HueInterpolation::Shorter, 0.25), 355.0, 1e-3);
+        assert_approx_eq!(hue_at(HueInterpolation::Shorter, 0.5), 0.0, 1e-3);
+        assert_approx_eq!(hue_at(HueInterpolation::Shorter, 0.75), 5.0, 1e-3);
+        assert_approx_eq!(hue_at(HueInterpolation::Increasing, 0.5), 0.0, 1e-3);
+
+        // The longer arc passes through 180°.
+        assert_approx_eq!(hue_at(HueInterpolation::Longer, 0.25), 265.0, 1e-3);
+        assert_approx_eq!(hue_at(HueInterpolation::Longer, 0.5), 180.0, 1e-3);
+        assert_approx_eq!(hue_at(HueInterpolation::Decreasing, 0.5), 180.0, 1e-3);
+
+        // The endpoints are kept, and factors are clamped.
+        for interpolation in [
+            HueInterpolation::Shorter,
+            HueInterpolation::Longer,
+            HueInterpolation::Increasing,
+            HueInterpolation::Decreasing,
+        ] {
+            assert_approx_eq!(hue_at(interpolation, -1.0), 350.0, 1e-3);
+            assert_approx_eq!(hue_at(interpolation, 2.0), 10.0, 1e-3);
+        }
+
+        // The default matches `Mix`.
+        let start = Oklcha::lch(0.7, 0.1, 20.0);
+        let end = Oklcha::lch(0.5, 0.2, 300.0);
+        let range = HueRange::new(start, end, HueInterpolation::default());
+        assert_eq!(range.at(0.3), start.mix(&end, 0.3));
+
+        // Equal hues go all the way around with `Longer`.
+        let red = Hsla::hsl(0.0, 1.0, 0.5);
+        let rainbow = HueRange::new(red, red, HueInterpolation::Longer);
+        assert_approx_eq!(rainbow.at(0.25).hue, 90.0, 1e-3);
+        assert_eq!(
+            HueRange::new(red, red, HueInterpolation::Shorter).at(0.5),
+            red
+        );
+    }
+
+    #[test]
+    fn test_in_space() {
+        let lightness = |color: Srgba| Oklaba::from(color).lightness;
+
+        let srgb_midpoint = (basic::RED..basic::LIME).at(0.5);
+        let oklab_midpoint = InSpace::<Oklaba, _>::new(basic::RED..basic::LIME).at(0.5);
+        assert_eq!(srgb_midpoint, Srgba::new(0.5, 0.5, 0.0, 1.0));
+        assert_ne!(srgb_midpoint, oklab_midpoint);
+
+        // Mixing in Oklab keeps the perceived lightness halfway between the endpoints,
+        // while mixing in sRGB produces a darker, muddy midpoint.
+        let expected = (lightness(basic::RED) + lightness(basic::LIME)) / 2.0;
+        assert_approx_eq!(lightness(oklab_midpoint), expected, 1e-4);
+        assert!(lightness(srgb_midpoint) < expected - 0.05);
+
+        // Mixing in linear RGB and converting back brightens the midpoint too.
+        let linear_midpoint = InSpace::<LinearRgba, _>::new(basic::RED..basic::LIME).at(0.5);
+        assert_approx_eq!(linear_midpoint.red, 0.7354, 1e-3);
+        assert_approx_eq!(linear_midpoint.green, 0.7354, 1e-3);
+        assert_approx_eq!(linear_midpoint.blue, 0.0, 1e-4);
+
+        // The endpoints are preserved, and factors are clamped.
+        let range = InSpace::<Oklaba, _>::new(basic::RED..basic::LIME);
+        for (factor, expected) in [(-1.0, basic::RED), (0.0, basic::RED), (2.0, basic::LIME)] {
+            let color = range.at(factor);
+            assert_approx_eq!(color.red, expected.red, 1e-4);
+            assert_approx_eq!(color.green, expected.green, 1e-4);
+            assert_approx_eq!(color.blue, expected.blue, 1e-4);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_gradient_in_space() {
+        let gradient =
+            Gradient::new([(0.0, basic::RED), (0.5, basic::LIME), (1.0, basic::BLUE)]).unwrap();
+        let in_space = InSpace::<Oklaba, _>::new(gradient);
+        assert_eq!(
+            in_space.at(0.25),
+            InSpace::<Oklaba, _>::new(basic::RED..basic::LIME).at(0.5)
+        );
+        assert_eq!(
+            in_space.at(0.75),
+            InSpace::<Oklaba, _>::new(basic::LIME..basic::BLUE).at(0.5)
+        );
+    }
+
+    #[test]
+    fn test_gamut_clamped_color_range() {
+        let in_gamut = |color: LinearRgba| {
+            [color.red, color.green, color.blue]
+                .iter()
+                .all(|channel| (-1e-4..=1.0 + 1e-4).contains(channel))
+        };
+
+        // An HDR endpoint.
+        let hdr = LinearRgba::new(3.0, -0.5, 0.25, 1.0);
+        let range = GamutClampedColorRange::new(hdr..LinearRgba::BLUE);
+        assert!(!in_gamut((hdr..LinearRgba::BLUE).at(0.25)));
+        for i in 0..=10 {
+            assert!(in_gamut(range.at(i as f32 / 10.0)));
+        }
+        assert_eq!(range.at(0.0), LinearRgba::new(1.0, 0.0, 0.25, 1.0));
+
+        // A wide-gamut Oklab endpoint, whose chroma is beyond what sRGB can show.
+        let wide = Oklaba::new(0.6, 0.35, 0.1, 1.0);
+        assert!(!in_gamut(wide.into()));
+        let range = GamutClampedColorRange::new(wide..Oklaba::from(LinearRgba::WHITE));
+        for i in 0..=10 {
+            let color = range.at(i as f32 / 10.0);
+            assert!(in_gamut(color.into()), "{color:?}");
+            assert_eq!(color.alpha, 1.0);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_gradient() {
+        // The stops are given out of order to check that they are sorted.
+        let gradient =
+            Gradient::new([(1.0, basic::BLUE), (0.0, basic::RED), (0.25, basic::LIME)]).unwrap();
+
+        assert_eq!(gradient.at(0.0), basic::RED);
+        assert_eq!(gradient.at(0.125), Srgba::new(0.5, 0.5, 0.0, 1.0));
+        assert_eq!(gradient.at(0.25), basic::LIME);
+        assert_eq!(gradient.at(0.625), Srgba::new(0.0, 0.5, 0.5, 1.0));
+        assert_eq!(gradient.at(1.0), basic::BLUE);
+
+        // Factors outside of [0, 1] are clamped.
+        assert_eq!(gradient.at(-0.5), basic::RED);
+        assert_eq!(gradient.at(1.5), basic::BLUE);
+
+        // Positions before the first stop and after the last one use their colors.
+        let gradient =
+            Gradient::new([(0.25, basic::RED), (0.5, basic::LIME), (0.875, basic::BLUE)]).unwrap();
+        assert_eq!(gradient.at(0.1), basic::RED);
+        assert_eq!(gradient.at(0.375), Srgba::new(0.5, 0.5, 0.0, 1.0));
+        assert_eq!(gradient.at(0.95), basic::BLUE);
+
+        // A single stop is a solid color.
+        let gradient = Gradient::new([(0.5, basic::RED)]).unwrap();
+        assert_eq!(gradient.at(0.0), basic::RED);
+        assert_eq!(gradient.at(1.0), basic::RED);
+
+        assert_eq!(
+            Gradient::<Srgba>::new([]).unwrap_err(),
+            GradientError::NoStops
+        );
+        assert_eq!(
+            Gradient::new([(f32::NAN, basic::RED)]).unwrap_err(),
+            GradientError::NanPosition
+        );
+    }
+
+    #[test]
+    fn test_bilinear_color_field() {
+        let field =
+            BilinearColorField::from_corners(basic::RED, basic::BLUE, basic::LIME, basic::WHITE);
+        assert_eq!(field.at2(0.0, 0.0), basic::RED);
+        assert_eq!(field.at2(1.0, 0.0), basic::BLUE);
+        assert_eq!(field.at2(0.0, 1.0), basic::LIME);
+        assert_eq!(field.at2(1.0, 1.0), basic::WHITE);
+        assert_eq!(field.at2(-0.5, 1.5), basic::LIME);
+        assert_eq!(field.at2(0.5, 0.5), Srgba::new(0.5, 0.5, 0.5, 1.0));
+    }
 }
@@ -0,0 +1,9 @@
+use bevy_derive::WrapperFrom;
+
+#[derive(WrapperFrom)]
+//~^ ERROR: cannot be derived on field-less structs
+struct UnitStruct;
+
+#[derive(WrapperFrom)]
+//~^ ERROR: can only be derived on structs
+enum Enum {}
@@ -0,0 +1,13 @@
+use bevy_derive::WrapperFrom;
+use core::marker::PhantomData;
+
+#[derive(WrapperFrom)]
+//~^ ERROR: requires one field to have
+struct TupleStruct(usize, String);
+
+#[derive(WrapperFrom)]
+//~^ ERROR: requires one field to have
+struct Struct<T> {
+    foo: usize,
+    _phantom: PhantomData<T>,
+}
@@ -0,0 +1,27 @@
+//@check-pass
+use bevy_derive::WrapperFrom;
+use core::marker::PhantomData;
+
+#[derive(WrapperFrom)]
+struct TupleStruct<T>(#[deref] usize, PhantomData<T>);
+
+#[derive(WrapperFrom)]
+struct Struct<T: Clone> {
+    // Works with other attributes
+    #[cfg(test)]
+    foo: usize,
+    #[deref]
+    bar: String,
+    /// Other fields are initialized with their default value.
+    baz: i32,
+    _phantom: PhantomData<T>,
+}
+
+fn main() {
+    let value = TupleStruct::<String>::from(123);
+    let _: usize = value.0;
+
+    let value: Struct<u8> = "Hello world!".to_string().into();
+    let _: String = value.bar;
+    let _: i32 = value.baz;
+}
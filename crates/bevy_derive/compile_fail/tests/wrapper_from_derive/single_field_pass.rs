@@ -0,0 +1,18 @@
+//@check-pass
+use bevy_derive::WrapperFrom;
+
+#[derive(WrapperFrom)]
+struct TupleStruct(String);
+
+#[derive(WrapperFrom)]
+struct Struct {
+    bar: String,
+}
+
+fn main() {
+    let value = TupleStruct::from("Hello world!".to_string());
+    let _: String = value.0;
+
+    let value: Struct = "Hello world!".to_string().into();
+    let _: String = value.bar;
+}
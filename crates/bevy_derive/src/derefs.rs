@@ -4,13 +4,14 @@ use syn::{parse_macro_input, Data, DeriveInput, Field, Index, Member, Type};
 
 const DEREF: &str = "Deref";
 const DEREF_MUT: &str = "DerefMut";
+const WRAPPER_FROM: &str = "WrapperFrom";
 const DEREF_ATTR: &str = "deref";
 
 pub fn derive_deref(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
 
     let ident = &ast.ident;
-    let (field_member, field_type) = match get_deref_field(&ast, false) {
+    let (field_member, field_type) = match get_deref_field(&ast, DEREF) {
         Ok(items) => items,
         Err(err) => {
             return err.into_compile_error().into();
@@ -33,7 +34,7 @@ pub fn derive_deref_mut(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
 
     let ident = &ast.ident;
-    let (field_member, _) = match get_deref_field(&ast, true) {
+    let (field_member, _) = match get_deref_field(&ast, DEREF_MUT) {
         Ok(items) => items,
         Err(err) => {
             return err.into_compile_error().into();
@@ -50,8 +51,49 @@ pub fn derive_deref_mut(input: TokenStream) -> TokenStream {
     })
 }
 
-fn get_deref_field(ast: &DeriveInput, is_mut: bool) -> syn::Result<(Member, &Type)> {
-    let deref_kind = if is_mut { DEREF_MUT } else { DEREF };
+pub fn derive_wrapper_from(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    let ident = &ast.ident;
+    let (field_member, field_type) = match get_deref_field(&ast, WRAPPER_FROM) {
+        Ok(items) => items,
+        Err(err) => {
+            return err.into_compile_error().into();
+        }
+    };
+    // `get_deref_field` only succeeds on structs.
+    let Data::Struct(data_struct) = &ast.data else {
+        unreachable!()
+    };
+    let other_fields = data_struct
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| (to_member(field, index), field))
+        .filter(|(member, _)| *member != field_member)
+        .map(|(member, field)| {
+            // Keep the fields that are conditionally compiled in sync with the struct.
+            let cfgs = field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("cfg"));
+            quote! { #(#cfgs)* #member: ::core::default::Default::default() }
+        });
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    TokenStream::from(quote! {
+        impl #impl_generics ::core::convert::From<#field_type> for #ident #ty_generics #where_clause {
+            fn from(value: #field_type) -> Self {
+                Self {
+                    #field_member: value,
+                    #(#other_fields,)*
+                }
+            }
+        }
+    })
+}
+
+fn get_deref_field<'a>(ast: &'a DeriveInput, deref_kind: &str) -> syn::Result<(Member, &'a Type)> {
     let deref_attr_str = format!("`#[{DEREF_ATTR}]`");
 
     match &ast.data {
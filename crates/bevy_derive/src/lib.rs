@@ -188,6 +188,50 @@ pub fn derive_deref_mut(input: TokenStream) -> TokenStream {
     derefs::derive_deref_mut(input)
 }
 
+/// Implements [`From`] for a struct, from its inner type. This is especially useful when utilizing the
+/// [newtype] pattern, alongside [`Deref`] and [`DerefMut`].
+///
+/// For single-field structs, the implementation automatically uses that field.
+/// For multi-field structs, you must specify which field to use with the `#[deref]` attribute,
+/// like for [`Deref`]. The other fields are initialized with their [`Default`] value.
+///
+/// # Example
+///
+/// ```
+/// use bevy_derive::WrapperFrom;
+///
+/// #[derive(WrapperFrom)]
+/// struct Meters(f32);
+///
+/// let distance = Meters::from(12.5);
+/// assert_eq!(12.5, distance.0);
+/// ```
+///
+/// Using a multi-field struct:
+///
+/// ```
+/// # use std::marker::PhantomData;
+/// use bevy_derive::WrapperFrom;
+///
+/// #[derive(WrapperFrom)]
+/// struct Id<T> {
+///   #[deref]
+///   index: u32,
+///   _phantom: PhantomData<T>,
+/// }
+///
+/// let id: Id<String> = 7.into();
+/// assert_eq!(7, id.index);
+/// ```
+///
+/// [newtype]: https://doc.rust-lang.org/rust-by-example/generics/new_types.html
+/// [`Deref`]: std::ops::Deref
+/// [`DerefMut`]: std::ops::DerefMut
+#[proc_macro_derive(WrapperFrom, attributes(deref))]
+pub fn derive_wrapper_from(input: TokenStream) -> TokenStream {
+    derefs::derive_wrapper_from(input)
+}
+
 #[proc_macro_attribute]
 pub fn bevy_main(attr: TokenStream, item: TokenStream) -> TokenStream {
     bevy_main::bevy_main(attr, item)
@@ -80,7 +80,7 @@ use crate::{
     storage::{SparseSetIndex, TableId, TableRow},
 };
 use alloc::vec::Vec;
-use bevy_platform::sync::atomic::Ordering;
+use bevy_platform::{collections::HashSet, sync::atomic::Ordering};
 use core::{
     fmt,
     hash::Hash,
@@ -824,6 +824,42 @@ impl Entities {
         }
     }
 
+    /// Allocates the rows of the given entities, which must have been freed and not allocated
+    /// again since.
+    ///
+    /// Each row keeps the generation it was given when it was freed, so each entity must be the
+    /// last one that was freed with its row. Returns the first entity that doesn't meet this, or
+    /// whose row is given more than once, without allocating anything in that case.
+    ///
+    /// Must not be called while reserved entities are awaiting `flush()`.
+    pub(crate) fn alloc_freed(&mut self, entities: &[Entity]) -> Result<Vec<Entity>, Entity> {
+        self.verify_flushed();
+
+        let free: HashSet<EntityRow> = self.pending.iter().copied().collect();
+        let mut requested = HashSet::<EntityRow>::default();
+        requested.reserve(entities.len());
+        for &entity in entities {
+            let is_last_freed = self.meta[entity.index() as usize].generation
+                == entity.generation().after_versions(1);
+            if !is_last_freed || !free.contains(&entity.row()) || !requested.insert(entity.row()) {
+                return Err(entity);
+            }
+        }
+
+        self.pending.retain(|row| !requested.contains(row));
+        let new_free_cursor = self.pending.len() as IdCursor;
+        *self.free_cursor.get_mut() = new_free_cursor;
+        Ok(entities
+            .iter()
+            .map(|entity| {
+                Entity::from_raw_and_generation(
+                    entity.row(),
+                    self.meta[entity.index() as usize].generation,
+                )
+            })
+            .collect())
+    }
+
     /// Destroy an entity, allowing it to be reused.
     ///
     /// Must not be called while reserved entities are awaiting `flush()`.
@@ -29,6 +29,14 @@ pub struct TryInsertBatchError {
     pub entities: Vec<Entity>,
 }
 
+/// The error type returned by [`World::spawn_batch_with_ids`] if the ID of one of the provided
+/// entities isn't free to be spawned again.
+///
+/// [`World::spawn_batch_with_ids`]: crate::world::World::spawn_batch_with_ids
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Could not spawn an entity with the ID of {0} because it is not free: it is alive, was reused already, or was given more than once")]
+pub struct SpawnBatchWithIdsError(pub Entity);
+
 /// An error that occurs when a specified [`Entity`] could not be despawned.
 #[derive(thiserror::Error, Debug, Clone, Copy)]
 #[error("Could not despawn entity: {0}")]
@@ -56,7 +56,8 @@ use crate::{
     world::{
         command_queue::RawCommandQueue,
         error::{
-            EntityDespawnError, EntityMutableFetchError, TryInsertBatchError, TryRunScheduleError,
+            EntityDespawnError, EntityMutableFetchError, SpawnBatchWithIdsError,
+            TryInsertBatchError, TryRunScheduleError,
         },
     },
 };
@@ -1242,6 +1243,70 @@ impl World {
         SpawnBatchIter::new(self, iter.into_iter(), MaybeLocation::caller())
     }
 
+    /// Spawns a batch of entities with the same component [`Bundle`] type, reusing the IDs of the
+    /// given despawned entities instead of allocating new ones.
+    ///
+    /// Each spawned entity has the same [index](Entity::index) as the despawned entity it
+    /// replaces, with the next generation of that index. This keeps the indices of pooled
+    /// entities dense, for example to index into external arrays. The spawned entities are
+    /// returned in the order of the batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SpawnBatchWithIdsError`] without spawning anything if the ID of any of the
+    /// given entities isn't free, because the entity is alive, its ID was reused already, even if
+    /// that entity was despawned again since, or it appears more than once in the batch.
+    ///
+    /// ```
+    /// use bevy_ecs::{component::Component, world::World};
+    ///
+    /// #[derive(Component)]
+    /// struct Bullet;
+    ///
+    /// let mut world = World::new();
+    /// let bullets: Vec<_> = world.spawn_batch([Bullet, Bullet]).collect();
+    /// for &bullet in &bullets {
+    ///     world.despawn(bullet);
+    /// }
+    ///
+    /// let respawned = world
+    ///     .spawn_batch_with_ids(bullets.iter().map(|&bullet| (bullet, Bullet)))
+    ///     .unwrap();
+    /// assert_eq!(respawned[0].index(), bullets[0].index());
+    /// assert_ne!(respawned[0], bullets[0]);
+    /// ```
+    #[track_caller]
+    pub fn spawn_batch_with_ids<I, B>(
+        &mut self,
+        batch: I,
+    ) -> Result<Vec<Entity>, SpawnBatchWithIdsError>
+    where
+        I: IntoIterator<Item = (Entity, B)>,
+        B: Bundle<Effect: NoBundleEffect>,
+    {
+        let caller = MaybeLocation::caller();
+        self.flush();
+
+        let (entities, bundles): (Vec<Entity>, Vec<B>) = batch.into_iter().unzip();
+        let spawned = self
+            .entities
+            .alloc_freed(&entities)
+            .map_err(SpawnBatchWithIdsError)?;
+
+        let change_tick = self.change_tick();
+        let mut spawner = BundleSpawner::new::<B>(self, change_tick);
+        spawner.reserve_storage(spawned.len());
+        for (&entity, bundle) in spawned.iter().zip(bundles) {
+            // SAFETY: `entity` was just allocated and doesn't exist yet, and `bundle` is of the
+            // type of the spawner.
+            unsafe { spawner.spawn_non_existent(entity, bundle, caller) };
+        }
+        // SAFETY: `spawner` is dropped right after.
+        unsafe { spawner.flush_commands() };
+
+        Ok(spawned)
+    }
+
     /// Retrieves a reference to the given `entity`'s [`Component`] of the given type.
     /// Returns `None` if the `entity` does not have a [`Component`] of the given type.
     /// ```
@@ -3598,11 +3663,14 @@ mod tests {
     use crate::{
         change_detection::{DetectChangesMut, MaybeLocation},
         component::{ComponentCloneBehavior, ComponentDescriptor, ComponentInfo, StorageType},
-        entity::EntityHashSet,
+        entity::{Entity, EntityHashSet},
         entity_disabling::{DefaultQueryFilters, Disabled},
         ptr::OwningPtr,
         resource::Resource,
-        world::{error::EntityMutableFetchError, DeferredWorld},
+        world::{
+            error::{EntityMutableFetchError, SpawnBatchWithIdsError},
+            DeferredWorld,
+        },
     };
     use alloc::{
         borrow::ToOwned,
@@ -3965,6 +4033,67 @@ mod tests {
     #[derive(Component)]
     struct Baz;
 
+    #[test]
+    fn spawn_batch_with_ids() {
+        let mut world = World::new();
+        let entities: Vec<Entity> = world.spawn_batch([Foo, Foo, Foo]).collect();
+        for &entity in &entities {
+            world.despawn(entity);
+        }
+
+        let respawned = world
+            .spawn_batch_with_ids(entities.iter().map(|&entity| (entity, (Foo, Bar))))
+            .unwrap();
+        assert_eq!(respawned.len(), entities.len());
+        for (old, new) in entities.iter().zip(&respawned) {
+            assert_eq!(old.index(), new.index());
+            assert!(new.generation() > old.generation());
+            assert!(world.entity(*new).contains::<Foo>());
+            assert!(world.entity(*new).contains::<Bar>());
+        }
+
+        // Alive entities can't be reused.
+        let alive = respawned[0];
+        assert_eq!(
+            world.spawn_batch_with_ids([(alive, Baz)]),
+            Err(SpawnBatchWithIdsError(alive))
+        );
+
+        // Neither can the same id twice in one batch.
+        world.despawn(alive);
+        assert_eq!(
+            world.spawn_batch_with_ids([(alive, Baz), (alive, Baz)]),
+            Err(SpawnBatchWithIdsError(alive))
+        );
+        assert_eq!(world.query::<&Baz>().iter(&world).count(), 0);
+
+        // The rejected id is still free and isn't handed out twice.
+        let spawned = world.spawn(Baz).id();
+        assert_eq!(spawned.index(), alive.index());
+        let other = world.spawn(Baz).id();
+        assert!(respawned
+            .iter()
+            .all(|entity| entity.index() != other.index()));
+
+        // Stale entities can't be reused, even once the entity that reused their id is despawned.
+        let stale = world.spawn(Foo).id();
+        world.despawn(stale);
+        let reused = world.spawn(Foo).id();
+        assert_eq!(reused.index(), stale.index());
+        world.despawn(reused);
+        assert_eq!(
+            world.spawn_batch_with_ids([(stale, Baz)]),
+            Err(SpawnBatchWithIdsError(stale))
+        );
+        assert_eq!(
+            world.spawn_batch_with_ids([(reused, Baz)]),
+            Ok(vec![Entity::from_raw_and_generation(
+                reused.row(),
+                reused.generation().after_versions(1)
+            )])
+        );
+    }
+
     #[test]
     fn inspect_entity_components() {
         let mut world = World::new();
@@ -28,6 +28,8 @@ use bevy_platform::collections::HashMap;
 use gilrs::GilrsBuilder;
 use gilrs_system::{gilrs_event_startup_system, gilrs_event_system};
 use rumble::{play_gilrs_rumble, RunningRumbleEffects};
+
+pub use rumble::{Rumble, RunningRumbles};
 use tracing::error;
 
 #[cfg(target_arch = "wasm32")]
@@ -1,15 +1,19 @@
 //! Handle user specified rumble request events.
 use crate::{Gilrs, GilrsGamepads};
-use bevy_ecs::prelude::{EventReader, Res, ResMut, Resource};
-use bevy_input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
-use bevy_platform::collections::HashMap;
+use bevy_ecs::{
+    component::{Component, HookContext},
+    entity::{Entity, EntityHashMap},
+    prelude::{EventReader, Res, ResMut, Resource},
+    system::SystemParam,
+    world::DeferredWorld,
+};
+use bevy_input::gamepad::{
+    GamepadRumbleEnvelope, GamepadRumbleIntensity, GamepadRumbleRequest, GamepadRumbleScale,
+};
 use bevy_time::{Real, Time};
 use bevy_utils::synccell::SyncCell;
 use core::time::Duration;
-use gilrs::{
-    ff::{self, BaseEffect, BaseEffectType, Repeat, Replay},
-    GamepadId,
-};
+use gilrs::ff::{self, BaseEffect, BaseEffectType, Envelope, Repeat, Replay, Ticks};
 use thiserror::Error;
 use tracing::{debug, warn};
 
@@ -40,7 +44,136 @@ enum RumbleError {
 pub(crate) struct RunningRumbleEffects {
     /// If multiple rumbles are running at the same time, their resulting rumble
     /// will be the saturated sum of their strengths up until [`u16::MAX`]
-    rumbles: HashMap<GamepadId, Vec<RunningRumble>>,
+    rumbles: EntityHashMap<Vec<RunningRumble>>,
+}
+
+impl RunningRumbleEffects {
+    /// Returns each gamepad with running rumbles, and how long after `current_time` the last of
+    /// them finishes.
+    fn remaining(&self, current_time: Duration) -> impl Iterator<Item = (Entity, Duration)> + '_ {
+        self.rumbles.iter().filter_map(move |(&gamepad, rumbles)| {
+            let deadline = rumbles.iter().map(|rumble| rumble.deadline).max()?;
+            let remaining = deadline.checked_sub(current_time)?;
+            (!remaining.is_zero()).then_some((gamepad, remaining))
+        })
+    }
+}
+
+/// A [`SystemParam`] for reading which gamepads are currently rumbling, and for how long.
+///
+/// This only reports rumbles added with [`GamepadRumbleRequest::Add`], and can't change them.
+/// To stop the rumbles of a gamepad, send a [`GamepadRumbleRequest::Stop`] instead.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_gilrs::RunningRumbles;
+/// fn log_rumbles(rumbles: RunningRumbles) {
+///     for (gamepad, remaining) in rumbles.iter() {
+///         println!("{gamepad} rumbles for another {remaining:?}");
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(log_rumbles);
+/// ```
+#[derive(SystemParam)]
+pub struct RunningRumbles<'w> {
+    time: Res<'w, Time<Real>>,
+    effects: Option<Res<'w, RunningRumbleEffects>>,
+}
+
+impl RunningRumbles<'_> {
+    /// Returns each gamepad [`Entity`] that is currently rumbling, and how long until its last
+    /// running rumble finishes.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Duration)> + '_ {
+        let current_time = self.time.elapsed();
+        self.effects
+            .iter()
+            .flat_map(move |effects| effects.remaining(current_time))
+    }
+
+    /// Returns how long until the last running rumble of `gamepad` finishes, or `None` if it
+    /// isn't rumbling.
+    pub fn remaining(&self, gamepad: Entity) -> Option<Duration> {
+        self.iter()
+            .find_map(|(entity, remaining)| (entity == gamepad).then_some(remaining))
+    }
+
+    /// Returns `true` if `gamepad` is currently rumbling.
+    pub fn is_rumbling(&self, gamepad: Entity) -> bool {
+        self.remaining(gamepad).is_some()
+    }
+}
+
+/// Rumbles a gamepad for as long as this component is on its entity.
+///
+/// Inserting it sends a [`GamepadRumbleRequest::Add`] for [`gamepad`](Self::gamepad), and
+/// removing it, replacing it or despawning its entity sends a [`GamepadRumbleRequest::Stop`].
+/// This ties a rumble to the lifetime of the entity emitting it, such as an explosion, instead of
+/// having to stop it by hand.
+///
+/// Like [`GamepadRumbleRequest::Stop`], removing it stops every rumble of the gamepad, including
+/// those requested by other emitters or events.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_gilrs::Rumble;
+/// # use bevy_input::gamepad::{Gamepad, GamepadRumbleIntensity};
+/// # use core::time::Duration;
+/// fn rumble_on_explosion(mut commands: Commands, gamepads: Query<Entity, With<Gamepad>>) {
+///     for gamepad in &gamepads {
+///         // The rumble stops early if the explosion is despawned before it finishes.
+///         commands.spawn(Rumble::new(
+///             gamepad,
+///             GamepadRumbleIntensity::MAX,
+///             Duration::from_secs(1),
+///         ));
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(rumble_on_explosion);
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[component(on_insert = start_rumble, on_replace = stop_rumble)]
+pub struct Rumble {
+    /// The gamepad to rumble.
+    pub gamepad: Entity,
+    /// How intense the rumble should be.
+    pub intensity: GamepadRumbleIntensity,
+    /// How long the gamepad should rumble, unless the component is removed first.
+    pub duration: Duration,
+}
+
+impl Rumble {
+    /// Creates a rumble of `gamepad` at the given `intensity`, for `duration`.
+    pub fn new(gamepad: Entity, intensity: GamepadRumbleIntensity, duration: Duration) -> Self {
+        Self {
+            gamepad,
+            intensity,
+            duration,
+        }
+    }
+}
+
+fn start_rumble(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(&Rumble {
+        gamepad,
+        intensity,
+        duration,
+    }) = world.get::<Rumble>(entity)
+    else {
+        return;
+    };
+    world.send_event(GamepadRumbleRequest::Add {
+        duration,
+        intensity,
+        gamepad,
+        envelope: None,
+    });
+}
+
+fn stop_rumble(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(&Rumble { gamepad, .. }) = world.get::<Rumble>(entity) else {
+        return;
+    };
+    world.send_event(GamepadRumbleRequest::Stop { gamepad });
 }
 
 /// gilrs uses magnitudes from 0 to [`u16::MAX`], while ours go from `0.0` to `1.0` ([`f32`])
@@ -48,13 +181,39 @@ fn to_gilrs_magnitude(ratio: f32) -> u16 {
     (ratio * u16::MAX as f32) as u16
 }
 
+/// Converts our envelope into a gilrs one for an effect that plays for `duration`.
+///
+/// gilrs requires the attack and fade to end before the effect does, so they are shortened to
+/// fit if necessary, with the fade taking precedence.
+fn to_gilrs_envelope(envelope: Option<GamepadRumbleEnvelope>, duration: Duration) -> Envelope {
+    let Some(envelope) = envelope else {
+        return Envelope::default();
+    };
+    let tick = Ticks::from_ms(1);
+    let play_for = Ticks::from(duration);
+    if play_for <= tick {
+        return Envelope::default();
+    }
+    let max_length = play_for - tick;
+    let fade_length = Ticks::from(envelope.release).min(max_length);
+    let attack_length = Ticks::from(envelope.attack).min(max_length - fade_length);
+    Envelope {
+        attack_length,
+        attack_level: envelope.attack_level.clamp(0.0, 1.0),
+        fade_length,
+        fade_level: envelope.release_level.clamp(0.0, 1.0),
+    }
+}
+
 fn get_base_effects(
     GamepadRumbleIntensity {
         weak_motor,
         strong_motor,
     }: GamepadRumbleIntensity,
     duration: Duration,
+    envelope: Option<GamepadRumbleEnvelope>,
 ) -> Vec<BaseEffect> {
+    let envelope = to_gilrs_envelope(envelope, duration);
     let mut effects = Vec::new();
     if strong_motor > 0. {
         effects.push(BaseEffect {
@@ -65,15 +224,19 @@ fn get_base_effects(
                 play_for: duration.into(),
                 ..Default::default()
             },
-            ..Default::default()
+            envelope,
         });
     }
     if weak_motor > 0. {
         effects.push(BaseEffect {
-            kind: BaseEffectType::Strong {
+            kind: BaseEffectType::Weak {
                 magnitude: to_gilrs_magnitude(weak_motor),
             },
-            ..Default::default()
+            scheduling: Replay {
+                play_for: duration.into(),
+                ..Default::default()
+            },
+            envelope,
         });
     }
     effects
@@ -84,9 +247,19 @@ fn handle_rumble_request(
     gilrs: &mut gilrs::Gilrs,
     gamepads: &GilrsGamepads,
     rumble: GamepadRumbleRequest,
+    scale: GamepadRumbleScale,
     current_time: Duration,
 ) -> Result<(), RumbleError> {
-    let gamepad = rumble.gamepad();
+    let gamepad = match rumble {
+        GamepadRumbleRequest::Add { gamepad, .. } | GamepadRumbleRequest::Stop { gamepad } => {
+            gamepad
+        }
+        GamepadRumbleRequest::StopAll => {
+            // `ff::Effect` uses RAII, dropping = deactivating
+            running_rumbles.rumbles.clear();
+            return Ok(());
+        }
+    };
 
     let (gamepad_id, _) = gilrs
         .gamepads()
@@ -96,16 +269,24 @@ fn handle_rumble_request(
     match rumble {
         GamepadRumbleRequest::Stop { .. } => {
             // `ff::Effect` uses RAII, dropping = deactivating
-            running_rumbles.rumbles.remove(&gamepad_id);
+            running_rumbles.rumbles.remove(&gamepad);
         }
         GamepadRumbleRequest::Add {
             duration,
             intensity,
+            envelope,
             ..
         } => {
+            let base_effects = get_base_effects(scale.scale(intensity), duration, envelope);
+            // Don't start rumbles that wouldn't be felt, such as when rumble is scaled down to
+            // nothing.
+            if base_effects.is_empty() {
+                return Ok(());
+            }
+
             let mut effect_builder = ff::EffectBuilder::new();
 
-            for effect in get_base_effects(intensity, duration) {
+            for effect in base_effects {
                 effect_builder.add_effect(effect);
                 effect_builder.repeat(Repeat::For(duration.into()));
             }
@@ -113,13 +294,15 @@ fn handle_rumble_request(
             let effect = effect_builder.gamepads(&[gamepad_id]).finish(gilrs)?;
             effect.play()?;
 
-            let gamepad_rumbles = running_rumbles.rumbles.entry(gamepad_id).or_default();
+            let gamepad_rumbles = running_rumbles.rumbles.entry(gamepad).or_default();
             let deadline = current_time + duration;
             gamepad_rumbles.push(RunningRumble {
                 deadline,
                 effect: SyncCell::new(effect),
             });
         }
+        // Handled above, as it doesn't target a single gamepad.
+        GamepadRumbleRequest::StopAll => {}
     }
 
     Ok(())
@@ -130,6 +313,7 @@ pub(crate) fn play_gilrs_rumble(
     gamepads: Res<GilrsGamepads>,
     mut requests: EventReader<GamepadRumbleRequest>,
     mut running_rumbles: ResMut<RunningRumbleEffects>,
+    scale: Res<GamepadRumbleScale>,
 ) {
     gilrs.with(|gilrs| {
         let current_time = time.elapsed();
@@ -145,28 +329,71 @@ pub(crate) fn play_gilrs_rumble(
         // Add new effects.
         for rumble in requests.read().cloned() {
             let gamepad = rumble.gamepad();
-            match handle_rumble_request(&mut running_rumbles, gilrs, &gamepads, rumble, current_time) {
-                Ok(()) => {}
-                Err(RumbleError::GilrsError(err)) => {
+            let result = handle_rumble_request(
+                &mut running_rumbles,
+                gilrs,
+                &gamepads,
+                rumble,
+                *scale,
+                current_time,
+            );
+            // Only requests for a single gamepad can fail.
+            let (Err(err), Some(gamepad)) = (result, gamepad) else {
+                continue;
+            };
+            match err {
+                RumbleError::GilrsError(err) => {
                     if let ff::Error::FfNotSupported(_) = err {
                         debug!("Tried to rumble {gamepad:?}, but it doesn't support force feedback");
                     } else {
                         warn!(
-                        "Tried to handle rumble request for {gamepad:?} but an error occurred: {err}"
+                            "Tried to handle rumble request for {gamepad:?} but an error occurred: {err}"
                         );
                     }
                 }
-                Err(RumbleError::GamepadNotFound) => {
+                RumbleError::GamepadNotFound => {
                     warn!("Tried to handle rumble request {gamepad:?} but it doesn't exist!");
                 }
-            };
+            }
         }
     });
 }
 
 #[cfg(test)]
 mod tests {
-    use super::to_gilrs_magnitude;
+    use super::{
+        get_base_effects, handle_rumble_request, to_gilrs_envelope, to_gilrs_magnitude, Rumble,
+        RunningRumble, RunningRumbleEffects, RunningRumbles,
+    };
+    use crate::GilrsGamepads;
+    use bevy_ecs::{event::Events, system::RunSystemOnce, world::World};
+    use bevy_input::gamepad::{
+        GamepadRumbleEnvelope, GamepadRumbleIntensity, GamepadRumbleRequest, GamepadRumbleScale,
+    };
+    use bevy_time::{Real, Time};
+    use bevy_utils::synccell::SyncCell;
+    use core::time::Duration;
+    use gilrs::ff::{self, BaseEffect, BaseEffectType, Envelope, Ticks};
+
+    fn gilrs_context() -> gilrs::Gilrs {
+        // Without a platform backend, gilrs still returns a context that can create effects.
+        match gilrs::GilrsBuilder::new()
+            .with_default_filters(false)
+            .set_update_state(false)
+            .build()
+        {
+            Ok(gilrs) | Err(gilrs::Error::NotImplemented(gilrs)) => gilrs,
+            Err(err) => panic!("failed to create a gilrs context: {err}"),
+        }
+    }
+
+    /// Creates an effect that isn't played on any gamepad.
+    fn test_effect(gilrs: &mut gilrs::Gilrs) -> ff::Effect {
+        ff::EffectBuilder::new()
+            .add_effect(BaseEffect::default())
+            .finish(gilrs)
+            .unwrap()
+    }
 
     #[test]
     fn magnitude_conversion() {
@@ -182,4 +409,255 @@ mod tests {
         assert_eq!(to_gilrs_magnitude(-1.0), 0);
         assert_eq!(to_gilrs_magnitude(-0.1), 0);
     }
+
+    #[test]
+    fn base_effects_drive_the_requested_motors() {
+        let duration = Duration::from_millis(500);
+        let kinds = |intensity| {
+            get_base_effects(intensity, duration, None)
+                .into_iter()
+                .map(|effect| effect.kind)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            kinds(GamepadRumbleIntensity::WEAK_MAX),
+            [BaseEffectType::Weak {
+                magnitude: u16::MAX
+            }]
+        );
+        assert_eq!(
+            kinds(GamepadRumbleIntensity::STRONG_MAX),
+            [BaseEffectType::Strong {
+                magnitude: u16::MAX
+            }]
+        );
+        assert_eq!(
+            kinds(GamepadRumbleIntensity::MAX),
+            [
+                BaseEffectType::Strong {
+                    magnitude: u16::MAX
+                },
+                BaseEffectType::Weak {
+                    magnitude: u16::MAX
+                },
+            ]
+        );
+        assert!(kinds(GamepadRumbleIntensity::weak_motor(0.0)).is_empty());
+
+        // Both motors play for the whole duration of the request.
+        for effect in get_base_effects(GamepadRumbleIntensity::MAX, duration, None) {
+            assert_eq!(effect.scheduling.play_for, Ticks::from(duration));
+        }
+    }
+
+    #[test]
+    fn scaled_magnitudes() {
+        let duration = Duration::from_secs(1);
+        let intensity = GamepadRumbleIntensity {
+            strong_motor: 0.8,
+            weak_motor: 0.4,
+        };
+        let magnitudes = |scale: f32| {
+            get_base_effects(GamepadRumbleScale(scale).scale(intensity), duration, None)
+                .into_iter()
+                .map(|effect| match effect.kind {
+                    BaseEffectType::Strong { magnitude } | BaseEffectType::Weak { magnitude } => {
+                        magnitude
+                    }
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            magnitudes(1.0),
+            [to_gilrs_magnitude(0.8), to_gilrs_magnitude(0.4)]
+        );
+        assert_eq!(
+            magnitudes(0.5),
+            [to_gilrs_magnitude(0.4), to_gilrs_magnitude(0.2)]
+        );
+
+        // Scaled intensities are clamped to the maximum magnitude.
+        assert_eq!(magnitudes(2.0), [u16::MAX, to_gilrs_magnitude(0.8)]);
+
+        // A scale of zero disables rumble entirely, as does a negative one.
+        assert!(magnitudes(0.0).is_empty());
+        assert!(magnitudes(-1.0).is_empty());
+    }
+
+    #[test]
+    fn envelope_conversion() {
+        let duration = Duration::from_secs(2);
+        assert_eq!(to_gilrs_envelope(None, duration), Envelope::default());
+
+        let envelope = GamepadRumbleEnvelope {
+            attack: Duration::from_millis(500),
+            attack_level: 0.25,
+            release: Duration::from_secs(1),
+            release_level: 0.5,
+        };
+        assert_eq!(
+            to_gilrs_envelope(Some(envelope), duration),
+            Envelope {
+                attack_length: Ticks::from_ms(500),
+                attack_level: 0.25,
+                fade_length: Ticks::from_ms(1000),
+                fade_level: 0.5,
+            }
+        );
+
+        // levels outside of the `0.0` to `1.0` range don't make sense, but just make sure
+        // they convert to something sensible in gilrs anyway.
+        let envelope = GamepadRumbleEnvelope {
+            attack_level: -1.0,
+            release_level: 2.0,
+            ..envelope
+        };
+        let converted = to_gilrs_envelope(Some(envelope), duration);
+        assert_eq!(converted.attack_level, 0.0);
+        assert_eq!(converted.fade_level, 1.0);
+
+        // The attack and fade are shortened to end before the rumble does, with the fade
+        // taking precedence.
+        let envelope = GamepadRumbleEnvelope::new(Duration::from_secs(2), Duration::from_secs(3));
+        let converted = to_gilrs_envelope(Some(envelope), duration);
+        assert_eq!(converted.fade_length, Ticks::from_ms(1950));
+        assert_eq!(converted.attack_length, Ticks::default());
+        assert!(converted.attack_length + converted.fade_length < Ticks::from(duration));
+
+        // Rumbles too short for an envelope play without one.
+        assert_eq!(
+            to_gilrs_envelope(Some(envelope), Duration::from_millis(50)),
+            Envelope::default()
+        );
+
+        // The envelope is applied to both motors.
+        for effect in get_base_effects(GamepadRumbleIntensity::MAX, duration, Some(envelope)) {
+            assert_eq!(effect.envelope, converted);
+        }
+    }
+
+    #[test]
+    fn running_rumbles_report_remaining_time() {
+        let mut gilrs = gilrs_context();
+        let effect = test_effect(&mut gilrs);
+
+        let mut world = World::new();
+        world.init_resource::<Time<Real>>();
+        let gamepad = world.spawn_empty().id();
+        let other_gamepad = world.spawn_empty().id();
+
+        let duration = Duration::from_secs(1);
+        let mut running_rumbles = RunningRumbleEffects::default();
+        running_rumbles.rumbles.insert(
+            gamepad,
+            vec![RunningRumble {
+                deadline: duration,
+                effect: SyncCell::new(effect),
+            }],
+        );
+        world.insert_resource(running_rumbles);
+
+        let remaining = |world: &mut World| {
+            world
+                .run_system_once(move |rumbles: RunningRumbles| {
+                    assert_eq!(
+                        rumbles.iter().count(),
+                        rumbles.remaining(gamepad).iter().count()
+                    );
+                    assert!(!rumbles.is_rumbling(other_gamepad));
+                    rumbles.remaining(gamepad)
+                })
+                .unwrap()
+        };
+
+        assert_eq!(remaining(&mut world), Some(duration));
+
+        world
+            .resource_mut::<Time<Real>>()
+            .advance_by(Duration::from_millis(250));
+        let left = remaining(&mut world).unwrap();
+        assert!(!left.is_zero() && left < duration, "{left:?}");
+
+        world.resource_mut::<Time<Real>>().advance_by(duration);
+        assert_eq!(remaining(&mut world), None);
+    }
+
+    #[test]
+    fn stop_all_clears_running_rumbles() {
+        let mut gilrs = gilrs_context();
+        let mut world = World::new();
+        let mut running_rumbles = RunningRumbleEffects::default();
+        for _ in 0..2 {
+            let gamepad = world.spawn_empty().id();
+            running_rumbles.rumbles.insert(
+                gamepad,
+                vec![RunningRumble {
+                    deadline: Duration::from_secs(1),
+                    effect: SyncCell::new(test_effect(&mut gilrs)),
+                }],
+            );
+        }
+
+        handle_rumble_request(
+            &mut running_rumbles,
+            &mut gilrs,
+            &GilrsGamepads::default(),
+            GamepadRumbleRequest::StopAll,
+            GamepadRumbleScale::default(),
+            Duration::ZERO,
+        )
+        .unwrap();
+        assert!(running_rumbles.rumbles.is_empty());
+    }
+
+    #[test]
+    fn rumble_component_follows_entity_lifetime() {
+        let mut world = World::new();
+        world.init_resource::<Events<GamepadRumbleRequest>>();
+        let gamepad = world.spawn_empty().id();
+        let drain = |world: &mut World| {
+            world
+                .resource_mut::<Events<GamepadRumbleRequest>>()
+                .drain()
+                .collect::<Vec<_>>()
+        };
+
+        let duration = Duration::from_secs(1);
+        let emitter = world
+            .spawn(Rumble::new(gamepad, GamepadRumbleIntensity::MAX, duration))
+            .id();
+        let requests = drain(&mut world);
+        assert!(matches!(
+            requests[..],
+            [GamepadRumbleRequest::Add {
+                gamepad: requested,
+                duration: requested_duration,
+                intensity: GamepadRumbleIntensity::MAX,
+                envelope: None,
+            }] if requested == gamepad && requested_duration == duration
+        ));
+
+        // Removing the component stops the rumble.
+        world.entity_mut(emitter).remove::<Rumble>();
+        let requests = drain(&mut world);
+        assert!(matches!(
+            requests[..],
+            [GamepadRumbleRequest::Stop { gamepad: stopped }] if stopped == gamepad
+        ));
+
+        // So does despawning the emitter.
+        let emitter = world
+            .spawn(Rumble::new(gamepad, GamepadRumbleIntensity::MAX, duration))
+            .id();
+        drain(&mut world);
+        world.despawn(emitter);
+        let requests = drain(&mut world);
+        assert!(matches!(
+            requests[..],
+            [GamepadRumbleRequest::Stop { gamepad: stopped }] if stopped == gamepad
+        ));
+    }
 }
@@ -10,7 +10,9 @@ use bevy_reflect::TypePath;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 
 use bevy_asset::{Asset, RenderAssetUsages};
-use bevy_color::{Color, ColorToComponents, Gray, LinearRgba, Srgba, Xyza};
+use bevy_color::{
+    Color, ColorRange, ColorToComponents, ColorToPacked, Gray, LinearRgba, Mix, Srgba, Xyza,
+};
 use bevy_math::{AspectRatio, UVec2, UVec3, Vec2};
 use core::hash::Hash;
 use serde::{Deserialize, Serialize};
@@ -817,6 +819,55 @@ impl Image {
         Image::new(size, dimension, data, format, asset_usage)
     }
 
+    /// Bakes a [`ColorRange`] into a `width` x 1 lookup texture, for use as a gradient or color
+    /// ramp in shaders.
+    ///
+    /// The range is sampled at `width` evenly spaced points including both ends, as with
+    /// [`ColorRange::samples_iter`]. The image has the [`TextureFormat::Rgba8UnormSrgb`] format.
+    ///
+    /// If `premultiply` is `true`, the color channels are multiplied by the alpha in linear space
+    /// before being stored, as expected by premultiplied alpha blending. Otherwise, colors are
+    /// stored with straight alpha.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero.
+    pub fn from_color_range<T: Mix + Into<Color>>(
+        range: &impl ColorRange<T>,
+        width: u32,
+        premultiply: bool,
+        asset_usage: RenderAssetUsages,
+    ) -> Image {
+        assert!(width > 0, "a color range image must not be empty");
+
+        let data = range
+            .samples_iter(width as usize)
+            .flat_map(|color| {
+                let mut color = LinearRgba::from(color.into());
+                if premultiply {
+                    color = LinearRgba::new(
+                        color.red * color.alpha,
+                        color.green * color.alpha,
+                        color.blue * color.alpha,
+                        color.alpha,
+                    );
+                }
+                Srgba::from(color).to_u8_array()
+            })
+            .collect();
+        Image::new(
+            Extent3d {
+                width,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            asset_usage,
+        )
+    }
+
     /// Returns the width of a 2D image.
     #[inline]
     pub fn width(&self) -> u32 {
@@ -1675,6 +1726,29 @@ mod test {
         );
     }
 
+    #[test]
+    fn image_from_color_range_premultiplied() {
+        let color = LinearRgba::new(0.8, 0.4, 0.2, 0.5);
+        let range = color..color;
+        let straight = Image::from_color_range(&range, 4, false, RenderAssetUsages::MAIN_WORLD);
+        let premultiplied = Image::from_color_range(&range, 4, true, RenderAssetUsages::MAIN_WORLD);
+        assert_eq!(premultiplied.size(), UVec2::new(4, 1));
+
+        for x in 0..4 {
+            let straight = straight.get_color_at(x, 0).unwrap().to_linear();
+            let premultiplied = premultiplied.get_color_at(x, 0).unwrap().to_linear();
+            for (straight, premultiplied) in straight
+                .to_f32_array_no_alpha()
+                .into_iter()
+                .zip(premultiplied.to_f32_array_no_alpha())
+            {
+                assert!((premultiplied - straight * 0.5).abs() < 0.01);
+            }
+            assert!((straight.alpha - 0.5).abs() < 0.01);
+            assert_eq!(premultiplied.alpha, straight.alpha);
+        }
+    }
+
     #[test]
     fn image_default_size() {
         let image = Image::default();
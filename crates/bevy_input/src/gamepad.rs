@@ -5,13 +5,14 @@ use core::{ops::RangeInclusive, time::Duration};
 use crate::{Axis, ButtonInput, ButtonState};
 use alloc::string::String;
 #[cfg(feature = "bevy_reflect")]
-use bevy_ecs::prelude::ReflectComponent;
+use bevy_ecs::prelude::{ReflectComponent, ReflectResource};
 use bevy_ecs::{
     change_detection::DetectChangesMut,
     component::Component,
     entity::Entity,
     event::{Event, EventReader, EventWriter},
     name::Name,
+    resource::Resource,
     system::{Commands, Query},
 };
 use bevy_math::ops;
@@ -1745,6 +1746,49 @@ impl GamepadRumbleIntensity {
     }
 }
 
+/// How the intensity of a gamepad rumble ramps up at its start and down at its end.
+///
+/// A rumble with an envelope starts at [`attack_level`](Self::attack_level) times its intensity
+/// and ramps up to its full intensity over the [`attack`](Self::attack) time. It then sustains
+/// its full intensity until the final [`release`](Self::release) time, over which it ramps down
+/// to [`release_level`](Self::release_level) times its intensity.
+///
+/// The attack and release are limited to fit within the duration of the rumble, leaving at
+/// least a short sustain between them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Default, Clone)
+)]
+pub struct GamepadRumbleEnvelope {
+    /// How long the rumble takes to ramp up to its full intensity.
+    pub attack: Duration,
+    /// The fraction of the full intensity the rumble starts at.
+    ///
+    /// Ranges from `0.0` to `1.0`.
+    pub attack_level: f32,
+    /// How long the rumble takes to ramp down at its end.
+    pub release: Duration,
+    /// The fraction of the full intensity the rumble ends at.
+    ///
+    /// Ranges from `0.0` to `1.0`.
+    pub release_level: f32,
+}
+
+impl GamepadRumbleEnvelope {
+    /// Creates an envelope that ramps up from nothing over `attack` and back down to nothing
+    /// over `release`.
+    pub const fn new(attack: Duration, release: Duration) -> Self {
+        Self {
+            attack,
+            attack_level: 0.0,
+            release,
+            release_level: 0.0,
+        }
+    }
+}
+
 /// An event that controls force-feedback rumbling of a [`Gamepad`] [`entity`](Entity).
 ///
 /// # Notes
@@ -1766,6 +1810,7 @@ impl GamepadRumbleIntensity {
 ///             gamepad: entity,
 ///             intensity: GamepadRumbleIntensity::MAX,
 ///             duration: Duration::from_secs_f32(0.5),
+///             envelope: None,
 ///         });
 ///     }
 /// }
@@ -1794,19 +1839,87 @@ pub enum GamepadRumbleRequest {
         intensity: GamepadRumbleIntensity,
         /// The gamepad to rumble.
         gamepad: Entity,
+        /// How the intensity of the rumble ramps up and down.
+        ///
+        /// If `None`, the rumble plays at a constant intensity for its whole duration.
+        envelope: Option<GamepadRumbleEnvelope>,
     },
     /// Stop all running rumbles on the given [`Entity`].
     Stop {
         /// The gamepad to stop rumble.
         gamepad: Entity,
     },
+    /// Stop all running rumbles on every gamepad.
+    ///
+    /// This is useful to silence all gamepads at once, for example when the game is paused or
+    /// its window loses focus.
+    StopAll,
 }
 
 impl GamepadRumbleRequest {
     /// Get the [`Entity`] associated with this request.
-    pub fn gamepad(&self) -> Entity {
+    ///
+    /// Returns `None` for [`GamepadRumbleRequest::StopAll`], which applies to every gamepad.
+    pub fn gamepad(&self) -> Option<Entity> {
         match self {
-            Self::Add { gamepad, .. } | Self::Stop { gamepad } => *gamepad,
+            Self::Add { gamepad, .. } | Self::Stop { gamepad } => Some(*gamepad),
+            Self::StopAll => None,
+        }
+    }
+
+    /// Returns a [`GamepadRumbleRequest::Stop`] for each of the given gamepads.
+    ///
+    /// ```
+    /// # use bevy_input::gamepad::{Gamepad, GamepadRumbleRequest};
+    /// # use bevy_ecs::prelude::{EventWriter, Query, Entity, With};
+    /// fn stop_rumble_system(
+    ///     mut rumble_requests: EventWriter<GamepadRumbleRequest>,
+    ///     gamepads: Query<Entity, With<Gamepad>>,
+    /// ) {
+    ///     rumble_requests.write_batch(GamepadRumbleRequest::stop_each(&gamepads));
+    /// }
+    /// ```
+    pub fn stop_each<'a>(
+        gamepads: impl IntoIterator<Item = Entity> + 'a,
+    ) -> impl Iterator<Item = Self> + 'a {
+        gamepads.into_iter().map(|gamepad| Self::Stop { gamepad })
+    }
+}
+
+/// A global scale applied to the intensity of every [`GamepadRumbleRequest`].
+///
+/// This can be used for a vibration strength setting, for example for accessibility. The
+/// intensities of each request are multiplied by the scale, then clamped within the `0.0` to
+/// `1.0` range. A scale of `0.0` disables rumble entirely.
+///
+/// Changing the scale only affects rumbles added afterwards.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Resource, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct GamepadRumbleScale(pub f32);
+
+impl Default for GamepadRumbleScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl GamepadRumbleScale {
+    /// Returns the given intensity, scaled by this scale.
+    ///
+    /// The intensity of each motor is clamped within the `0.0` to `1.0` range.
+    pub fn scale(&self, intensity: GamepadRumbleIntensity) -> GamepadRumbleIntensity {
+        GamepadRumbleIntensity {
+            strong_motor: (intensity.strong_motor * self.0).clamp(0.0, 1.0),
+            weak_motor: (intensity.weak_motor * self.0).clamp(0.0, 1.0),
         }
     }
 }
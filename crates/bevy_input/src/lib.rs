@@ -63,8 +63,8 @@ use gamepad::{
     gamepad_connection_system, gamepad_event_processing_system, GamepadAxis,
     GamepadAxisChangedEvent, GamepadButton, GamepadButtonChangedEvent,
     GamepadButtonStateChangedEvent, GamepadConnection, GamepadConnectionEvent, GamepadEvent,
-    GamepadInput, GamepadRumbleRequest, GamepadSettings, RawGamepadAxisChangedEvent,
-    RawGamepadButtonChangedEvent, RawGamepadEvent,
+    GamepadInput, GamepadRumbleRequest, GamepadRumbleScale, GamepadSettings,
+    RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent, RawGamepadEvent,
 };
 
 #[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
@@ -118,6 +118,7 @@ impl Plugin for InputPlugin {
             .add_event::<RawGamepadAxisChangedEvent>()
             .add_event::<RawGamepadButtonChangedEvent>()
             .add_event::<GamepadRumbleRequest>()
+            .init_resource::<GamepadRumbleScale>()
             .init_resource::<AccumulatedMouseMotion>()
             .init_resource::<AccumulatedMouseScroll>()
             .add_systems(
@@ -157,6 +158,7 @@ impl Plugin for InputPlugin {
                 .register_type::<GamepadAxis>()
                 .register_type::<GamepadButton>()
                 .register_type::<GamepadInput>()
+                .register_type::<GamepadRumbleScale>()
                 .register_type::<AccumulatedMouseMotion>()
                 .register_type::<AccumulatedMouseScroll>();
         }
@@ -9,10 +9,16 @@ use super::{
 };
 use alloc::collections::BTreeMap;
 use bevy_asset::{Asset, Handle, RenderAssetUsages};
+use bevy_color::{Color, ColorRange, ColorToComponents, LinearRgba};
 use bevy_image::Image;
-use bevy_math::{primitives::Triangle3d, *};
+use bevy_math::{
+    primitives::{Measured2d, Triangle3d},
+    *,
+};
+use bevy_platform::collections::{HashMap, HashSet};
 use bevy_reflect::Reflect;
 use bytemuck::cast_slice;
+use rand::Rng;
 use thiserror::Error;
 use tracing::warn;
 use wgpu_types::{VertexAttribute, VertexFormat, VertexStepMode};
@@ -276,6 +282,54 @@ impl Mesh {
         self
     }
 
+    /// Moves the data of the vertex attribute `from` to the vertex attribute `to`, replacing any
+    /// data already set for `to`. Returns `false`, leaving the mesh unchanged, if `from` has no
+    /// data.
+    ///
+    /// This is useful to move data into a custom attribute expected by a shader, such as
+    /// moving [`Mesh::ATTRIBUTE_UV_0`] into [`Mesh::ATTRIBUTE_UV_1`].
+    ///
+    /// # Panics
+    /// Panics when the format of `to` does not match the format of the data.
+    pub fn rename_attribute(
+        &mut self,
+        from: impl Into<MeshVertexAttributeId>,
+        to: MeshVertexAttribute,
+    ) -> bool {
+        let from = from.into();
+        let Some(data) = self.attributes.get(&from) else {
+            return false;
+        };
+        if data.attribute.format != to.format {
+            panic!(
+                "Failed to rename attribute. Invalid attribute format for {}. Format of {} is {:?} but expected {:?}",
+                to.name, data.attribute.name, data.attribute.format, to.format
+            );
+        }
+
+        if let Some(values) = self.remove_attribute(from) {
+            self.insert_attribute(to, values);
+        }
+        true
+    }
+
+    /// Consumes the mesh and returns a mesh with the data of the vertex attribute `from` moved to
+    /// the vertex attribute `to`.
+    ///
+    /// (Alternatively, you can use [`Mesh::rename_attribute`] to mutate an existing mesh in-place)
+    ///
+    /// # Panics
+    /// Panics when the format of `to` does not match the format of the data.
+    #[must_use]
+    pub fn with_renamed_attribute(
+        mut self,
+        from: impl Into<MeshVertexAttributeId>,
+        to: MeshVertexAttribute,
+    ) -> Self {
+        self.rename_attribute(from, to);
+        self
+    }
+
     #[inline]
     pub fn contains_attribute(&self, id: impl Into<MeshVertexAttributeId>) -> bool {
         self.attributes.contains_key(&id.into())
@@ -502,50 +556,12 @@ impl Mesh {
     /// This can dramatically increase the vertex count, so make sure this is what you want.
     /// Does nothing if no [Indices] are set.
     pub fn duplicate_vertices(&mut self) {
-        fn duplicate<T: Copy>(values: &[T], indices: impl Iterator<Item = usize>) -> Vec<T> {
-            indices.map(|i| values[i]).collect()
-        }
-
         let Some(indices) = self.indices.take() else {
             return;
         };
 
         for attributes in self.attributes.values_mut() {
-            let indices = indices.iter();
-            #[expect(
-                clippy::match_same_arms,
-                reason = "Although the `vec` binding on some match arms may have different types, each variant has different semantics; thus it's not guaranteed that they will use the same type forever."
-            )]
-            match &mut attributes.values {
-                VertexAttributeValues::Float32(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Float32x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Float32x3(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32x3(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32x3(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint32x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint32x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Float32x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm16x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm16x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm8x2(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Sint8x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Snorm8x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Uint8x4(vec) => *vec = duplicate(vec, indices),
-                VertexAttributeValues::Unorm8x4(vec) => *vec = duplicate(vec, indices),
-            }
+            duplicate_attribute_values(&mut attributes.values, indices.iter());
         }
     }
 
@@ -727,6 +743,143 @@ impl Mesh {
         self.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
     }
 
+    /// Calculates the [`Mesh::ATTRIBUTE_NORMAL`] of an indexed mesh, smoothing normals across
+    /// edges where faces meet at an angle of at most `crease_angle` radians, and keeping sharp
+    /// edges everywhere else.
+    ///
+    /// Vertices sharing a position are welded together while computing the normals, so faces are
+    /// smoothed across seams of the other attributes, such as UV seams. The normal of each corner
+    /// is the average of the normals of the faces around it that are within the crease angle,
+    /// weighted by the angle of their corner. Vertices whose corners end up with different
+    /// normals are split into one vertex for each normal, duplicating their other attributes.
+    ///
+    /// A `crease_angle` of `0` gives flat normals, while `PI` smooths every edge. Degenerate
+    /// triangles without any area are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MeshNormalsError`] if the mesh isn't an indexed
+    /// [`PrimitiveTopology::TriangleList`] with [`Mesh::ATTRIBUTE_POSITION`] of type `float3`.
+    pub fn compute_creased_normals(&mut self, crease_angle: f32) -> Result<(), MeshNormalsError> {
+        if self.primitive_topology != PrimitiveTopology::TriangleList {
+            return Err(MeshNormalsError::WrongTopology);
+        }
+        let positions = match self.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions,
+            Some(_) => return Err(MeshNormalsError::PositionsFormat),
+            None => return Err(MeshNormalsError::MissingPositions),
+        };
+        let indices: Vec<usize> = self
+            .indices()
+            .ok_or(MeshNormalsError::MissingIndices)?
+            .iter()
+            .collect();
+        if indices.iter().any(|&index| index >= positions.len()) {
+            return Err(MeshNormalsError::BadIndices);
+        }
+
+        // Weld the vertices by position.
+        let mut welded = HashMap::<[u32; 3], usize>::default();
+        let position_ids: Vec<usize> = positions
+            .iter()
+            .map(|&position| {
+                let next_id = welded.len();
+                *welded
+                    .entry(weld_key(Vec3::from(position)))
+                    .or_insert(next_id)
+            })
+            .collect();
+
+        // The normal of each face and the angles of its corners, or `None` if it's degenerate.
+        let faces: Vec<Option<(Vec3, [f32; 3])>> = indices
+            .chunks_exact(3)
+            .map(|face| {
+                let [a, b, c] = [face[0], face[1], face[2]].map(|i| Vec3::from(positions[i]));
+                let normal = (b - a).cross(c - a).try_normalize()?;
+                let angles = [
+                    (b - a).angle_between(c - a),
+                    (c - b).angle_between(a - b),
+                    (a - c).angle_between(b - c),
+                ];
+                Some((normal, angles))
+            })
+            .collect();
+        let face = |corner: usize| faces.get(corner / 3).copied().flatten();
+
+        let mut corners_at = vec![Vec::new(); welded.len()];
+        for (corner, &vertex) in indices.iter().enumerate() {
+            if face(corner).is_some() {
+                corners_at[position_ids[vertex]].push(corner);
+            }
+        }
+
+        // A small tolerance keeps coplanar faces together despite floating point error.
+        let min_cos = ops::cos(crease_angle.clamp(0.0, core::f32::consts::PI)) - 1e-5;
+        let mut corner_normals = vec![Vec3::ZERO; indices.len()];
+        for corners in &corners_at {
+            for &corner in corners {
+                let Some((normal, _)) = face(corner) else {
+                    continue;
+                };
+                corner_normals[corner] = corners
+                    .iter()
+                    .filter_map(|&other| {
+                        let (other_normal, angles) = face(other)?;
+                        (normal.dot(other_normal) >= min_cos)
+                            .then(|| other_normal * angles[other % 3])
+                    })
+                    .sum::<Vec3>()
+                    .normalize_or_zero();
+            }
+        }
+
+        // Split the vertices whose corners have different normals. Corners of degenerate
+        // triangles keep their vertex, whatever its normal.
+        let mut normals = vec![Vec3::ZERO; positions.len()];
+        let mut copies = vec![Vec::new(); positions.len()];
+        let mut sources: Vec<usize> = (0..positions.len()).collect();
+        let mut new_indices = Vec::with_capacity(indices.len());
+        for (corner, &vertex) in indices.iter().enumerate() {
+            if face(corner).is_none() {
+                new_indices.push(vertex);
+                continue;
+            }
+            let normal = corner_normals[corner];
+            let copy = copies[vertex]
+                .iter()
+                .copied()
+                .find(|&copy: &usize| normals[copy].abs_diff_eq(normal, 1e-4));
+            let copy = copy.unwrap_or_else(|| {
+                let copy = if copies[vertex].is_empty() {
+                    vertex
+                } else {
+                    sources.push(vertex);
+                    normals.push(Vec3::ZERO);
+                    sources.len() - 1
+                };
+                normals[copy] = normal;
+                copies[vertex].push(copy);
+                copy
+            });
+            new_indices.push(copy);
+        }
+
+        if sources.len() > positions.len() {
+            for attribute in self.attributes.values_mut() {
+                duplicate_attribute_values(&mut attribute.values, sources.iter().copied());
+            }
+        }
+        let indices = match self.indices {
+            Some(Indices::U16(_)) if sources.len() <= u16::MAX as usize + 1 => {
+                Indices::U16(new_indices.into_iter().map(|i| i as u16).collect())
+            }
+            _ => Indices::U32(new_indices.into_iter().map(|i| i as u32).collect()),
+        };
+        self.insert_indices(indices);
+        self.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        Ok(())
+    }
+
     /// Consumes the mesh and returns a mesh with calculated [`Mesh::ATTRIBUTE_NORMAL`].
     /// If the mesh is indexed, this defaults to smooth normals. Otherwise, it defaults to flat
     /// normals.
@@ -770,6 +923,23 @@ impl Mesh {
         self
     }
 
+    /// Consumes the mesh and returns a mesh with calculated [`Mesh::ATTRIBUTE_NORMAL`], smoothed
+    /// across edges where faces meet at an angle of at most `crease_angle` radians.
+    ///
+    /// (Alternatively, you can use [`Mesh::compute_creased_normals`] to mutate an existing mesh in-place)
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MeshNormalsError`] if the mesh isn't an indexed
+    /// [`PrimitiveTopology::TriangleList`] with [`Mesh::ATTRIBUTE_POSITION`] of type `float3`.
+    pub fn with_computed_creased_normals(
+        mut self,
+        crease_angle: f32,
+    ) -> Result<Self, MeshNormalsError> {
+        self.compute_creased_normals(crease_angle)?;
+        Ok(self)
+    }
+
     /// Generate tangents for the mesh using the `mikktspace` algorithm.
     ///
     /// Sets the [`Mesh::ATTRIBUTE_TANGENT`] attribute if successful.
@@ -792,6 +962,60 @@ impl Mesh {
         Ok(self)
     }
 
+    /// Checks that the [`Mesh::ATTRIBUTE_TANGENT`] of every vertex describes a valid tangent space
+    /// together with its [`Mesh::ATTRIBUTE_NORMAL`].
+    ///
+    /// The `xyz` part of each tangent must be nonzero and roughly orthogonal to the normal, and its
+    /// `w` part, which gives the handedness of the bitangent, must be `1.0` or `-1.0`. Meshes that
+    /// fail these checks may be lit incorrectly when normal mapped.
+    ///
+    /// Requires the [`Mesh::ATTRIBUTE_NORMAL`] and [`Mesh::ATTRIBUTE_TANGENT`] attributes set.
+    pub fn validate_tangent_space(&self) -> Result<(), TangentError> {
+        let normals = match self.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float32x3(normals)) => normals,
+            Some(_) => {
+                return Err(TangentError::InvalidVertexAttributeFormat(
+                    Mesh::ATTRIBUTE_NORMAL.name,
+                    VertexFormat::Float32x3,
+                ))
+            }
+            None => {
+                return Err(TangentError::MissingVertexAttribute(
+                    Mesh::ATTRIBUTE_NORMAL.name,
+                ))
+            }
+        };
+        let tangents = match self.attribute(Mesh::ATTRIBUTE_TANGENT) {
+            Some(VertexAttributeValues::Float32x4(tangents)) => tangents,
+            Some(_) => {
+                return Err(TangentError::InvalidVertexAttributeFormat(
+                    Mesh::ATTRIBUTE_TANGENT.name,
+                    VertexFormat::Float32x4,
+                ))
+            }
+            None => {
+                return Err(TangentError::MissingVertexAttribute(
+                    Mesh::ATTRIBUTE_TANGENT.name,
+                ))
+            }
+        };
+
+        for (vertex, (&normal, &tangent)) in normals.iter().zip(tangents).enumerate() {
+            let [x, y, z, w] = tangent;
+            if w != 1.0 && w != -1.0 {
+                return Err(TangentError::InvalidHandedness { vertex, w });
+            }
+            let Some(tangent) = Vec3::new(x, y, z).try_normalize() else {
+                return Err(TangentError::ZeroTangent { vertex });
+            };
+            let normal = Vec3::from(normal).normalize_or_zero();
+            if tangent.dot(normal).abs() > 1e-3 {
+                return Err(TangentError::NotOrthogonal { vertex });
+            }
+        }
+        Ok(())
+    }
+
     /// Merges the [`Mesh`] data of `other` with `self`. The attributes and indices of `other` will be appended to `self`.
     ///
     /// Note that attributes of `other` that don't exist on `self` will be ignored.
@@ -802,9 +1026,17 @@ impl Mesh {
     ///
     /// Returns [`Err(MergeMeshError)`](MergeMeshError) if the vertex attribute values of `other` are incompatible with `self`.
     /// For example, [`VertexAttributeValues::Float32`] is incompatible with [`VertexAttributeValues::Float32x3`].
+    /// The primitive topologies of both meshes must also be the same.
     pub fn merge(&mut self, other: &Mesh) -> Result<(), MergeMeshError> {
         use VertexAttributeValues::*;
 
+        if self.primitive_topology != other.primitive_topology {
+            return Err(MergeMeshError::IncompatiblePrimitiveTopology {
+                self_primitive_topology: self.primitive_topology,
+                other_primitive_topology: other.primitive_topology,
+            });
+        }
+
         // The indices of `other` should start after the last vertex of `self`.
         let index_offset = self.count_vertices();
 
@@ -845,7 +1077,7 @@ impl Mesh {
                     (Uint8x4(vec1), Uint8x4(vec2)) => vec1.extend(vec2),
                     (Unorm8x4(vec1), Unorm8x4(vec2)) => vec1.extend(vec2),
                     _ => {
-                        return Err(MergeMeshError {
+                        return Err(MergeMeshError::IncompatibleVertexAttributes {
                             self_attribute: *attribute,
                             other_attribute: other
                                 .attribute_data(attribute.id)
@@ -863,6 +1095,92 @@ impl Mesh {
         Ok(())
     }
 
+    /// Merges `other`, transformed by `transform`, into this mesh. The vertices of `other` are
+    /// appended to the ones of `self`, and its indices are offset to point to them.
+    ///
+    /// This is useful to combine static meshes into a single one, so that they're drawn at once.
+    /// Unlike [`Mesh::merge`], the meshes must have the same set of vertex attributes, so that no
+    /// data is silently dropped, and `self` is left untouched on errors. If either mesh has no
+    /// vertices, it doesn't need to have any attributes: merging into an empty mesh gives a
+    /// transformed copy of `other`. If only one of the meshes has indices, indices are added for the
+    /// vertices of the other one. Morph targets aren't kept, as they no longer match the vertices.
+    ///
+    /// `Aabb` of entities with modified mesh are not updated automatically.
+    ///
+    /// ```
+    /// # use bevy_math::primitives::Cuboid;
+    /// # use bevy_mesh::{Mesh, MeshBuilder, Meshable};
+    /// # use bevy_transform::components::Transform;
+    /// // Two boxes stacked on top of each other, as a single mesh.
+    /// let mut mesh = Cuboid::default().mesh().build();
+    /// mesh.merge_transformed(&mesh.clone(), Transform::from_xyz(0.0, 1.0, 0.0))
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err(MergeMeshError)`](MergeMeshError) if the meshes have different primitive
+    /// topologies, or if they don't have the same vertex attributes with the same formats.
+    pub fn merge_transformed(
+        &mut self,
+        other: &Mesh,
+        transform: Transform,
+    ) -> Result<(), MergeMeshError> {
+        if self.primitive_topology != other.primitive_topology {
+            return Err(MergeMeshError::IncompatiblePrimitiveTopology {
+                self_primitive_topology: self.primitive_topology,
+                other_primitive_topology: other.primitive_topology,
+            });
+        }
+        if other.count_vertices() == 0 {
+            return Ok(());
+        }
+        if self.count_vertices() == 0 {
+            let asset_usage = self.asset_usage;
+            *self = other.clone().transformed_by(transform);
+            self.asset_usage = asset_usage;
+            self.morph_targets = None;
+            self.morph_target_names = None;
+            return Ok(());
+        }
+
+        for (attribute, values) in self.attributes() {
+            match other.attribute_data(attribute.id) {
+                Some(other_data)
+                    if VertexFormat::from(values) == VertexFormat::from(&other_data.values) => {}
+                other_data => {
+                    return Err(MergeMeshError::IncompatibleVertexAttributes {
+                        self_attribute: *attribute,
+                        other_attribute: other_data.map(|data| data.attribute),
+                    })
+                }
+            }
+        }
+        if let Some((attribute, _)) = other
+            .attributes()
+            .find(|(attribute, _)| !self.contains_attribute(attribute.id))
+        {
+            return Err(MergeMeshError::MissingVertexAttribute {
+                attribute: *attribute,
+            });
+        }
+
+        let mut other = other.clone().transformed_by(transform);
+        match (&self.indices, &other.indices) {
+            (Some(_), None) => {
+                other.insert_indices(Indices::U32((0..other.count_vertices() as u32).collect()));
+            }
+            (None, Some(_)) => {
+                self.insert_indices(Indices::U32((0..self.count_vertices() as u32).collect()));
+            }
+            _ => {}
+        }
+        self.merge(&other)?;
+        self.morph_targets = None;
+        self.morph_target_names = None;
+        Ok(())
+    }
+
     /// Transforms the vertex positions, normals, and tangents of the mesh by the given [`Transform`].
     ///
     /// `Aabb` of entities with modified mesh are not updated automatically.
@@ -1226,110 +1544,920 @@ impl Mesh {
             })
         }
     }
-}
 
-impl core::ops::Mul<Mesh> for Transform {
-    type Output = Mesh;
+    /// Computes the area of each of this Mesh's [triangles], in the order returned by
+    /// [`Mesh::triangles`].
+    ///
+    /// Returns an error under the same conditions as [`Mesh::triangles`].
+    ///
+    /// [triangles]: Triangle3d
+    pub fn triangle_areas(&self) -> Result<Vec<f32>, MeshTrianglesError> {
+        Ok(self.triangles()?.map(|triangle| triangle.area()).collect())
+    }
 
-    fn mul(self, rhs: Mesh) -> Self::Output {
-        rhs.transformed_by(self)
+    /// Computes the total surface area of this Mesh, which is the sum of the areas of all of
+    /// its [triangles].
+    ///
+    /// Returns an error under the same conditions as [`Mesh::triangles`].
+    ///
+    /// [triangles]: Triangle3d
+    pub fn surface_area(&self) -> Result<f32, MeshTrianglesError> {
+        Ok(self.triangles()?.map(|triangle| triangle.area()).sum())
     }
-}
 
-/// Error that can occur when calling [`Mesh::merge`].
-#[derive(Error, Debug, Clone)]
-#[error("Incompatible vertex attribute types {} and {}", self_attribute.name, other_attribute.map(|a| a.name).unwrap_or("None"))]
-pub struct MergeMeshError {
-    pub self_attribute: MeshVertexAttribute,
-    pub other_attribute: Option<MeshVertexAttribute>,
-}
+    /// Samples a uniformly random point on the surface of this Mesh, returning the point along
+    /// with the surface normal at that point.
+    ///
+    /// Each of the Mesh's [triangles] is chosen with a probability proportional to its area, so
+    /// larger triangles receive more samples. If the Mesh has [`Mesh::ATTRIBUTE_NORMAL`] data in
+    /// the `Float32x3` format, the returned normal is interpolated from the normals of the
+    /// triangle's vertices; otherwise, the flat face normal of the triangle is used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Mesh::triangles`] would return an error, or if the Mesh has no triangles
+    /// with a non-zero area.
+    ///
+    /// [triangles]: Triangle3d
+    pub fn sample_surface<R: Rng + ?Sized>(&self, rng: &mut R) -> (Vec3, Vec3) {
+        let triangles = self
+            .triangle_vertex_indices()
+            .expect("Mesh must be a valid triangle mesh to sample its surface");
+        let positions = self
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+            .expect("Mesh must be a valid triangle mesh to sample its surface");
+        let normals = self
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3);
 
-#[cfg(test)]
-mod tests {
-    use super::Mesh;
-    use crate::mesh::{Indices, MeshWindingInvertError, VertexAttributeValues};
-    use crate::PrimitiveTopology;
-    use bevy_asset::RenderAssetUsages;
-    use bevy_math::primitives::Triangle3d;
-    use bevy_math::Vec3;
-    use bevy_transform::components::Transform;
+        // Build a cumulative distribution of the triangle areas so that a triangle can be picked
+        // with a binary search.
+        let mut total_area = 0.0;
+        let cumulative_areas: Vec<f32> = triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                let triangle = Triangle3d::new(
+                    positions[a].into(),
+                    positions[b].into(),
+                    positions[c].into(),
+                );
+                total_area += triangle.area();
+                total_area
+            })
+            .collect();
+        assert!(
+            total_area > 0.0,
+            "Mesh must have a non-zero surface area to sample its surface"
+        );
 
-    #[test]
-    #[should_panic]
-    fn panic_invalid_format() {
-        let _mesh = Mesh::new(
-            PrimitiveTopology::TriangleList,
-            RenderAssetUsages::default(),
-        )
-        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0, 0.0]]);
+        let target = rng.gen_range(0.0..total_area);
+        let index = cumulative_areas
+            .partition_point(|&area| area <= target)
+            .min(triangles.len() - 1);
+        let [a, b, c] = triangles[index];
+
+        // Uniformly sample barycentric coordinates, reflecting samples that fall outside of the
+        // triangle back into it.
+        let (mut u, mut v) = (rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0));
+        if u + v > 1.0 {
+            u = 1.0 - u;
+            v = 1.0 - v;
+        }
+        let w = 1.0 - u - v;
+
+        let [pa, pb, pc] = [a, b, c].map(|i| Vec3::from(positions[i]));
+        let point = w * pa + u * pb + v * pc;
+
+        let face_normal = (pb - pa).cross(pc - pa).normalize_or_zero();
+        let normal = normals
+            .and_then(|normals| {
+                let na = Vec3::from(*normals.get(a)?);
+                let nb = Vec3::from(*normals.get(b)?);
+                let nc = Vec3::from(*normals.get(c)?);
+                (w * na + u * nb + v * nc).try_normalize()
+            })
+            .unwrap_or(face_normal);
+
+        (point, normal)
     }
 
-    #[test]
-    fn transform_mesh() {
-        let mesh = Mesh::new(
-            PrimitiveTopology::TriangleList,
-            RenderAssetUsages::default(),
-        )
-        .with_inserted_attribute(
-            Mesh::ATTRIBUTE_POSITION,
-            vec![[-1., -1., 2.], [1., -1., 2.], [0., 1., 2.]],
-        )
-        .with_inserted_attribute(
-            Mesh::ATTRIBUTE_NORMAL,
-            vec![
-                Vec3::new(-1., -1., 1.).normalize().to_array(),
-                Vec3::new(1., -1., 1.).normalize().to_array(),
-                [0., 0., 1.],
-            ],
-        )
-        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0., 0.], [1., 0.], [0.5, 1.]]);
+    /// Creates a [`PrimitiveTopology::LineList`] mesh visualizing the normals of this Mesh,
+    /// with one line of the given `length` per vertex, going from the vertex's position along
+    /// its normal.
+    ///
+    /// This is useful for debugging normals without a dedicated shader.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Mesh::ATTRIBUTE_POSITION`] or [`Mesh::ATTRIBUTE_NORMAL`] is missing or
+    /// not of type `float3`.
+    pub fn debug_normals_mesh(&self, length: f32) -> Mesh {
+        let positions = self
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+            .expect("`Mesh::ATTRIBUTE_POSITION` must be of type `float3`");
+        let normals = self
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3)
+            .expect("`Mesh::ATTRIBUTE_NORMAL` must be of type `float3`");
 
-        let mesh = mesh.transformed_by(
-            Transform::from_translation(Vec3::splat(-2.)).with_scale(Vec3::new(2., 0., -1.)),
-        );
+        let lines: Vec<[f32; 3]> = positions
+            .iter()
+            .zip(normals)
+            .flat_map(|(&position, &normal)| {
+                let position = Vec3::from(position);
+                [position, position + length * Vec3::from(normal)].map(Into::into)
+            })
+            .collect();
 
-        if let Some(VertexAttributeValues::Float32x3(positions)) =
-            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
-        {
-            // All positions are first scaled resulting in `vec![[-2, 0., -2.], [2., 0., -2.], [0., 0., -2.]]`
-            // and then shifted by `-2.` along each axis
-            assert_eq!(
-                positions,
-                &vec![[-4.0, -2.0, -4.0], [0.0, -2.0, -4.0], [-2.0, -2.0, -4.0]]
-            );
-        } else {
-            panic!("Mesh does not have a position attribute");
-        }
+        Mesh::new(PrimitiveTopology::LineList, self.asset_usage)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, lines)
+    }
 
-        if let Some(VertexAttributeValues::Float32x3(normals)) =
-            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
-        {
-            assert_eq!(normals, &vec![[0., -1., 0.], [0., -1., 0.], [0., 0., -1.]]);
+    /// Creates a [`PrimitiveTopology::LineList`] mesh with a line along each edge of the triangles
+    /// of this Mesh, for drawing it as a wireframe.
+    ///
+    /// This is useful on platforms that don't support
+    /// [`PolygonMode::Line`](wgpu_types::PolygonMode::Line), like WebGL2 and WebGPU. The wireframe
+    /// has the same positions as this mesh, and [`Indices::U32`] with two indices per line.
+    /// Other vertex attributes aren't kept.
+    ///
+    /// Each edge is only drawn once, even when it's shared by several triangles, no matter which
+    /// way around the triangles go. Edges are matched by the indices of their vertices, so
+    /// overlapping edges between vertices that are split, such as between the faces of a
+    /// [`Cuboid`](primitives::Cuboid), are drawn once per side. Meshes without indices are treated
+    /// as if their vertices were indexed in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mesh's topology isn't [`PrimitiveTopology::TriangleList`] or
+    /// [`PrimitiveTopology::TriangleStrip`], or if [`Mesh::ATTRIBUTE_POSITION`] is missing or not
+    /// of type `float3`.
+    pub fn to_wireframe(&self) -> Mesh {
+        let indexed;
+        let mesh = if self.indices.is_some() {
+            self
         } else {
-            panic!("Mesh does not have a normal attribute");
+            indexed = self
+                .clone()
+                .with_inserted_indices(Indices::U32((0..self.count_vertices() as u32).collect()));
+            &indexed
+        };
+        let triangles = mesh
+            .triangle_vertex_indices()
+            .unwrap_or_else(|error| panic!("can't build a wireframe for this mesh: {error}"));
+
+        let mut edges = HashSet::new();
+        let mut indices = Vec::new();
+        for [a, b, c] in triangles {
+            for (start, end) in [(a, b), (b, c), (c, a)] {
+                if edges.insert((start.min(end), start.max(end))) {
+                    indices.extend_from_slice(&[start as u32, end as u32]);
+                }
+            }
         }
 
-        if let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
-            assert_eq!(uvs, &vec![[0., 0.], [1., 0.], [0.5, 1.]]);
-        } else {
-            panic!("Mesh does not have a uv attribute");
-        }
+        let positions = self
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .expect("`Mesh::ATTRIBUTE_POSITION` must be of type `float3`")
+            .clone();
+        Mesh::new(PrimitiveTopology::LineList, self.asset_usage)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_indices(Indices::U32(indices))
     }
 
-    #[test]
-    fn point_list_mesh_invert_winding() {
-        let mesh = Mesh::new(PrimitiveTopology::PointList, RenderAssetUsages::default())
-            .with_inserted_indices(Indices::U32(vec![]));
-        assert!(matches!(
-            mesh.with_inverted_winding(),
-            Err(MeshWindingInvertError::WrongTopology)
-        ));
-    }
+    /// Returns a closed solid made of this mesh's surface and a copy of it offset inwards by
+    /// `thickness`, for example for hollow objects or outlines.
+    ///
+    /// The inner surface is made by moving each vertex against its normal, and faces the other
+    /// way, with flipped normals and winding. Along the borders of open meshes, the two surfaces
+    /// are stitched together by a wall of two triangles per border edge. Edges are matched by the
+    /// positions of their vertices rather than their indices, so seams where vertices are split,
+    /// such as between the faces of a [`Cuboid`](primitives::Cuboid), aren't borders, and closed
+    /// meshes get no walls.
+    ///
+    /// As vertices are moved along their own normals, the inner faces of a mesh with flat normals
+    /// don't meet at its edges. Meshes with smooth normals have a closed inner surface.
+    ///
+    /// The shell is a [`PrimitiveTopology::TriangleList`] with [`Indices::U32`]. All other vertex
+    /// attributes are copied from the vertices each new vertex is made from, so tangents should
+    /// be regenerated with [`Mesh::generate_tangents`] if needed. Morph targets aren't kept, as
+    /// they no longer match the vertices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mesh's topology isn't [`PrimitiveTopology::TriangleList`] or
+    /// [`PrimitiveTopology::TriangleStrip`], or if [`Mesh::ATTRIBUTE_POSITION`] or
+    /// [`Mesh::ATTRIBUTE_NORMAL`] is missing or not of type `float3`.
+    pub fn to_shell(&self, thickness: f32) -> Mesh {
+        let mut shell = self.clone();
+        if shell.indices.is_none() {
+            shell.insert_indices(Indices::U32((0..self.count_vertices() as u32).collect()));
+        }
+        let triangles = shell
+            .triangle_vertex_indices()
+            .unwrap_or_else(|error| panic!("can't make a shell from this mesh: {error}"));
+        let positions: Vec<Vec3> = shell
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+            .expect("`Mesh::ATTRIBUTE_POSITION` must be of type `float3`")
+            .iter()
+            .map(|&position| Vec3::from(position))
+            .collect();
+        let normals: Vec<Vec3> = shell
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3)
+            .expect("`Mesh::ATTRIBUTE_NORMAL` must be of type `float3`")
+            .iter()
+            .map(|&normal| Vec3::from(normal))
+            .collect();
+        let vertex_count = positions.len();
 
-    #[test]
-    fn line_list_mesh_invert_winding() {
-        let mesh = Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
-            .with_inserted_indices(Indices::U32(vec![0, 1, 1, 2, 2, 3]));
+        // Identify vertices by position, so that edges along seams are shared.
+        let mut welded = HashMap::<[u32; 3], usize>::default();
+        let vertex_ids: Vec<usize> = positions
+            .iter()
+            .map(|&position| {
+                let next_id = welded.len();
+                *welded.entry(weld_key(position)).or_insert(next_id)
+            })
+            .collect();
+        let edges: HashSet<[usize; 2]> = triangles
+            .iter()
+            .flat_map(|&[a, b, c]| [[a, b], [b, c], [c, a]])
+            .map(|edge| edge.map(|i| vertex_ids[i]))
+            .collect();
+        // An edge is a border if no triangle runs along it in the other direction.
+        let borders: Vec<[usize; 2]> = triangles
+            .iter()
+            .flat_map(|&[a, b, c]| [[a, b], [b, c], [c, a]])
+            .filter(|&[a, b]| !edges.contains(&[vertex_ids[b], vertex_ids[a]]))
+            .collect();
+
+        // The outer surface, then the inner surface, then four vertices for each wall.
+        let sources = (0..vertex_count)
+            .chain(0..vertex_count)
+            .chain(borders.iter().flat_map(|&[a, b]| [a, b, a, b]));
+        for attribute in shell.attributes.values_mut() {
+            duplicate_attribute_values(&mut attribute.values, sources.clone());
+        }
+
+        let offset = |i: usize| positions[i] - normals[i] * thickness;
+        if let Some(VertexAttributeValues::Float32x3(shell_positions)) =
+            shell.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+        {
+            for (i, position) in shell_positions[vertex_count..2 * vertex_count]
+                .iter_mut()
+                .enumerate()
+            {
+                *position = offset(i).into();
+            }
+            for (wall, &[a, b]) in shell_positions[2 * vertex_count..]
+                .chunks_exact_mut(4)
+                .zip(&borders)
+            {
+                wall[2] = offset(a).into();
+                wall[3] = offset(b).into();
+            }
+        }
+        if let Some(VertexAttributeValues::Float32x3(shell_normals)) =
+            shell.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+        {
+            for normal in &mut shell_normals[vertex_count..2 * vertex_count] {
+                *normal = (-Vec3::from(*normal)).into();
+            }
+            for (wall, &[a, b]) in shell_normals[2 * vertex_count..]
+                .chunks_exact_mut(4)
+                .zip(&borders)
+            {
+                let normal =
+                    face_normal(positions[b].into(), positions[a].into(), offset(a).into());
+                wall.fill(normal);
+            }
+        }
+
+        let inner = vertex_count as u32;
+        let mut indices = Vec::with_capacity(6 * triangles.len() + 6 * borders.len());
+        for &[a, b, c] in &triangles {
+            indices.extend([a, b, c].map(|i| i as u32));
+        }
+        for &[a, b, c] in &triangles {
+            indices.extend([a, c, b].map(|i| inner + i as u32));
+        }
+        for wall in 0..borders.len() as u32 {
+            let [outer_a, outer_b, inner_a, inner_b] =
+                [0, 1, 2, 3].map(|i| 2 * inner + 4 * wall + i);
+            indices.extend([outer_b, outer_a, inner_a, outer_b, inner_a, inner_b]);
+        }
+
+        shell.primitive_topology = PrimitiveTopology::TriangleList;
+        shell.insert_indices(Indices::U32(indices));
+        shell.morph_targets = None;
+        shell.morph_target_names = None;
+        shell
+    }
+
+    /// Merges `other`, transformed by `transform`, into a copy of this mesh, then welds the
+    /// vertices where the two meshes touch.
+    ///
+    /// This is not a full constructive solid geometry union: triangles aren't clipped, so
+    /// overlapping meshes still overlap. Instead, each vertex of `other` within a distance of
+    /// `1e-4` of a vertex of `self`, and with a normal within the same distance of that vertex's
+    /// normal, is replaced by that vertex, so that primitives which touch share their edges. The other attributes of a welded vertex, such as its UVs, are the ones of the
+    /// vertex from `self`. Vertices are never welded with other vertices of the same mesh.
+    ///
+    /// For [`PrimitiveTopology::TriangleList`] meshes, faces where the meshes touch from opposite
+    /// sides, like the shared face of two adjacent cubes, end up inside the union, so they're
+    /// removed: a triangle whose corners all touch the other mesh is removed if its corners and
+    /// its centroid lie on triangles of the other mesh that face the opposite way. Vertices that
+    /// are no longer used are removed too.
+    ///
+    /// The union has [`Indices::U32`], and only the attributes that both meshes have. Morph
+    /// targets aren't kept, as they no longer match the vertices.
+    ///
+    /// `Aabb` of entities with modified mesh are not updated automatically.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err(MergeMeshError)`](MergeMeshError) if the vertex attribute values of `other` are
+    /// incompatible with `self`, like [`Mesh::merge`].
+    pub fn union(&self, other: &Mesh, transform: Transform) -> Result<Mesh, MergeMeshError> {
+        let with_indices = |mesh: &Mesh| {
+            let mut mesh = mesh.clone();
+            if mesh.indices.is_none() {
+                mesh.insert_indices(Indices::U32((0..mesh.count_vertices() as u32).collect()));
+            }
+            mesh
+        };
+        let other = with_indices(other).transformed_by(transform);
+        let mut union = with_indices(self);
+        union
+            .attributes
+            .retain(|id, _| other.attributes.contains_key(id));
+        let self_vertex_count = union.count_vertices();
+        let self_index_count = union.indices().map_or(0, Indices::len);
+        union.merge(&other)?;
+
+        let Some(positions) = union
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+        else {
+            return Ok(union);
+        };
+        let positions: Vec<Vec3> = positions.iter().copied().map(Vec3::from).collect();
+        let normals: Option<Vec<Vec3>> = union
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3)
+            .map(|normals| normals.iter().copied().map(Vec3::from).collect());
+        // Which of the two meshes each vertex comes from, `0` for `self` and `1` for `other`.
+        let mesh_of = |i: usize| usize::from(i >= self_vertex_count);
+
+        let mut cells = [HashMap::default(), HashMap::default()];
+        for (i, &position) in positions.iter().enumerate() {
+            cells[mesh_of(i)]
+                .entry(weld_cell(position))
+                .or_insert_with(Vec::new)
+                .push(i);
+        }
+        let touches_other_mesh = |i: usize| {
+            vertices_near(&cells[1 - mesh_of(i)], &positions, positions[i])
+                .next()
+                .is_some()
+        };
+
+        // The vertex of `self` that each vertex of `other` is welded to, or the vertex itself.
+        let welded: Vec<usize> = (0..positions.len())
+            .map(|i| {
+                if i < self_vertex_count {
+                    return i;
+                }
+                vertices_near(&cells[0], &positions, positions[i])
+                    .filter(|&j| {
+                        normals.as_ref().is_none_or(|normals| {
+                            normals[i].distance(normals[j]) <= UNION_WELD_DISTANCE
+                        })
+                    })
+                    .min_by(|&a, &b| {
+                        positions[a]
+                            .distance_squared(positions[i])
+                            .total_cmp(&positions[b].distance_squared(positions[i]))
+                    })
+                    .unwrap_or(i)
+            })
+            .collect();
+
+        let indices: Vec<usize> = union
+            .indices()
+            .into_iter()
+            .flat_map(Indices::iter)
+            .collect();
+        let mut removed = vec![false; indices.len().div_ceil(3)];
+        if union.primitive_topology == PrimitiveTopology::TriangleList {
+            let triangle = |t: usize| [0, 1, 2].map(|corner| positions[indices[3 * t + corner]]);
+            // The triangles of each mesh whose corners all touch the other mesh.
+            let mut touching = [Vec::new(), Vec::new()];
+            for t in 0..indices.len() / 3 {
+                if indices[3 * t..3 * t + 3]
+                    .iter()
+                    .all(|&i| touches_other_mesh(i))
+                {
+                    touching[usize::from(3 * t >= self_index_count)].push(t);
+                }
+            }
+            for mesh in 0..2 {
+                for &t in &touching[mesh] {
+                    let [a, b, c] = triangle(t);
+                    let normal = (b - a).cross(c - a);
+                    let opposite: Vec<[Vec3; 3]> = touching[1 - mesh]
+                        .iter()
+                        .map(|&u| triangle(u))
+                        .filter(|[d, e, f]| normal.dot((e - d).cross(f - d)) < 0.0)
+                        .collect();
+                    removed[t] = [a, b, c, (a + b + c) / 3.0].into_iter().all(|point| {
+                        opposite
+                            .iter()
+                            .any(|triangle| is_on_triangle(point, triangle))
+                    });
+                }
+            }
+        }
+
+        let indices: Vec<usize> = indices
+            .iter()
+            .enumerate()
+            .filter(|(n, _)| !removed[n / 3])
+            .map(|(_, &i)| welded[i])
+            .collect();
+        let mut used = vec![false; positions.len()];
+        for &i in &indices {
+            used[i] = true;
+        }
+        // The vertices that are kept, and the new index of every kept vertex.
+        let kept: Vec<usize> = (0..positions.len()).filter(|&i| used[i]).collect();
+        let mut new_indices = vec![0; positions.len()];
+        for (new_index, &i) in kept.iter().enumerate() {
+            new_indices[i] = new_index as u32;
+        }
+
+        let indices = indices.into_iter().map(|i| new_indices[i]).collect();
+        for attribute in union.attributes.values_mut() {
+            duplicate_attribute_values(&mut attribute.values, kept.iter().copied());
+        }
+        union.insert_indices(Indices::U32(indices));
+        union.morph_targets = None;
+        union.morph_target_names = None;
+        Ok(union)
+    }
+
+    /// Bakes ambient occlusion into the [`Mesh::ATTRIBUTE_COLOR`] of each vertex.
+    ///
+    /// For every vertex, `samples` rays are cast from it into the hemisphere around its normal,
+    /// spread evenly by solid angle. The occlusion of the vertex is the fraction of rays that hit
+    /// a triangle of the mesh, and its color is multiplied by one minus the occlusion. Vertices
+    /// in creases and corners that face into the mesh are darkened, while vertices where nothing
+    /// blocks the view, like those of convex meshes, are unchanged. Vertices without a color
+    /// start out white.
+    ///
+    /// This takes time proportional to the number of vertices times the number of triangles
+    /// times `samples`, so it's meant to be run once on static meshes, not every frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mesh's topology isn't [`PrimitiveTopology::TriangleList`] or
+    /// [`PrimitiveTopology::TriangleStrip`], if [`Mesh::ATTRIBUTE_POSITION`] or
+    /// [`Mesh::ATTRIBUTE_NORMAL`] is missing or not of type `float3`, or if
+    /// [`Mesh::ATTRIBUTE_COLOR`] is present but not of type `float4`.
+    pub fn bake_vertex_ao(&mut self, samples: usize) {
+        let triangles: Vec<[Vec3; 3]> = self
+            .triangles()
+            .unwrap_or_else(|error| panic!("can't bake ambient occlusion for this mesh: {error}"))
+            .map(|triangle| triangle.vertices)
+            .collect();
+        let positions = self
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+            .expect("`Mesh::ATTRIBUTE_POSITION` must be of type `float3`");
+        let normals = self
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3)
+            .expect("`Mesh::ATTRIBUTE_NORMAL` must be of type `float3`");
+
+        // Rays start slightly off the surface, so that they don't hit the triangles of their own
+        // vertex. This is relative to the size of the mesh, so that it works at any scale.
+        let extent = positions
+            .iter()
+            .fold(Vec3::ZERO, |extent, &position| {
+                extent.max(Vec3::from(position).abs())
+            })
+            .max_element();
+        let offset = extent.max(f32::MIN_POSITIVE) * 1e-4;
+
+        // Directions in the hemisphere around +Z, on a Fibonacci spiral. Every direction covers the
+        // same solid angle, as their z coordinates are evenly spaced.
+        let golden_angle = core::f32::consts::PI * (3.0 - ops::sqrt(5.0));
+        let directions: Vec<Vec3> = (0..samples)
+            .map(|i| {
+                let z = 1.0 - (i as f32 + 0.5) / samples as f32;
+                let radius = ops::sqrt(1.0 - z * z);
+                let (sin, cos) = ops::sin_cos(i as f32 * golden_angle);
+                Vec3::new(radius * cos, radius * sin, z)
+            })
+            .collect();
+
+        let ambient: Vec<f32> = positions
+            .iter()
+            .zip(normals)
+            .map(|(&position, &normal)| {
+                let Ok(normal) = Dir3::new(normal.into()) else {
+                    return 1.0;
+                };
+                let origin = Vec3::from(position) + normal * offset;
+                let rotation = Quat::from_rotation_arc(Vec3::Z, *normal);
+                let hits = directions
+                    .iter()
+                    .filter(|&&direction| {
+                        let direction = rotation * direction;
+                        triangles.iter().any(|triangle| {
+                            // Accept hits on surfaces through the origin, which meet the vertex's
+                            // own surface in a crease, but not on its own triangles.
+                            ray_triangle_distance(origin, direction, triangle)
+                                .is_some_and(|distance| distance > -0.5 * offset)
+                        })
+                    })
+                    .count();
+                1.0 - hits as f32 / samples.max(1) as f32
+            })
+            .collect();
+
+        let colors = match self.remove_attribute(Mesh::ATTRIBUTE_COLOR) {
+            Some(VertexAttributeValues::Float32x4(colors)) => colors,
+            Some(_) => panic!("`Mesh::ATTRIBUTE_COLOR` must be of type `float4`"),
+            None => vec![[1.0; 4]; ambient.len()],
+        };
+        let colors: Vec<[f32; 4]> = colors
+            .into_iter()
+            .zip(ambient)
+            .map(|([r, g, b, a], ambient)| [r * ambient, g * ambient, b * ambient, a])
+            .collect();
+        self.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    }
+
+    /// Sets the [`Mesh::ATTRIBUTE_COLOR`] of each vertex to a color of `range`, depending on how far
+    /// the vertex is along `axis`.
+    ///
+    /// The vertices lowest along `axis` get the start of the range, the highest ones get its end,
+    /// and the others are in between, in proportion to their position. If all vertices are equally
+    /// far along `axis`, like those of a plane facing it, they all get the start of the range.
+    /// Existing vertex colors are replaced.
+    ///
+    /// ```
+    /// # use bevy_color::{palettes::basic::{BLUE, RED}, Color};
+    /// # use bevy_math::{prelude::Cuboid, Dir3};
+    /// # use bevy_mesh::{Mesh, MeshBuilder, Meshable};
+    /// // Fade from red at the bottom to blue at the top.
+    /// let mut mesh = Cuboid::default().mesh().build();
+    /// mesh.fill_color_gradient(&(Color::from(RED)..Color::from(BLUE)), Dir3::Y);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Mesh::ATTRIBUTE_POSITION`] is missing or not of type `float3`.
+    pub fn fill_color_gradient(&mut self, range: &impl ColorRange<Color>, axis: Dir3) {
+        let positions = self
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+            .expect("`Mesh::ATTRIBUTE_POSITION` must be of type `float3`");
+
+        let heights: Vec<f32> = positions
+            .iter()
+            .map(|&position| Vec3::from(position).dot(*axis))
+            .collect();
+        let (min, max) = heights
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &height| {
+                (min.min(height), max.max(height))
+            });
+        let extent = max - min;
+
+        let colors: Vec<[f32; 4]> = heights
+            .into_iter()
+            .map(|height| {
+                let factor = if extent > 0.0 {
+                    (height - min) / extent
+                } else {
+                    0.0
+                };
+                LinearRgba::from(range.at(factor)).to_f32_array()
+            })
+            .collect();
+        self.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    }
+
+    /// Returns the vertex indices of each of this Mesh's triangles, in the order returned by
+    /// [`Mesh::triangles`].
+    fn triangle_vertex_indices(&self) -> Result<Vec<[usize; 3]>, MeshTrianglesError> {
+        let Some(position_data) = self.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            return Err(MeshTrianglesError::MissingPositions);
+        };
+
+        let Some(vertices) = position_data.as_float3() else {
+            return Err(MeshTrianglesError::PositionsFormat);
+        };
+
+        let Some(indices) = self.indices() else {
+            return Err(MeshTrianglesError::MissingIndices);
+        };
+
+        let indices: Vec<usize> = indices.iter().collect();
+        // Like `Mesh::triangles`, triangles referencing out-of-bounds vertex data are omitted.
+        let in_bounds = |triangle: &[usize; 3]| triangle.iter().all(|&i| i < vertices.len());
+
+        match self.primitive_topology {
+            PrimitiveTopology::TriangleList => Ok(indices
+                .chunks_exact(3)
+                .map(|chunk| [chunk[0], chunk[1], chunk[2]])
+                .filter(in_bounds)
+                .collect()),
+            PrimitiveTopology::TriangleStrip => Ok(indices
+                .windows(3)
+                .enumerate()
+                .map(|(i, window)| {
+                    if i % 2 == 0 {
+                        [window[0], window[1], window[2]]
+                    } else {
+                        [window[1], window[0], window[2]]
+                    }
+                })
+                .filter(in_bounds)
+                .collect()),
+            _ => Err(MeshTrianglesError::WrongTopology),
+        }
+    }
+}
+
+/// Returns a key identifying the given vector by its exact coordinates, for welding vertices.
+///
+/// Negative zeros are turned positive, so that they're welded with positive ones.
+fn weld_key(vector: Vec3) -> [u32; 3] {
+    (vector + Vec3::ZERO).to_array().map(f32::to_bits)
+}
+
+/// The distance within which [`Mesh::union`] welds vertices, and considers points to lie on
+/// triangles.
+const UNION_WELD_DISTANCE: f32 = 1.0e-4;
+
+/// Returns the cell of the grid with [`UNION_WELD_DISTANCE`] sized cells that contains `position`.
+fn weld_cell(position: Vec3) -> IVec3 {
+    (position / UNION_WELD_DISTANCE).floor().as_ivec3()
+}
+
+/// Returns the vertices within [`UNION_WELD_DISTANCE`] of `position`, out of those in `cells`,
+/// which are grouped by their [`weld_cell`].
+fn vertices_near<'a>(
+    cells: &'a HashMap<IVec3, Vec<usize>>,
+    positions: &'a [Vec3],
+    position: Vec3,
+) -> impl Iterator<Item = usize> + 'a {
+    let cell = weld_cell(position);
+    (-1..=1)
+        .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| IVec3::new(x, y, z))))
+        .filter_map(move |offset| cells.get(&(cell + offset)))
+        .flatten()
+        .copied()
+        .filter(move |&i| positions[i].distance(position) <= UNION_WELD_DISTANCE)
+}
+
+/// Returns whether `point` lies on the triangle, within [`UNION_WELD_DISTANCE`].
+fn is_on_triangle(point: Vec3, &[a, b, c]: &[Vec3; 3]) -> bool {
+    let normal = (b - a).cross(c - a).normalize_or_zero();
+    if normal == Vec3::ZERO || (point - a).dot(normal).abs() > UNION_WELD_DISTANCE {
+        return false;
+    }
+    [(a, b), (b, c), (c, a)].into_iter().all(|(start, end)| {
+        let edge = end - start;
+        edge.cross(point - start).dot(normal) >= -UNION_WELD_DISTANCE * edge.length()
+    })
+}
+
+/// Returns the signed distance along the ray from `origin` in `direction` at which it crosses the
+/// plane of the triangle, if the crossing point is inside the triangle.
+///
+/// This uses the Möller-Trumbore algorithm, and accepts triangles facing either way.
+fn ray_triangle_distance(origin: Vec3, direction: Vec3, [a, b, c]: &[Vec3; 3]) -> Option<f32> {
+    let edge_1 = *b - *a;
+    let edge_2 = *c - *a;
+    let p = direction.cross(edge_2);
+    let determinant = edge_1.dot(p);
+    if determinant.abs() < f32::EPSILON {
+        // The ray is parallel to the triangle.
+        return None;
+    }
+    let inverse_determinant = determinant.recip();
+    let to_origin = origin - *a;
+    let u = to_origin.dot(p) * inverse_determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = to_origin.cross(edge_1);
+    let v = direction.dot(q) * inverse_determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    Some(edge_2.dot(q) * inverse_determinant)
+}
+
+/// Replaces `values` with the values at each of the given `indices`, in order.
+fn duplicate_attribute_values(
+    values: &mut VertexAttributeValues,
+    indices: impl Iterator<Item = usize>,
+) {
+    fn duplicate<T: Copy>(values: &[T], indices: impl Iterator<Item = usize>) -> Vec<T> {
+        indices.map(|i| values[i]).collect()
+    }
+
+    #[expect(
+        clippy::match_same_arms,
+        reason = "Although the `vec` binding on some match arms may have different types, each variant has different semantics; thus it's not guaranteed that they will use the same type forever."
+    )]
+    match values {
+        VertexAttributeValues::Float32(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Sint32(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Uint32(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Float32x2(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Sint32x2(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Uint32x2(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Float32x3(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Sint32x3(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Uint32x3(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Sint32x4(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Uint32x4(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Float32x4(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Sint16x2(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Snorm16x2(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Uint16x2(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Unorm16x2(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Sint16x4(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Snorm16x4(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Uint16x4(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Unorm16x4(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Sint8x2(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Snorm8x2(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Uint8x2(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Unorm8x2(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Sint8x4(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Snorm8x4(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Uint8x4(vec) => *vec = duplicate(vec, indices),
+        VertexAttributeValues::Unorm8x4(vec) => *vec = duplicate(vec, indices),
+    }
+}
+
+impl core::ops::Mul<Mesh> for Transform {
+    type Output = Mesh;
+
+    fn mul(self, rhs: Mesh) -> Self::Output {
+        rhs.transformed_by(self)
+    }
+}
+
+/// Error that can occur when calling [`Mesh::merge`] or [`Mesh::merge_transformed`].
+#[derive(Error, Debug, Clone)]
+pub enum MergeMeshError {
+    #[error("Incompatible vertex attribute types {} and {}", self_attribute.name, other_attribute.map(|a| a.name).unwrap_or("None"))]
+    IncompatibleVertexAttributes {
+        self_attribute: MeshVertexAttribute,
+        other_attribute: Option<MeshVertexAttribute>,
+    },
+    #[error("Vertex attribute {} is missing from the mesh being merged into", attribute.name)]
+    MissingVertexAttribute { attribute: MeshVertexAttribute },
+    #[error("Incompatible primitive topologies {self_primitive_topology:?} and {other_primitive_topology:?}")]
+    IncompatiblePrimitiveTopology {
+        self_primitive_topology: PrimitiveTopology,
+        other_primitive_topology: PrimitiveTopology,
+    },
+}
+
+/// Error that can occur when calling [`Mesh::compute_creased_normals`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MeshNormalsError {
+    #[error("Source mesh does not have primitive topology TriangleList")]
+    WrongTopology,
+    #[error("Source mesh lacks position data")]
+    MissingPositions,
+    #[error("Source mesh position data is not Float32x3")]
+    PositionsFormat,
+    #[error("Source mesh lacks face index data")]
+    MissingIndices,
+    #[error("Face index data references vertices that do not exist")]
+    BadIndices,
+}
+
+/// Error that can occur when calling [`Mesh::validate_tangent_space`].
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TangentError {
+    #[error("missing vertex attributes '{0}'")]
+    MissingVertexAttribute(&'static str),
+    #[error("the '{0}' vertex attribute should have {1:?} format")]
+    InvalidVertexAttributeFormat(&'static str, VertexFormat),
+    #[error("the tangent of vertex {vertex} has handedness {w}, which should be 1 or -1")]
+    InvalidHandedness { vertex: usize, w: f32 },
+    #[error("the tangent of vertex {vertex} is zero")]
+    ZeroTangent { vertex: usize },
+    #[error("the tangent of vertex {vertex} isn't orthogonal to its normal")]
+    NotOrthogonal { vertex: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mesh;
+    use crate::mesh::{
+        Indices, MeshNormalsError, MeshWindingInvertError, TangentError, VertexAttributeValues,
+    };
+    use crate::{MeshBuilder, Meshable, PrimitiveTopology};
+    use bevy_asset::RenderAssetUsages;
+    use bevy_math::primitives::{Capsule3d, Cuboid, Plane3d, Triangle3d};
+    use bevy_math::{ops, Quat, Vec3};
+    use bevy_transform::components::Transform;
+    use core::f32::consts::{FRAC_PI_2, PI};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    #[should_panic]
+    fn panic_invalid_format() {
+        let _mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0, 0.0]]);
+    }
+
+    #[test]
+    fn transform_mesh() {
+        let mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[-1., -1., 2.], [1., -1., 2.], [0., 1., 2.]],
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            vec![
+                Vec3::new(-1., -1., 1.).normalize().to_array(),
+                Vec3::new(1., -1., 1.).normalize().to_array(),
+                [0., 0., 1.],
+            ],
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0., 0.], [1., 0.], [0.5, 1.]]);
+
+        let mesh = mesh.transformed_by(
+            Transform::from_translation(Vec3::splat(-2.)).with_scale(Vec3::new(2., 0., -1.)),
+        );
+
+        if let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        {
+            // All positions are first scaled resulting in `vec![[-2, 0., -2.], [2., 0., -2.], [0., 0., -2.]]`
+            // and then shifted by `-2.` along each axis
+            assert_eq!(
+                positions,
+                &vec![[-4.0, -2.0, -4.0], [0.0, -2.0, -4.0], [-2.0, -2.0, -4.0]]
+            );
+        } else {
+            panic!("Mesh does not have a position attribute");
+        }
+
+        if let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        {
+            assert_eq!(normals, &vec![[0., -1., 0.], [0., -1., 0.], [0., 0., -1.]]);
+        } else {
+            panic!("Mesh does not have a normal attribute");
+        }
+
+        if let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+            assert_eq!(uvs, &vec![[0., 0.], [1., 0.], [0.5, 1.]]);
+        } else {
+            panic!("Mesh does not have a uv attribute");
+        }
+    }
+
+    #[test]
+    fn point_list_mesh_invert_winding() {
+        let mesh = Mesh::new(PrimitiveTopology::PointList, RenderAssetUsages::default())
+            .with_inserted_indices(Indices::U32(vec![]));
+        assert!(matches!(
+            mesh.with_inverted_winding(),
+            Err(MeshWindingInvertError::WrongTopology)
+        ));
+    }
+
+    #[test]
+    fn line_list_mesh_invert_winding() {
+        let mesh = Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
+            .with_inserted_indices(Indices::U32(vec![0, 1, 1, 2, 2, 3]));
         let mesh = mesh.with_inverted_winding().unwrap();
         assert_eq!(
             mesh.indices().unwrap().iter().collect::<Vec<usize>>(),
@@ -1475,6 +2603,126 @@ mod tests {
         assert_eq!([1., 0., 0.], normals[3]);
     }
 
+    #[test]
+    fn compute_creased_normals() {
+        let cuboid = Cuboid::default().mesh().build();
+        let face_normals = cuboid
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .unwrap()
+            .as_float3()
+            .unwrap()
+            .to_vec();
+        let positions = cuboid
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .unwrap()
+            .to_vec();
+
+        // The same cube with its 8 corners shared by the faces around them.
+        let mut corners: Vec<[f32; 3]> = Vec::new();
+        let mut welded_indices: Vec<u16> = cuboid
+            .indices()
+            .unwrap()
+            .iter()
+            .map(|i| match corners.iter().position(|&c| c == positions[i]) {
+                Some(corner) => corner as u16,
+                None => {
+                    corners.push(positions[i]);
+                    corners.len() as u16 - 1
+                }
+            })
+            .collect();
+        // A degenerate triangle is ignored.
+        welded_indices.extend([0, 0, 1]);
+        let welded = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, corners)
+        .with_inserted_indices(Indices::U16(welded_indices));
+
+        let normals = |mesh: &Mesh| {
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+                .unwrap()
+                .as_float3()
+                .unwrap()
+                .iter()
+                .map(|&normal| Vec3::from(normal))
+                .collect::<Vec<_>>()
+        };
+        // Each corner of a triangle has the normal of its side of the cube, made of two
+        // triangles, or of the corner of the cube.
+        let assert_normals = |mesh: &Mesh, smooth: bool| {
+            let positions = mesh
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3()
+                .unwrap();
+            let normals = normals(mesh);
+            for (triangle, indices) in mesh.indices().unwrap().iter().collect::<Vec<_>>()[..36]
+                .chunks_exact(3)
+                .enumerate()
+            {
+                for &i in indices {
+                    let expected = if smooth {
+                        Vec3::from(positions[i]).normalize()
+                    } else {
+                        Vec3::from(face_normals[triangle / 2 * 4])
+                    };
+                    assert!(normals[i].abs_diff_eq(expected, 1e-5), "{normals:?}");
+                }
+            }
+        };
+
+        for mut mesh in [cuboid.clone(), welded.clone()] {
+            mesh.remove_attribute(Mesh::ATTRIBUTE_NORMAL);
+            let smooth = mesh.clone().with_computed_creased_normals(PI).unwrap();
+            assert_normals(&smooth, true);
+            let flat = mesh.with_computed_creased_normals(0.0).unwrap();
+            assert_normals(&flat, false);
+        }
+
+        // The welded cube is split into a vertex for each corner of each face when flat.
+        let flat = welded.clone().with_computed_creased_normals(0.0).unwrap();
+        assert_eq!(flat.count_vertices(), 24);
+        assert!(matches!(flat.indices(), Some(Indices::U16(_))));
+        let smooth = welded.with_computed_creased_normals(PI).unwrap();
+        assert_eq!(smooth.count_vertices(), 8);
+        // Edges sharper than the crease angle stay sharp.
+        let creased = Cuboid::default()
+            .mesh()
+            .build()
+            .with_computed_creased_normals(FRAC_PI_2 - 0.1)
+            .unwrap();
+        assert_eq!(
+            normals(&creased),
+            face_normals
+                .iter()
+                .copied()
+                .map(Vec3::from)
+                .collect::<Vec<_>>()
+        );
+
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+        );
+        assert_eq!(
+            mesh.compute_creased_normals(PI),
+            Err(MeshNormalsError::MissingIndices)
+        );
+        mesh.insert_indices(Indices::U32(vec![0, 1, 3]));
+        assert_eq!(
+            mesh.compute_creased_normals(PI),
+            Err(MeshNormalsError::BadIndices)
+        );
+    }
+
     #[test]
     fn triangles_from_triangle_list() {
         let mut mesh = Mesh::new(
@@ -1551,4 +2799,543 @@ mod tests {
             mesh.triangles().unwrap().collect::<Vec<Triangle3d>>()
         );
     }
+
+    #[test]
+    fn triangle_areas_and_surface_area() {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0., 0., 0.], [2., 0., 0.], [0., 3., 0.], [0., 0., 1.]],
+        );
+        mesh.insert_indices(Indices::U16(vec![0, 1, 2, 0, 2, 3]));
+        assert_eq!(vec![3.0, 1.5], mesh.triangle_areas().unwrap());
+        assert_eq!(4.5, mesh.surface_area().unwrap());
+
+        let cube = Cuboid::new(1.0, 1.0, 1.0).mesh().build();
+        assert!((cube.surface_area().unwrap() - 6.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn remove_and_rename_attributes() {
+        let mut mesh = Capsule3d::default().mesh().build();
+        let vertex_count = mesh.count_vertices();
+
+        assert!(mesh.remove_attribute(Mesh::ATTRIBUTE_UV_0).is_some());
+        assert!(!mesh.contains_attribute(Mesh::ATTRIBUTE_UV_0));
+        assert!(mesh.contains_attribute(Mesh::ATTRIBUTE_POSITION));
+        assert_eq!(vertex_count, mesh.count_vertices());
+
+        let uvs = Capsule3d::default()
+            .mesh()
+            .build()
+            .with_renamed_attribute(Mesh::ATTRIBUTE_UV_0, Mesh::ATTRIBUTE_UV_1);
+        assert!(!uvs.contains_attribute(Mesh::ATTRIBUTE_UV_0));
+        assert_eq!(
+            Some(vertex_count),
+            uvs.attribute(Mesh::ATTRIBUTE_UV_1)
+                .map(VertexAttributeValues::len)
+        );
+
+        // Renaming a missing attribute does nothing.
+        assert!(!mesh.rename_attribute(Mesh::ATTRIBUTE_UV_0, Mesh::ATTRIBUTE_UV_1));
+        assert!(!mesh.contains_attribute(Mesh::ATTRIBUTE_UV_1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rename_attribute_invalid_format() {
+        Capsule3d::default()
+            .mesh()
+            .build()
+            .rename_attribute(Mesh::ATTRIBUTE_UV_0, Mesh::ATTRIBUTE_COLOR);
+    }
+
+    #[test]
+    fn shell_of_closed_cube() {
+        let cube = Cuboid::from_length(2.0).mesh().build();
+        let shell = cube.to_shell(0.25);
+
+        // A closed mesh has no borders, so the shell is just the two surfaces.
+        assert_eq!(shell.count_vertices(), 2 * cube.count_vertices());
+        let indices: Vec<usize> = shell.indices().unwrap().iter().collect();
+        assert_eq!(indices.len(), 2 * cube.indices().unwrap().len());
+        assert_eq!(indices.len() % 3, 0);
+        assert!(indices.iter().all(|&i| i < shell.count_vertices()));
+        for (_, values) in shell.attributes() {
+            assert_eq!(values.len(), shell.count_vertices());
+        }
+
+        let positions = shell
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+            .unwrap();
+        let normals = shell
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3)
+            .unwrap();
+        let (outer, inner) = positions.split_at(cube.count_vertices());
+        let (outer_normals, inner_normals) = normals.split_at(cube.count_vertices());
+        for i in 0..cube.count_vertices() {
+            let normal = Vec3::from(outer_normals[i]);
+            let expected = Vec3::from(outer[i]) - 0.25 * normal;
+            assert!(Vec3::from(inner[i]).abs_diff_eq(expected, 1e-6));
+            assert_eq!(Vec3::from(inner_normals[i]), -normal);
+        }
+
+        // The inner surface faces inwards.
+        for triangle in shell.triangles().unwrap().skip(12) {
+            let normal = triangle.normal().unwrap();
+            assert!(normal.dot(triangle.centroid()) < 0.0);
+        }
+    }
+
+    #[test]
+    fn shell_of_open_plane() {
+        let plane = Plane3d::default().mesh().size(2.0, 2.0).build();
+        let shell = plane.to_shell(0.5);
+
+        // Two surfaces of two triangles each, and a wall of two triangles along each of the
+        // four borders.
+        assert_eq!(shell.count_vertices(), 2 * 4 + 4 * 4);
+        let indices: Vec<usize> = shell.indices().unwrap().iter().collect();
+        assert_eq!(indices.len(), 3 * (2 * 2 + 4 * 2));
+        assert!(indices.iter().all(|&i| i < shell.count_vertices()));
+
+        // The walls face outwards, and span the thickness of the shell.
+        for wall in shell.triangles().unwrap().skip(4) {
+            let normal = wall.normal().unwrap();
+            let centroid = wall.centroid();
+            assert_eq!(normal.y, 0.0);
+            assert!(normal.dot(centroid) > 0.0);
+            assert!(wall.vertices.iter().all(|v| v.y == 0.0 || v.y == -0.5));
+        }
+    }
+
+    #[test]
+    fn union_of_adjacent_cubes() {
+        let cube = Cuboid::new(1.0, 1.0, 1.0).mesh().build();
+        // The rotated cube's positions and normals are only close to the ones of the first cube.
+        for transform in [
+            Transform::from_xyz(1.0, 0.0, 0.0),
+            Transform::from_xyz(1.0, 0.0, 0.0).with_rotation(Quat::from_rotation_y(FRAC_PI_2)),
+        ] {
+            let union = cube.union(&cube, transform).unwrap();
+
+            // The shared face of each cube is removed, and the two vertices along the shared edge
+            // of each of the four faces that continue across both cubes are welded.
+            assert_eq!(union.count_vertices(), 2 * 24 - 2 * 4 - 4 * 2);
+            let indices: Vec<usize> = union.indices().unwrap().iter().collect();
+            assert_eq!(indices.len(), 2 * 36 - 2 * 6);
+            assert!(indices.iter().all(|&i| i < union.count_vertices()));
+            for attribute in union.attributes() {
+                assert_eq!(attribute.1.len(), union.count_vertices());
+            }
+
+            let positions = union
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3()
+                .unwrap();
+            let normals = union
+                .attribute(Mesh::ATTRIBUTE_NORMAL)
+                .unwrap()
+                .as_float3()
+                .unwrap();
+            let shared_face: Vec<usize> = (0..union.count_vertices())
+                .filter(|&i| ops::abs(positions[i][0] - 0.5) < 1.0e-4)
+                .collect();
+            assert_eq!(shared_face.len(), 4 * 2);
+            for &vertex in &shared_face {
+                // None of the shared face's own vertices are left.
+                assert!(ops::abs(normals[vertex][0]) < 1.0e-4);
+                // Triangles of both cubes use each welded vertex.
+                let triangles_using_vertex = indices
+                    .chunks_exact(3)
+                    .filter(|triangle| triangle.contains(&vertex))
+                    .map(|triangle| {
+                        triangle
+                            .iter()
+                            .map(|&i| positions[i][0])
+                            .fold(0.0, |a: f32, b| a.max(b))
+                    })
+                    .collect::<Vec<_>>();
+                assert!(triangles_using_vertex.iter().any(|&x| x < 0.5 + 1.0e-4));
+                assert!(triangles_using_vertex.iter().any(|&x| x > 1.5 - 1.0e-4));
+            }
+        }
+    }
+
+    #[test]
+    fn validate_tangent_space() {
+        let mut mesh = Cuboid::default().mesh().build();
+        assert_eq!(
+            mesh.validate_tangent_space(),
+            Err(TangentError::MissingVertexAttribute(
+                Mesh::ATTRIBUTE_TANGENT.name
+            ))
+        );
+
+        mesh.generate_tangents().unwrap();
+        assert_eq!(mesh.validate_tangent_space(), Ok(()));
+
+        let Some(VertexAttributeValues::Float32x4(tangents)) =
+            mesh.attribute(Mesh::ATTRIBUTE_TANGENT).cloned()
+        else {
+            panic!("Expected tangents f32x4");
+        };
+        let normal = Vec3::from(
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+                .unwrap()
+                .as_float3()
+                .unwrap()[0],
+        );
+        // A tangent halfway between the normal and the surface.
+        let leaning = normal + normal.any_orthonormal_vector();
+        let with_tangent = |vertex: usize, tangent: [f32; 4]| {
+            let mut tangents = tangents.clone();
+            tangents[vertex] = tangent;
+            mesh.clone()
+                .with_inserted_attribute(Mesh::ATTRIBUTE_TANGENT, tangents)
+        };
+
+        assert_eq!(
+            with_tangent(0, [1.0, 0.0, 0.0, 0.5]).validate_tangent_space(),
+            Err(TangentError::InvalidHandedness { vertex: 0, w: 0.5 })
+        );
+        assert_eq!(
+            with_tangent(0, [0.0, 0.0, 0.0, 1.0]).validate_tangent_space(),
+            Err(TangentError::ZeroTangent { vertex: 0 })
+        );
+        assert_eq!(
+            with_tangent(0, leaning.extend(1.0).to_array()).validate_tangent_space(),
+            Err(TangentError::NotOrthogonal { vertex: 0 })
+        );
+    }
+
+    #[test]
+    fn merge_transformed() {
+        use super::MergeMeshError;
+
+        let cuboid = Cuboid::new(1.0, 2.0, 1.0).mesh().build();
+        let mut mesh = cuboid.clone();
+        mesh.merge_transformed(
+            &cuboid,
+            Transform::from_xyz(3.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
+        )
+        .unwrap();
+
+        let vertex_count = cuboid.count_vertices();
+        assert_eq!(mesh.count_vertices(), vertex_count * 2);
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .unwrap();
+        let (min, max) = positions.iter().fold(
+            (Vec3::INFINITY, Vec3::NEG_INFINITY),
+            |(min, max), &position| (min.min(position.into()), max.max(position.into())),
+        );
+        assert_eq!(min, Vec3::new(-0.5, -2.0, -1.0));
+        assert_eq!(max, Vec3::new(4.0, 2.0, 1.0));
+
+        // The indices of the second cuboid point to its own vertices.
+        let cuboid_indices: Vec<usize> = cuboid.indices().unwrap().iter().collect();
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        assert_eq!(indices.len(), cuboid_indices.len() * 2);
+        assert_eq!(&indices[..cuboid_indices.len()], &cuboid_indices[..]);
+        for (index, cuboid_index) in indices[cuboid_indices.len()..].iter().zip(&cuboid_indices) {
+            assert_eq!(*index, cuboid_index + vertex_count);
+        }
+
+        // Merging into an empty mesh gives a transformed copy, and merging an empty mesh does
+        // nothing.
+        let transform = Transform::from_xyz(0.0, 5.0, 0.0);
+        let mut empty = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        empty.merge_transformed(&cuboid, transform).unwrap();
+        let moved = cuboid.clone().transformed_by(transform);
+        assert_eq!(
+            empty
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3(),
+            moved
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3()
+        );
+        let mut copy = cuboid.clone();
+        copy.merge_transformed(
+            &Mesh::new(
+                PrimitiveTopology::TriangleList,
+                RenderAssetUsages::default(),
+            ),
+            transform,
+        )
+        .unwrap();
+        assert_eq!(copy.count_vertices(), vertex_count);
+
+        // Meshes with different UV channels can't be merged, whichever has more of them.
+        let mut with_uv_1 = cuboid.clone();
+        let uvs = with_uv_1.attribute(Mesh::ATTRIBUTE_UV_0).unwrap().clone();
+        with_uv_1.insert_attribute(Mesh::ATTRIBUTE_UV_1, uvs);
+        let mut copy = cuboid.clone();
+        assert!(matches!(
+            copy.merge_transformed(&with_uv_1, Transform::IDENTITY),
+            Err(MergeMeshError::MissingVertexAttribute { attribute }) if attribute.id == Mesh::ATTRIBUTE_UV_1.id
+        ));
+        assert!(matches!(
+            with_uv_1.merge_transformed(&cuboid, Transform::IDENTITY),
+            Err(MergeMeshError::IncompatibleVertexAttributes {
+                other_attribute: None,
+                ..
+            })
+        ));
+        // `self` is left untouched on errors.
+        assert_eq!(copy.count_vertices(), vertex_count);
+        assert_eq!(with_uv_1.count_vertices(), vertex_count);
+
+        // Neither can meshes with different topologies.
+        let lines = Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0; 3], [1.0; 3]]);
+        assert!(matches!(
+            copy.merge_transformed(&lines, Transform::IDENTITY),
+            Err(MergeMeshError::IncompatiblePrimitiveTopology { .. })
+        ));
+    }
+
+    #[test]
+    fn bake_vertex_ao() {
+        // A small cube standing on a large one, which makes a concave crease around its base.
+        let mut mesh = Cuboid::new(2.0, 1.0, 2.0).mesh().build();
+        mesh.merge(
+            &Cuboid::new(1.0, 1.0, 1.0)
+                .mesh()
+                .build()
+                .transformed_by(Transform::from_xyz(0.0, 1.0, 0.0)),
+        )
+        .unwrap();
+        mesh.bake_vertex_ao(64);
+
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .unwrap();
+        let normals = mesh
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .unwrap()
+            .as_float3()
+            .unwrap();
+        let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+        else {
+            panic!("Expected colors f32x4");
+        };
+        assert_eq!(colors.len(), positions.len());
+
+        for ((position, normal), color) in positions.iter().zip(normals).zip(colors) {
+            let ambient = color[0];
+            assert_eq!(color, &[ambient, ambient, ambient, 1.0]);
+            if position[1] == 0.5 && normal[1] == 0.0 && position[0].abs() <= 0.5 {
+                // The sides of the small cube face down into the crease at their base.
+                assert!(ambient < 0.6, "{position:?} {normal:?} {ambient}");
+            } else if normal[1] != 0.0 && (position[1] == -0.5 || position[1] == 1.5) {
+                // The bottom of the large cube and the top of the small cube aren't occluded.
+                assert_eq!(ambient, 1.0, "{position:?} {normal:?}");
+            } else if position[1] == 0.5 && normal[1] == 1.0 && position[0].abs() == 1.0 {
+                // The outer corners of the top of the large cube only see the small cube.
+                assert!(ambient > 0.8, "{position:?} {normal:?} {ambient}");
+            }
+        }
+    }
+
+    #[test]
+    fn fill_color_gradient() {
+        use bevy_color::{
+            palettes::basic::{BLUE, RED},
+            Color, ColorToComponents, LinearRgba,
+        };
+        use bevy_math::Dir3;
+
+        let start = LinearRgba::from(RED).to_f32_array();
+        let end = LinearRgba::from(BLUE).to_f32_array();
+        let range = Color::from(RED)..Color::from(BLUE);
+
+        for axis in [Dir3::Y, Dir3::NEG_X, Dir3::new(Vec3::ONE).unwrap()] {
+            let mut mesh = Cuboid::new(1.0, 2.0, 3.0)
+                .mesh()
+                .build()
+                .translated_by(Vec3::new(4.0, -5.0, 6.0));
+            mesh.fill_color_gradient(&range, axis);
+
+            let positions = mesh
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3()
+                .unwrap();
+            let Some(VertexAttributeValues::Float32x4(colors)) =
+                mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+            else {
+                panic!("Expected colors f32x4");
+            };
+            assert_eq!(colors.len(), positions.len());
+
+            let heights: Vec<f32> = positions
+                .iter()
+                .map(|&position| Vec3::from(position).dot(*axis))
+                .collect();
+            let min = heights.iter().copied().fold(f32::INFINITY, f32::min);
+            let max = heights.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            for (height, color) in heights.iter().zip(colors) {
+                if *height == min {
+                    assert_eq!(*color, start, "{axis:?}");
+                } else if *height == max {
+                    assert_eq!(*color, end, "{axis:?}");
+                } else {
+                    assert_ne!(*color, start, "{axis:?}");
+                    assert_ne!(*color, end, "{axis:?}");
+                }
+            }
+        }
+
+        // A flat mesh facing the axis gets the start of the range everywhere.
+        let mut mesh = Plane3d::default().mesh().build();
+        mesh.fill_color_gradient(&range, Dir3::Y);
+        let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+        else {
+            panic!("Expected colors f32x4");
+        };
+        assert!(colors.iter().all(|color| *color == start));
+    }
+
+    #[test]
+    fn to_wireframe() {
+        let triangle = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        );
+        let wireframe = triangle.to_wireframe();
+        assert_eq!(wireframe.primitive_topology(), PrimitiveTopology::LineList);
+        assert_eq!(
+            wireframe
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3(),
+            triangle
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3()
+        );
+        let indices: Vec<usize> = wireframe.indices().unwrap().iter().collect();
+        assert_eq!(indices, [0, 1, 1, 2, 2, 0]);
+
+        // The diagonal of a quad is shared by both of its triangles, but only drawn once, even
+        // though the triangles go along it in opposite directions.
+        let quad = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+            ],
+        )
+        .with_inserted_indices(Indices::U16(vec![0, 1, 2, 2, 3, 0]));
+        let indices: Vec<usize> = quad.to_wireframe().indices().unwrap().iter().collect();
+        assert_eq!(indices, [0, 1, 1, 2, 2, 0, 2, 3, 3, 0]);
+    }
+
+    #[test]
+    fn debug_normals_mesh() {
+        let mesh = Cuboid::new(1.0, 1.0, 1.0).mesh().build();
+        let normals_mesh = mesh.debug_normals_mesh(0.5);
+
+        assert_eq!(
+            PrimitiveTopology::LineList,
+            normals_mesh.primitive_topology()
+        );
+        assert_eq!(2 * mesh.count_vertices(), normals_mesh.count_vertices());
+
+        let Some(VertexAttributeValues::Float32x3(lines)) =
+            normals_mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("Expected positions f32x3");
+        };
+        for line in lines.chunks_exact(2) {
+            let length = Vec3::from(line[0]).distance(Vec3::from(line[1]));
+            assert!((length - 0.5).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn sample_surface_is_area_weighted() {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        // The first triangle lies in the XY plane with an area of 3, the second lies in the YZ
+        // plane with an area of 1.5.
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0., 0., 0.], [2., 0., 0.], [0., 3., 0.], [0., 0., 1.]],
+        );
+        mesh.insert_indices(Indices::U16(vec![0, 1, 2, 0, 2, 3]));
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let samples = 10_000;
+        let mut first_triangle_samples = 0;
+        for _ in 0..samples {
+            let (point, normal) = mesh.sample_surface(&mut rng);
+            if point.z == 0.0 {
+                assert_eq!(Vec3::Z, normal);
+                assert!(point.x >= 0.0 && point.y >= 0.0 && point.x / 2.0 + point.y / 3.0 <= 1.0);
+                first_triangle_samples += 1;
+            } else {
+                assert_eq!(0.0, point.x);
+                assert_eq!(Vec3::X, normal);
+            }
+        }
+
+        let ratio = first_triangle_samples as f32 / samples as f32;
+        assert!((ratio - 2.0 / 3.0).abs() < 0.02, "ratio was {ratio}");
+    }
+
+    #[test]
+    fn sample_surface_interpolates_normals() {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            vec![[0., 0., 1.], [1., 0., 0.], [0., 1., 0.]],
+        );
+        mesh.insert_indices(Indices::U16(vec![0, 1, 2]));
+
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        for _ in 0..100 {
+            let (point, normal) = mesh.sample_surface(&mut rng);
+            assert!(normal.is_normalized());
+            // The normal is a blend of the vertex normals weighted by the barycentric coordinates.
+            let expected = Vec3::new(point.x, point.y, 1.0 - point.x - point.y).normalize();
+            assert!(normal.abs_diff_eq(expected, 1e-5));
+        }
+    }
 }
@@ -1020,6 +1020,7 @@ pub struct Capsule2dMeshBuilder {
     pub capsule: Capsule2d,
     /// The number of vertices used for one hemicircle.
     /// The total number of vertices for the capsule mesh will be two times the resolution.
+    /// Must be at least `2`.
     ///
     /// The default is `16`.
     pub resolution: u32,
@@ -1058,6 +1059,10 @@ impl MeshBuilder for Capsule2dMeshBuilder {
     fn build(&self) -> Mesh {
         // The resolution is the number of vertices for one semicircle
         let resolution = self.resolution;
+        debug_assert!(
+            resolution > 1,
+            "capsule has less than 2 vertices per semicircle"
+        );
         let vertex_count = 2 * resolution;
 
         // Six extra indices for the two triangles between the semicircles
@@ -1175,7 +1180,11 @@ impl From<Capsule2d> for Mesh {
 
 #[cfg(test)]
 mod tests {
-    use bevy_math::{prelude::Annulus, primitives::RegularPolygon, FloatOrd};
+    use bevy_math::{
+        prelude::Annulus,
+        primitives::{Capsule2d, RegularPolygon},
+        FloatOrd, Vec2,
+    };
     use bevy_platform::collections::HashSet;
 
     use crate::{Mesh, MeshBuilder, Meshable, VertexAttributeValues};
@@ -1254,4 +1263,49 @@ mod tests {
 
         assert_eq!(&[[0.0, 0.0, 1.0]; 4], &normals[..]);
     }
+
+    #[test]
+    fn test_capsule2d() {
+        for half_length in [1.0, 1e-4, 0.0] {
+            for resolution in [2, 3, 16] {
+                let mesh = Capsule2d::new(0.5, 2.0 * half_length)
+                    .mesh()
+                    .resolution(resolution)
+                    .build();
+
+                let positions: Vec<Vec2> = mesh
+                    .attribute(Mesh::ATTRIBUTE_POSITION)
+                    .unwrap()
+                    .as_float3()
+                    .unwrap()
+                    .iter()
+                    .map(|&[x, y, _]| Vec2::new(x, y))
+                    .collect();
+                assert_eq!(positions.len(), 2 * resolution as usize);
+
+                // The vertices go counterclockwise around a convex outline.
+                for i in 0..positions.len() {
+                    let [a, b, c] = [i, i + 1, i + 2].map(|j| positions[j % positions.len()]);
+                    assert!((b - a).perp_dot(c - b) > 0.0);
+                }
+
+                // No triangle is flipped or degenerate, even when the capsule is a circle.
+                let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+                assert_eq!(indices.len(), 3 * (positions.len() - 2));
+                for triangle in indices.chunks_exact(3) {
+                    let [a, b, c] = [0, 1, 2].map(|j| positions[triangle[j]]);
+                    assert!((b - a).perp_dot(c - a) > 0.0);
+                }
+
+                let Some(VertexAttributeValues::Float32x2(uvs)) =
+                    mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                else {
+                    panic!("Expected uvs f32x2");
+                };
+                for uv in uvs {
+                    assert!(uv.iter().all(|coord| (0.0..=1.0).contains(coord)));
+                }
+            }
+        }
+    }
 }
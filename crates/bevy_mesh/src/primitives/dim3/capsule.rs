@@ -1,7 +1,17 @@
-use crate::{Indices, Mesh, MeshBuilder, Meshable, PrimitiveTopology};
+use crate::{
+    Facing, FacingMeshBuilder, Indices, Mesh, MeshBuilder, Meshable, PrimitiveTopology,
+    TryFromMesh, VertexAttributeValues,
+};
+use alloc::sync::Arc;
 use bevy_asset::RenderAssetUsages;
-use bevy_math::{ops, primitives::Capsule3d, Vec2, Vec3};
+use bevy_math::{ops, primitives::Capsule3d, Vec2, Vec3, Vec4};
+use bevy_platform::collections::HashMap;
 use bevy_reflect::prelude::*;
+use core::{
+    f32::consts::{FRAC_PI_2, TAU},
+    fmt,
+    ops::Range,
+};
 
 /// Manner in which UV coordinates are distributed vertically.
 #[derive(Clone, Copy, Debug, Default, Reflect)]
@@ -17,8 +27,84 @@ pub enum CapsuleUvProfile {
     Fixed,
 }
 
+/// The shape of the ends of a capsule mesh.
+#[derive(Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Default, Debug, Clone)]
+pub enum CapStyle {
+    /// The ends are hemispheres, producing a true capsule.
+    #[default]
+    Rounded,
+    /// The ends are flat discs at the ends of the cylindrical part,
+    /// producing a plain cylinder with the capsule's radius and length.
+    Flat,
+}
+
+/// The ranges of the index buffer of a capsule mesh that belong to each of its sections,
+/// as returned by [`Capsule3dMeshBuilder::build_with_sections`].
+///
+/// Together, the ranges cover the whole index buffer in order, without gaps or overlaps.
+/// They can be used to draw each section with a different material. Sections that were
+/// clipped away by [`Capsule3dMeshBuilder::height_fraction`] have empty ranges.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CapsuleSections {
+    /// The indices of the northern hemisphere, or the top disc for [`CapStyle::Flat`].
+    pub north_cap: Range<u32>,
+    /// The indices of the cylindrical part between the caps.
+    pub cylinder: Range<u32>,
+    /// The indices of the southern hemisphere, or the bottom disc for [`CapStyle::Flat`].
+    pub south_cap: Range<u32>,
+    /// The indices of the disc closing the bottom of a clipped capsule,
+    /// which is empty unless [`Capsule3dMeshBuilder::base_cap`] is set.
+    pub base_cap: Range<u32>,
+}
+
+/// A section of a capsule mesh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub enum CapsuleSection {
+    /// The northern hemisphere, or the top disc for [`CapStyle::Flat`].
+    NorthCap,
+    /// The cylindrical part between the caps, including its edges.
+    Cylinder,
+    /// The southern hemisphere, or the bottom disc for [`CapStyle::Flat`].
+    SouthCap,
+}
+
+/// A vertex of a capsule mesh, passed to a [`CapsuleUvMapper`] to compute its UV coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CapsulePoint {
+    /// The section of the capsule that the vertex belongs to.
+    ///
+    /// The vertices on the edges of the cylinder are shared with the hemispheres,
+    /// and belong to [`CapsuleSection::Cylinder`].
+    pub section: CapsuleSection,
+    /// The angle of the vertex around the Y axis in radians, counterclockwise from the +X axis
+    /// when viewed from above, in the `[0, 2π]` range.
+    ///
+    /// The vertices on the seam at `0` are duplicated with an angle of `2π`,
+    /// so that the texture can wrap around.
+    pub longitude: f32,
+    /// The angle of the vertex above the equator of its cap in radians, from `π/2` at the
+    /// north pole to `-π/2` at the south pole. It is `0` everywhere on the cylinder.
+    ///
+    /// For [`CapStyle::Flat`], the latitude is `±π/2` at the center of the caps and `0` on
+    /// their rims.
+    pub latitude: f32,
+    /// The position of the vertex.
+    pub position: Vec3,
+    /// The UV coordinates that the [`CapsuleUvProfile`] of the builder gives the vertex.
+    pub uv: Vec2,
+}
+
+/// A function computing the UV coordinates of a vertex of a capsule mesh,
+/// see [`Capsule3dMeshBuilder::uv_mapper`].
+pub type CapsuleUvMapper = Arc<dyn Fn(CapsulePoint) -> Vec2 + Send + Sync + 'static>;
+
 /// A builder used for creating a [`Mesh`] with a [`Capsule3d`] shape.
-#[derive(Clone, Copy, Debug, Reflect)]
+///
+/// The builder is [`Clone`] but not [`Copy`], because it can hold a
+/// [`uv_mapper`](Self::uv_mapper) function.
+#[derive(Clone, Reflect)]
 #[reflect(Default, Debug, Clone)]
 pub struct Capsule3dMeshBuilder {
     /// The [`Capsule3d`] shape.
@@ -35,6 +121,54 @@ pub struct Capsule3dMeshBuilder {
     /// The manner in which UV coordinates are distributed vertically.
     /// The default is [`CapsuleUvProfile::Aspect`].
     pub uv_profile: CapsuleUvProfile,
+    /// The shape of the ends of the capsule.
+    /// The default is [`CapStyle::Rounded`].
+    pub caps: CapStyle,
+    /// If set to `true`, [`Mesh::ATTRIBUTE_TANGENT`] is computed analytically from the surface
+    /// of the capsule and inserted into the mesh.
+    /// The default is `false`.
+    pub generate_tangents: bool,
+    /// The direction that the faces of the capsule point in.
+    /// The default is [`Facing::Outward`].
+    pub facing: Facing,
+    /// The fraction of the capsule's height that is kept, measured down from its top.
+    /// Everything below is clipped away, leaving an open bottom.
+    ///
+    /// For example, `0.5` keeps the northern hemisphere and the top half of the cylinder,
+    /// which is useful for domes and buttons. The default is `1.0`, which keeps the
+    /// whole capsule.
+    pub height_fraction: f32,
+    /// If set to `true` and the capsule is clipped by [`height_fraction`](Self::height_fraction),
+    /// the open bottom is closed with a flat disc.
+    /// The default is `false`.
+    pub base_cap: bool,
+    /// A function computing the UV coordinates of every vertex, replacing the ones given by
+    /// the [`uv_profile`](Self::uv_profile). This can be used for texture atlases, or to map
+    /// each [section](CapsuleSection) to a different region of the texture.
+    ///
+    /// The mapper is applied before clipping by [`height_fraction`](Self::height_fraction),
+    /// so the UV coordinates of the rim are interpolated from the mapped ones.
+    /// The default is `None`.
+    #[reflect(ignore, clone)]
+    pub uv_mapper: Option<CapsuleUvMapper>,
+}
+
+impl fmt::Debug for Capsule3dMeshBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Capsule3dMeshBuilder")
+            .field("capsule", &self.capsule)
+            .field("rings", &self.rings)
+            .field("longitudes", &self.longitudes)
+            .field("latitudes", &self.latitudes)
+            .field("uv_profile", &self.uv_profile)
+            .field("caps", &self.caps)
+            .field("generate_tangents", &self.generate_tangents)
+            .field("facing", &self.facing)
+            .field("height_fraction", &self.height_fraction)
+            .field("base_cap", &self.base_cap)
+            .field("uv_mapper", &self.uv_mapper.as_ref().map(|_| ".."))
+            .finish()
+    }
 }
 
 impl Default for Capsule3dMeshBuilder {
@@ -45,6 +179,12 @@ impl Default for Capsule3dMeshBuilder {
             longitudes: 32,
             latitudes: 16,
             uv_profile: CapsuleUvProfile::default(),
+            caps: CapStyle::default(),
+            generate_tangents: false,
+            facing: Facing::default(),
+            height_fraction: 1.0,
+            base_cap: false,
+            uv_mapper: None,
         }
     }
 }
@@ -91,10 +231,502 @@ impl Capsule3dMeshBuilder {
         self.uv_profile = uv_profile;
         self
     }
+
+    /// Sets the shape of the ends of the capsule.
+    #[inline]
+    pub const fn caps(mut self, caps: CapStyle) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    /// Sets whether [`Mesh::ATTRIBUTE_TANGENT`] is computed analytically from the surface of
+    /// the capsule and inserted into the mesh.
+    ///
+    /// This avoids the seams and failures that [`Mesh::generate_tangents`] can produce on the
+    /// degenerate triangles at the poles.
+    #[inline]
+    pub const fn with_generated_tangents(mut self, generate_tangents: bool) -> Self {
+        self.generate_tangents = generate_tangents;
+        self
+    }
+
+    /// Sets the fraction of the capsule's height that is kept, measured down from its top.
+    ///
+    /// The fraction is clamped to the `[0, 1]` range.
+    #[inline]
+    pub const fn height_fraction(mut self, height_fraction: f32) -> Self {
+        self.height_fraction = height_fraction;
+        self
+    }
+
+    /// Sets whether the open bottom of a capsule clipped by
+    /// [`height_fraction`](Self::height_fraction) is closed with a flat disc.
+    #[inline]
+    pub const fn with_base_cap(mut self, base_cap: bool) -> Self {
+        self.base_cap = base_cap;
+        self
+    }
+
+    /// Sets a function computing the UV coordinates of every vertex, replacing the ones given
+    /// by the [`uv_profile`](Self::uv_profile).
+    ///
+    /// ```
+    /// # use bevy_mesh::{Meshable, MeshBuilder, CapsuleSection};
+    /// # use bevy_math::{primitives::Capsule3d, Vec2};
+    /// // Map the caps to the left half of the texture, and the cylinder to the right half.
+    /// let mesh = Capsule3d::default()
+    ///     .mesh()
+    ///     .uv_mapper(|point| {
+    ///         let offset = match point.section {
+    ///             CapsuleSection::Cylinder => 0.5,
+    ///             _ => 0.0,
+    ///         };
+    ///         Vec2::new(offset + point.uv.x / 2.0, point.uv.y)
+    ///     })
+    ///     .build();
+    /// ```
+    #[inline]
+    pub fn uv_mapper(
+        mut self,
+        uv_mapper: impl Fn(CapsulePoint) -> Vec2 + Send + Sync + 'static,
+    ) -> Self {
+        self.uv_mapper = Some(Arc::new(uv_mapper));
+        self
+    }
+
+    /// Builds a chain of levels of detail of the capsule, one for each `(longitudes, latitudes)`
+    /// pair in `levels`, with every other setting of the builder kept.
+    ///
+    /// The meshes are returned in the same order as `levels`. They are intended to be
+    /// spawned as separate entities with a `VisibilityRange` each, so that the renderer
+    /// switches between them based on the distance to the camera.
+    pub fn build_lods(&self, levels: &[(u32, u32)]) -> Vec<Mesh> {
+        levels
+            .iter()
+            .map(|&(longitudes, latitudes)| {
+                self.clone()
+                    .longitudes(longitudes)
+                    .latitudes(latitudes)
+                    .build()
+            })
+            .collect()
+    }
+
+    /// Builds a cylinder with flat disc caps at `±half_length`, for [`CapStyle::Flat`].
+    ///
+    /// The vertices are laid out like those of the rounded capsule with the hemisphere
+    /// latitudes removed, so the caps take up the same regions of the texture as the
+    /// hemispheres would for the chosen [`CapsuleUvProfile`].
+    fn build_flat(&self) -> (Mesh, CapsuleSections) {
+        let Capsule3dMeshBuilder {
+            capsule,
+            rings,
+            longitudes,
+            latitudes,
+            uv_profile,
+            generate_tangents,
+            ..
+        } = *self;
+        let Capsule3d {
+            radius,
+            half_length,
+        } = capsule;
+
+        let half_lats = latitudes / 2;
+        let ringsp1 = rings + 1;
+        let lonsp1 = longitudes + 1;
+
+        // Vertex index offsets.
+        let vert_offset_north_rim = longitudes;
+        let vert_offset_cylinder = vert_offset_north_rim + lonsp1;
+        let vert_offset_south_rim = vert_offset_cylinder + lonsp1 * (ringsp1 + 1);
+        let vert_offset_south_cap = vert_offset_south_rim + lonsp1;
+
+        let vert_len = (vert_offset_south_cap + longitudes) as usize;
+
+        let mut vs: Vec<[f32; 3]> = Vec::with_capacity(vert_len);
+        let mut vts: Vec<[f32; 2]> = Vec::with_capacity(vert_len);
+        let mut vns: Vec<[f32; 3]> = Vec::with_capacity(vert_len);
+        // The tangent of every vertex points along its line of latitude, in the direction of
+        // increasing `u`, which is also the direction of increasing `u` on the flat caps.
+        let mut vtans: Vec<[f32; 4]> = Vec::with_capacity(vert_len);
+
+        let to_theta = 2.0 * core::f32::consts::PI / longitudes as f32;
+        let to_tex_horizontal = 1.0 / longitudes as f32;
+
+        let vt_aspect_ratio = match uv_profile {
+            CapsuleUvProfile::Aspect => radius / (2.0 * half_length + radius + radius),
+            CapsuleUvProfile::Uniform => half_lats as f32 / (ringsp1 + latitudes) as f32,
+            CapsuleUvProfile::Fixed => 1.0 / 3.0,
+        };
+        let vt_aspect_north = 1.0 - vt_aspect_ratio;
+        let vt_aspect_south = vt_aspect_ratio;
+
+        let theta_cartesian: Vec<Vec2> = (0..lonsp1)
+            .map(|j| Vec2::from_angle((j % longitudes) as f32 * to_theta))
+            .collect();
+
+        // North cap center, one vertex per longitude so each triangle gets its own texture
+        // coordinate, like the poles of the rounded capsule.
+        for j in 0..longitudes {
+            let s_texture_polar = 1.0 - ((j as f32 + 0.5) * to_tex_horizontal);
+            let tc = Vec2::from_angle((j as f32 + 0.5) * to_theta);
+            vs.push([0.0, half_length, 0.0]);
+            vts.push([s_texture_polar, 1.0]);
+            vns.push([0.0, 1.0, 0.0]);
+            vtans.push([tc.y, 0.0, tc.x, 1.0]);
+        }
+
+        // North cap rim.
+        for (j, tc) in theta_cartesian.iter().enumerate() {
+            let s_texture = 1.0 - j as f32 * to_tex_horizontal;
+            vs.push([radius * tc.x, half_length, -radius * tc.y]);
+            vts.push([s_texture, vt_aspect_north]);
+            vns.push([0.0, 1.0, 0.0]);
+            vtans.push([tc.y, 0.0, tc.x, 1.0]);
+        }
+
+        // Cylinder, from the north edge to the south edge.
+        let to_fac = 1.0 / ringsp1 as f32;
+        for h in 0..=ringsp1 {
+            let fac = h as f32 * to_fac;
+            let t_texture = (1.0 - fac) * vt_aspect_north + fac * vt_aspect_south;
+            let y = half_length - 2.0 * half_length * fac;
+
+            for (j, tc) in theta_cartesian.iter().enumerate() {
+                let s_texture = 1.0 - j as f32 * to_tex_horizontal;
+                vs.push([radius * tc.x, y, -radius * tc.y]);
+                vts.push([s_texture, t_texture]);
+                vns.push([tc.x, 0.0, -tc.y]);
+                vtans.push([tc.y, 0.0, tc.x, 1.0]);
+            }
+        }
+
+        // South cap rim.
+        for (j, tc) in theta_cartesian.iter().enumerate() {
+            let s_texture = 1.0 - j as f32 * to_tex_horizontal;
+            vs.push([radius * tc.x, -half_length, -radius * tc.y]);
+            vts.push([s_texture, vt_aspect_south]);
+            vns.push([0.0, -1.0, 0.0]);
+            vtans.push([tc.y, 0.0, tc.x, 1.0]);
+        }
+
+        // South cap center.
+        for j in 0..longitudes {
+            let s_texture_polar = 1.0 - ((j as f32 + 0.5) * to_tex_horizontal);
+            let tc = Vec2::from_angle((j as f32 + 0.5) * to_theta);
+            vs.push([0.0, -half_length, 0.0]);
+            vts.push([s_texture_polar, 0.0]);
+            vns.push([0.0, -1.0, 0.0]);
+            vtans.push([tc.y, 0.0, tc.x, 1.0]);
+        }
+
+        // Triangle indices.
+        let mut tris: Vec<u32> =
+            Vec::with_capacity((longitudes * 6 + ringsp1 * longitudes * 6) as usize);
+
+        // North cap.
+        for i in 0..longitudes {
+            tris.extend_from_slice(&[i, vert_offset_north_rim + i, vert_offset_north_rim + i + 1]);
+        }
+        let tri_offset_cylinder = tris.len() as u32;
+
+        // Cylinder.
+        for i in 0..ringsp1 {
+            let vert_curr_lat = vert_offset_cylinder + i * lonsp1;
+            let vert_next_lat = vert_curr_lat + lonsp1;
+
+            for j in 0..longitudes {
+                let cy00 = vert_curr_lat + j;
+                let cy01 = vert_next_lat + j;
+                let cy11 = vert_next_lat + j + 1;
+                let cy10 = vert_curr_lat + j + 1;
+
+                tris.extend_from_slice(&[cy00, cy11, cy10, cy00, cy01, cy11]);
+            }
+        }
+        let tri_offset_south_cap = tris.len() as u32;
+
+        // South cap.
+        for i in 0..longitudes {
+            tris.extend_from_slice(&[
+                vert_offset_south_cap + i,
+                vert_offset_south_rim + i + 1,
+                vert_offset_south_rim + i,
+            ]);
+        }
+        let fs_len = tris.len() as u32;
+
+        assert_eq!(vs.len(), vert_len);
+
+        if let Some(uv_mapper) = &self.uv_mapper {
+            for (i, vt) in (0..).zip(vts.iter_mut()) {
+                let (section, latitude, longitude) = if i < vert_offset_north_rim {
+                    (
+                        CapsuleSection::NorthCap,
+                        FRAC_PI_2,
+                        (i as f32 + 0.5) * to_theta,
+                    )
+                } else if i < vert_offset_cylinder {
+                    let j = i - vert_offset_north_rim;
+                    (CapsuleSection::NorthCap, 0.0, j as f32 * to_theta)
+                } else if i < vert_offset_south_rim {
+                    let j = (i - vert_offset_cylinder) % lonsp1;
+                    (CapsuleSection::Cylinder, 0.0, j as f32 * to_theta)
+                } else if i < vert_offset_south_cap {
+                    let j = i - vert_offset_south_rim;
+                    (CapsuleSection::SouthCap, 0.0, j as f32 * to_theta)
+                } else {
+                    let j = i - vert_offset_south_cap;
+                    (
+                        CapsuleSection::SouthCap,
+                        -FRAC_PI_2,
+                        (j as f32 + 0.5) * to_theta,
+                    )
+                };
+                *vt = uv_mapper(CapsulePoint {
+                    section,
+                    latitude,
+                    longitude: longitude.min(TAU),
+                    position: Vec3::from(vs[i as usize]),
+                    uv: Vec2::from(*vt),
+                })
+                .into();
+            }
+        }
+
+        let sections = CapsuleSections {
+            north_cap: 0..tri_offset_cylinder,
+            cylinder: tri_offset_cylinder..tri_offset_south_cap,
+            south_cap: tri_offset_south_cap..fs_len,
+            base_cap: fs_len..fs_len,
+        };
+
+        let mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vs)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vns)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vts)
+        .with_inserted_indices(Indices::U32(tris));
+
+        if generate_tangents {
+            (
+                mesh.with_inserted_attribute(Mesh::ATTRIBUTE_TANGENT, vtans),
+                sections,
+            )
+        } else {
+            (mesh, sections)
+        }
+    }
+
+    /// Clips away the part of the capsule `mesh` below [`height_fraction`](Self::height_fraction).
+    ///
+    /// Triangles crossing the clipping plane are cut along it, and the attributes of the new
+    /// vertices are interpolated along the cut edges, so the texture continues seamlessly up to
+    /// the rim. Triangles that would be degenerate are never emitted, so a fraction of `0.0`
+    /// produces an empty mesh.
+    ///
+    /// The triangles keep their order, so the `sections` of the mesh are remapped to the
+    /// clipped index buffer.
+    fn clip(&self, mesh: Mesh, sections: CapsuleSections) -> (Mesh, CapsuleSections) {
+        let height_fraction = self.height_fraction.clamp(0.0, 1.0);
+        if height_fraction >= 1.0 {
+            return (mesh, sections);
+        }
+
+        let summit = match self.caps {
+            CapStyle::Rounded => self.capsule.half_length + self.capsule.radius,
+            CapStyle::Flat => self.capsule.half_length,
+        };
+        let clip_y = summit - 2.0 * summit * height_fraction;
+        // Vertices this close to the plane are considered to lie on it,
+        // which prevents slivers from being generated next to them.
+        let epsilon = summit * 1e-5;
+
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            unreachable!("Capsule meshes always have positions");
+        };
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            unreachable!("Capsule meshes always have normals");
+        };
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            unreachable!("Capsule meshes always have UVs");
+        };
+        let tangents = match mesh.attribute(Mesh::ATTRIBUTE_TANGENT) {
+            Some(VertexAttributeValues::Float32x4(tangents)) => Some(tangents),
+            _ => None,
+        };
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            unreachable!("Capsule meshes always have u32 indices");
+        };
+
+        let distance = |i: u32| {
+            let d = positions[i as usize][1] - clip_y;
+            if d.abs() <= epsilon {
+                0.0
+            } else {
+                d
+            }
+        };
+
+        let mut vs: Vec<[f32; 3]> = Vec::new();
+        let mut vns: Vec<[f32; 3]> = Vec::new();
+        let mut vts: Vec<[f32; 2]> = Vec::new();
+        let mut vtans: Vec<[f32; 4]> = Vec::new();
+        let mut on_plane: Vec<bool> = Vec::new();
+        let mut tris: Vec<u32> = Vec::new();
+        // The edges of the rim, in the winding order of the triangles they belong to.
+        let mut rim: Vec<[u32; 2]> = Vec::new();
+
+        // Each vertex of the clipped mesh is either a vertex of the original mesh, keyed by
+        // `(i, i)`, or a cut along the edge between two vertices, keyed by `(lo, hi)` so that
+        // both triangles sharing the edge get the exact same vertex.
+        let mut clipped: HashMap<(u32, u32), u32> = HashMap::default();
+        let key_on_plane = |(lo, hi): (u32, u32)| lo != hi || distance(lo) == 0.0;
+
+        // The offset into the clipped index buffer at which each original triangle starts.
+        let mut tri_offsets: Vec<u32> = Vec::with_capacity(indices.len() / 3 + 1);
+
+        for triangle in indices.chunks_exact(3) {
+            tri_offsets.push(tris.len() as u32);
+            let mut polygon: Vec<(u32, u32)> = Vec::with_capacity(4);
+
+            for k in 0..3 {
+                let (a, b) = (triangle[k], triangle[(k + 1) % 3]);
+                let (da, db) = (distance(a), distance(b));
+
+                if da >= 0.0 {
+                    polygon.push((a, a));
+                }
+                if (da > 0.0 && db < 0.0) || (da < 0.0 && db > 0.0) {
+                    polygon.push((a.min(b), a.max(b)));
+                }
+            }
+
+            // Also skip triangles lying in the plane, like the top of a flat cap clipped at
+            // a fraction of `0.0`, as they would only be left over with no height.
+            if polygon.len() < 3 || polygon.iter().all(|&key| key_on_plane(key)) {
+                continue;
+            }
+
+            let polygon: Vec<u32> = polygon
+                .into_iter()
+                .map(|(lo, hi)| {
+                    *clipped.entry((lo, hi)).or_insert_with(|| {
+                        let (dlo, dhi) = (distance(lo), distance(hi));
+                        let t = if lo == hi { 0.0 } else { dlo / (dlo - dhi) };
+                        let (lo, hi) = (lo as usize, hi as usize);
+
+                        let mut position =
+                            Vec3::from(positions[lo]).lerp(Vec3::from(positions[hi]), t);
+                        if dlo == 0.0 || lo != hi {
+                            position.y = clip_y;
+                        }
+                        vs.push(position.into());
+                        let normal = Vec3::from(normals[lo]).lerp(Vec3::from(normals[hi]), t);
+                        vns.push(normal.normalize_or_zero().into());
+                        vts.push(Vec2::from(uvs[lo]).lerp(Vec2::from(uvs[hi]), t).into());
+                        if let Some(tangents) = tangents {
+                            let tangent = Vec4::from(tangents[lo])
+                                .truncate()
+                                .lerp(Vec4::from(tangents[hi]).truncate(), t);
+                            vtans.push(tangent.normalize_or_zero().extend(tangents[lo][3]).into());
+                        }
+                        on_plane.push(dlo == 0.0 || lo != hi);
+                        vs.len() as u32 - 1
+                    })
+                })
+                .collect();
+
+            for k in 1..polygon.len() - 1 {
+                tris.extend_from_slice(&[polygon[0], polygon[k], polygon[k + 1]]);
+            }
+            for k in 0..polygon.len() {
+                let edge = [polygon[k], polygon[(k + 1) % polygon.len()]];
+                if on_plane[edge[0] as usize] && on_plane[edge[1] as usize] {
+                    rim.push(edge);
+                }
+            }
+        }
+
+        tri_offsets.push(tris.len() as u32);
+        let remap = |range: Range<u32>| {
+            tri_offsets[range.start as usize / 3]..tri_offsets[range.end as usize / 3]
+        };
+        let base_cap_start = tris.len() as u32;
+
+        if self.base_cap {
+            // The cap is textured like the poles, with the center at the bottom edge of the
+            // texture and the rim sharing the texture coordinates of the side's rim.
+            let mut cap_rim: HashMap<u32, u32> = HashMap::default();
+            for [a, b] in rim {
+                let [a, b] = [a, b].map(|i| {
+                    *cap_rim.entry(i).or_insert_with(|| {
+                        let i = i as usize;
+                        vs.push(vs[i]);
+                        vns.push([0.0, -1.0, 0.0]);
+                        vts.push(vts[i]);
+                        if !vtans.is_empty() {
+                            vtans.push(vtans[i]);
+                        }
+                        vs.len() as u32 - 1
+                    })
+                });
+
+                let center = vs.len() as u32;
+                vs.push([0.0, clip_y, 0.0]);
+                vns.push([0.0, -1.0, 0.0]);
+                vts.push([(vts[a as usize][0] + vts[b as usize][0]) / 2.0, 0.0]);
+                if !vtans.is_empty() {
+                    let tangent = (Vec4::from(vtans[a as usize]).truncate()
+                        + Vec4::from(vtans[b as usize]).truncate())
+                    .normalize_or_zero();
+                    vtans.push(tangent.extend(vtans[a as usize][3]).into());
+                }
+
+                // Traversed in the opposite direction, the rim edge winds the cap to face down.
+                tris.extend_from_slice(&[center, b, a]);
+            }
+        }
+
+        let sections = CapsuleSections {
+            north_cap: remap(sections.north_cap),
+            cylinder: remap(sections.cylinder),
+            south_cap: remap(sections.south_cap),
+            base_cap: base_cap_start..tris.len() as u32,
+        };
+
+        let mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vs)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vns)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vts)
+        .with_inserted_indices(Indices::U32(tris));
+
+        if tangents.is_some() {
+            (
+                mesh.with_inserted_attribute(Mesh::ATTRIBUTE_TANGENT, vtans),
+                sections,
+            )
+        } else {
+            (mesh, sections)
+        }
+    }
 }
 
-impl MeshBuilder for Capsule3dMeshBuilder {
-    fn build(&self) -> Mesh {
+impl Capsule3dMeshBuilder {
+    /// Builds the capsule [`Mesh`] like [`MeshBuilder::build`], along with the ranges of its
+    /// index buffer that belong to each of its [sections](CapsuleSections).
+    pub fn build_with_sections(&self) -> (Mesh, CapsuleSections) {
         // code adapted from https://behreajj.medium.com/making-a-capsule-mesh-via-script-in-five-3d-environments-c2214abf02db
         let Capsule3dMeshBuilder {
             capsule,
@@ -102,7 +734,18 @@ impl MeshBuilder for Capsule3dMeshBuilder {
             longitudes,
             latitudes,
             uv_profile,
+            caps,
+            generate_tangents,
+            facing,
+            ..
         } = *self;
+
+        if let CapStyle::Flat = caps {
+            let (mesh, sections) = self.build_flat();
+            let (mesh, sections) = self.clip(mesh, sections);
+            return (facing.apply(mesh), sections);
+        }
+
         let Capsule3d {
             radius,
             half_length,
@@ -135,6 +778,9 @@ impl MeshBuilder for Capsule3dMeshBuilder {
         let mut vs: Vec<Vec3> = vec![Vec3::ZERO; vert_len];
         let mut vts: Vec<Vec2> = vec![Vec2::ZERO; vert_len];
         let mut vns: Vec<Vec3> = vec![Vec3::ZERO; vert_len];
+        // The tangent of every vertex points along its line of latitude,
+        // in the direction of increasing `u`.
+        let mut vtans: Vec<Vec3> = vec![Vec3::ZERO; vert_len];
 
         let to_theta = 2.0 * core::f32::consts::PI / longitudes as f32;
         let to_phi = core::f32::consts::PI / latitudes as f32;
@@ -161,16 +807,22 @@ impl MeshBuilder for Capsule3dMeshBuilder {
             theta_cartesian[j] = Vec2::from_angle(theta);
             rho_theta_cartesian[j] = radius * theta_cartesian[j];
 
+            // The poles have no line of latitude, so use the one halfway between the
+            // longitudes that their texture coordinates are centered on.
+            let tc_polar = Vec2::from_angle((jf + 0.5) * to_theta);
+
             // North.
             vs[j] = Vec3::new(0.0, summit, 0.0);
             vts[j] = Vec2::new(s_texture_polar, 1.0);
             vns[j] = Vec3::Y;
+            vtans[j] = Vec3::new(tc_polar.y, 0.0, tc_polar.x);
 
             // South.
             let idx = vert_offset_south_cap as usize + j;
             vs[idx] = Vec3::new(0.0, -summit, 0.0);
             vts[idx] = Vec2::new(s_texture_polar, 0.0);
             vns[idx] = Vec3::new(0.0, -1.0, 0.0);
+            vtans[idx] = Vec3::new(tc_polar.y, 0.0, tc_polar.x);
         }
 
         // Equatorial vertices.
@@ -188,12 +840,14 @@ impl MeshBuilder for Capsule3dMeshBuilder {
             vs[idxn] = Vec3::new(rtc.x, half_length, -rtc.y);
             vts[idxn] = Vec2::new(s_texture, vt_aspect_north);
             vns[idxn] = Vec3::new(tc.x, 0.0, -tc.y);
+            vtans[idxn] = Vec3::new(tc.y, 0.0, tc.x);
 
             // South equator.
             let idxs = vert_offset_south_equator as usize + j;
             vs[idxs] = Vec3::new(rtc.x, -half_length, -rtc.y);
             vts[idxs] = Vec2::new(s_texture, vt_aspect_south);
             vns[idxs] = Vec3::new(tc.x, 0.0, -tc.y);
+            vtans[idxs] = Vec3::new(tc.y, 0.0, tc.x);
         }
 
         // Hemisphere vertices.
@@ -241,6 +895,7 @@ impl MeshBuilder for Capsule3dMeshBuilder {
                 );
                 vts[idxn] = Vec2::new(*s_texture, t_tex_north);
                 vns[idxn] = Vec3::new(cos_phi_north * tc.x, -sin_phi_north, -cos_phi_north * tc.y);
+                vtans[idxn] = Vec3::new(tc.y, 0.0, tc.x);
 
                 // South hemisphere.
                 let idxs = vert_curr_lat_south as usize + j;
@@ -251,6 +906,7 @@ impl MeshBuilder for Capsule3dMeshBuilder {
                 );
                 vts[idxs] = Vec2::new(*s_texture, t_tex_south);
                 vns[idxs] = Vec3::new(cos_phi_south * tc.x, -sin_phi_south, -cos_phi_south * tc.y);
+                vtans[idxs] = Vec3::new(tc.y, 0.0, tc.x);
             }
         }
 
@@ -275,12 +931,56 @@ impl MeshBuilder for Capsule3dMeshBuilder {
                     vs[idx_cyl_lat] = Vec3::new(rtc.x, z, -rtc.y);
                     vts[idx_cyl_lat] = Vec2::new(*s_texture, t_texture);
                     vns[idx_cyl_lat] = Vec3::new(tc.x, 0.0, -tc.y);
+                    vtans[idx_cyl_lat] = Vec3::new(tc.y, 0.0, tc.x);
 
                     idx_cyl_lat += 1;
                 }
             }
         }
 
+        if let Some(uv_mapper) = &self.uv_mapper {
+            for (i, vt) in (0..).zip(vts.iter_mut()) {
+                let (section, latitude, longitude) = if i < vert_offset_north_hemi {
+                    (
+                        CapsuleSection::NorthCap,
+                        FRAC_PI_2,
+                        (i as f32 + 0.5) * to_theta,
+                    )
+                } else if i < vert_offset_north_equator {
+                    let (r, j) = (
+                        (i - vert_offset_north_hemi) / lonsp1,
+                        (i - vert_offset_north_hemi) % lonsp1,
+                    );
+                    let latitude = FRAC_PI_2 - (r + 1) as f32 * to_phi;
+                    (CapsuleSection::NorthCap, latitude, j as f32 * to_theta)
+                } else if i < vert_offset_south_hemi {
+                    let j = (i - vert_offset_north_equator) % lonsp1;
+                    (CapsuleSection::Cylinder, 0.0, j as f32 * to_theta)
+                } else if i < vert_offset_south_cap {
+                    let (r, j) = (
+                        (i - vert_offset_south_hemi) / lonsp1,
+                        (i - vert_offset_south_hemi) % lonsp1,
+                    );
+                    let latitude = -((r + 1) as f32 * to_phi);
+                    (CapsuleSection::SouthCap, latitude, j as f32 * to_theta)
+                } else {
+                    let j = i - vert_offset_south_cap;
+                    (
+                        CapsuleSection::SouthCap,
+                        -FRAC_PI_2,
+                        (j as f32 + 0.5) * to_theta,
+                    )
+                };
+                *vt = uv_mapper(CapsulePoint {
+                    section,
+                    latitude,
+                    longitude: longitude.min(TAU),
+                    position: vs[i as usize],
+                    uv: *vt,
+                });
+            }
+        }
+
         // Triangle indices.
 
         // Stride is 3 for polar triangles;
@@ -407,14 +1107,48 @@ impl MeshBuilder for Capsule3dMeshBuilder {
         assert_eq!(vs.len(), vert_len);
         assert_eq!(tris.len(), fs_len as usize);
 
-        Mesh::new(
+        let sections = CapsuleSections {
+            north_cap: 0..tri_offset_cylinder,
+            cylinder: tri_offset_cylinder..tri_offset_south_hemi,
+            south_cap: tri_offset_south_hemi..fs_len,
+            base_cap: fs_len..fs_len,
+        };
+
+        let mesh = Mesh::new(
             PrimitiveTopology::TriangleList,
             RenderAssetUsages::default(),
         )
         .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vs)
         .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vns)
         .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vts)
-        .with_inserted_indices(Indices::U32(tris))
+        .with_inserted_indices(Indices::U32(tris));
+
+        if generate_tangents {
+            // Like `Mesh::generate_tangents`, the bitangent points towards decreasing `v`,
+            // which is `normal × tangent` everywhere on the capsule.
+            let vtans: Vec<[f32; 4]> = vtans.into_iter().map(|t| t.extend(1.0).into()).collect();
+            let (mesh, sections) = self.clip(
+                mesh.with_inserted_attribute(Mesh::ATTRIBUTE_TANGENT, vtans),
+                sections,
+            );
+            (facing.apply(mesh), sections)
+        } else {
+            let (mesh, sections) = self.clip(mesh, sections);
+            (facing.apply(mesh), sections)
+        }
+    }
+}
+
+impl MeshBuilder for Capsule3dMeshBuilder {
+    fn build(&self) -> Mesh {
+        self.build_with_sections().0
+    }
+}
+
+impl FacingMeshBuilder for Capsule3dMeshBuilder {
+    fn facing(mut self, facing: Facing) -> Self {
+        self.facing = facing;
+        self
     }
 }
 
@@ -434,3 +1168,406 @@ impl From<Capsule3d> for Mesh {
         capsule.mesh().build()
     }
 }
+
+impl TryFromMesh for Capsule3d {
+    /// Recovers the radius and half-length of a capsule from a mesh of it.
+    ///
+    /// The mesh must be aligned with the Y axis and centered on the origin, like the meshes built
+    /// from a [`Capsule3d`] with rounded caps, and all of its vertices must lie on the surface of
+    /// the capsule. This is checked with a tolerance of a thousandth of the size of the mesh, so
+    /// meshes of any resolution are accepted. A sphere is recovered as a capsule with a
+    /// half-length of zero.
+    fn try_from_mesh(mesh: &Mesh) -> Option<Self> {
+        let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+        if positions.is_empty() {
+            return None;
+        }
+
+        let (min, max) = positions.iter().fold(
+            (Vec3::INFINITY, Vec3::NEG_INFINITY),
+            |(min, max), &position| (min.min(position.into()), max.max(position.into())),
+        );
+        let size = (max - min).max_element();
+        // This also rejects meshes with non-finite positions.
+        if !(size > 0.0 && size.is_finite()) {
+            return None;
+        }
+        let tolerance = size * 1e-3;
+        if (max + min).abs().max_element() > tolerance {
+            return None;
+        }
+
+        // The vertices around the middle of the capsule are the furthest from its axis.
+        let radius = positions
+            .iter()
+            .map(|&[x, _, z]| Vec2::new(x, z).length())
+            .fold(0.0, f32::max);
+        let half_length = max.y - radius;
+        if radius <= tolerance || half_length < -tolerance {
+            return None;
+        }
+        let half_length = half_length.max(0.0);
+
+        let on_surface = positions.iter().all(|&position| {
+            let position = Vec3::from(position);
+            let closest_on_axis = Vec3::new(0.0, position.y.clamp(-half_length, half_length), 0.0);
+            (position.distance(closest_on_axis) - radius).abs() <= tolerance
+        });
+        on_surface.then_some(Capsule3d {
+            radius,
+            half_length,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CapStyle, CapsuleSection, CapsuleSections};
+    use crate::{
+        Facing, FacingMeshBuilder, Mesh, MeshBuilder, Meshable, TryFromMesh, VertexAttributeValues,
+    };
+    use bevy_math::{
+        ops,
+        primitives::{Capsule3d, Cuboid, Cylinder},
+        Vec2, Vec3, Vec4,
+    };
+
+    fn normals(mesh: &Mesh) -> &[[f32; 3]] {
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("Expected normals f32x3");
+        };
+        normals
+    }
+
+    #[test]
+    fn rounded_capsule_mesh() {
+        let mesh = Capsule3d::new(0.5, 1.0)
+            .mesh()
+            .longitudes(8)
+            .latitudes(4)
+            .build();
+
+        // Two poles of 8 vertices, two equators and one latitude per hemisphere of 9 vertices.
+        assert_eq!(8 * 2 + 9 * 4, mesh.count_vertices());
+        assert!(normals(&mesh)
+            .iter()
+            .all(|normal| Vec3::from(*normal).is_normalized()));
+    }
+
+    #[test]
+    fn flat_capsule_mesh() {
+        let mesh = Capsule3d::new(0.5, 1.0)
+            .mesh()
+            .longitudes(8)
+            .latitudes(4)
+            .rings(2)
+            .caps(CapStyle::Flat)
+            .build();
+
+        // Two cap centers of 8 vertices, two cap rims of 9 vertices,
+        // and 4 rows of 9 vertices along the cylinder.
+        assert_eq!(8 * 2 + 9 * 2 + 9 * 4, mesh.count_vertices());
+        assert!(normals(&mesh)
+            .iter()
+            .all(|normal| Vec3::from(*normal).is_normalized()));
+
+        // No vertices extend past the flat caps.
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("Expected positions f32x3");
+        };
+        assert!(positions.iter().all(|position| position[1].abs() <= 0.5));
+
+        // Every triangle faces the same way as its vertex normals.
+        let normals = normals(&mesh);
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        for (triangle, chunk) in mesh.triangles().unwrap().zip(indices.chunks_exact(3)) {
+            let face_normal = triangle.normal().unwrap();
+            for &i in chunk {
+                assert!(face_normal.dot(Vec3::from(normals[i])) > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn capsule_tangents() {
+        for caps in [CapStyle::Rounded, CapStyle::Flat] {
+            let mesh = Capsule3d::new(0.5, 1.0)
+                .mesh()
+                .rings(2)
+                .caps(caps)
+                .with_generated_tangents(true)
+                .build();
+
+            let Some(VertexAttributeValues::Float32x4(tangents)) =
+                mesh.attribute(Mesh::ATTRIBUTE_TANGENT)
+            else {
+                panic!("Expected tangents f32x4");
+            };
+
+            let normals = normals(&mesh);
+            assert_eq!(normals.len(), tangents.len());
+            for (normal, tangent) in normals.iter().zip(tangents) {
+                let tangent = Vec4::from(*tangent);
+                assert!(tangent.truncate().is_normalized());
+                assert!(tangent.truncate().dot(Vec3::from(*normal)).abs() < 1e-6);
+            }
+            mesh.validate_tangent_space().unwrap();
+        }
+
+        let mesh = Capsule3d::new(0.5, 1.0).mesh().build();
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_TANGENT).is_none());
+    }
+
+    #[test]
+    fn capsule_from_mesh() {
+        for (radius, length) in [(0.5, 1.0), (2.0, 0.5), (0.1, 10.0), (1.0, 0.0)] {
+            for (longitudes, latitudes) in [(8, 4), (32, 16)] {
+                let capsule = Capsule3d::new(radius, length);
+                let mesh = capsule
+                    .mesh()
+                    .longitudes(longitudes)
+                    .latitudes(latitudes)
+                    .build();
+                let recovered = Capsule3d::try_from_mesh(&mesh).unwrap();
+                assert!((recovered.radius - radius).abs() < 1e-4, "{recovered:?}");
+                assert!(
+                    (recovered.half_length - length / 2.0).abs() < 1e-4,
+                    "{recovered:?}"
+                );
+            }
+        }
+
+        // Shapes that aren't capsules around the origin are rejected.
+        let capsule = Capsule3d::new(0.5, 1.0);
+        assert!(Capsule3d::try_from_mesh(&capsule.mesh().caps(CapStyle::Flat).build()).is_none());
+        assert!(Capsule3d::try_from_mesh(
+            &capsule
+                .mesh()
+                .build()
+                .translated_by(Vec3::new(0.0, 1.0, 0.0))
+        )
+        .is_none());
+        assert!(Capsule3d::try_from_mesh(&Cuboid::new(1.0, 2.0, 1.0).mesh().build()).is_none());
+        assert!(Capsule3d::try_from_mesh(&Cylinder::new(0.5, 2.0).mesh().build()).is_none());
+    }
+
+    #[test]
+    fn inward_facing_capsule() {
+        let builder = Capsule3d::new(0.5, 1.0).mesh().longitudes(4).latitudes(4);
+        let outward = builder.build();
+        let inward = builder.facing(Facing::Inward).build();
+
+        // Every triangle has its winding reversed.
+        let outward_indices: Vec<usize> = outward.indices().unwrap().iter().collect();
+        let inward_indices: Vec<usize> = inward.indices().unwrap().iter().collect();
+        assert_eq!(outward_indices.len(), inward_indices.len());
+        for (outward, inward) in outward_indices
+            .chunks_exact(3)
+            .zip(inward_indices.chunks_exact(3))
+        {
+            assert_eq!([outward[0], outward[2], outward[1]], inward);
+        }
+
+        // Every normal is negated.
+        assert_eq!(normals(&outward).len(), normals(&inward).len());
+        for (outward, inward) in normals(&outward).iter().zip(normals(&inward)) {
+            assert_eq!(-Vec3::from(*outward), Vec3::from(*inward));
+        }
+    }
+
+    #[test]
+    fn clipped_capsule_rim() {
+        for base_cap in [false, true] {
+            let mesh = Capsule3d::new(0.5, 1.0)
+                .mesh()
+                .height_fraction(0.5)
+                .with_base_cap(base_cap)
+                .build();
+
+            let Some(VertexAttributeValues::Float32x3(positions)) =
+                mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            else {
+                panic!("Expected positions f32x3");
+            };
+            let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+            else {
+                panic!("Expected uvs f32x2");
+            };
+
+            // Half of the capsule is clipped away, so the rim lies in the XZ plane.
+            assert!(positions.iter().all(|position| position[1] >= 0.0));
+            let rim: Vec<usize> = (0..positions.len())
+                .filter(|&i| positions[i][1] == 0.0)
+                .collect();
+            assert!(!rim.is_empty());
+
+            // The texture continues up to the rim, where it is cut horizontally.
+            let rim_v = uvs[rim[0]][1];
+            assert!(rim_v > 0.0);
+            assert!(rim
+                .iter()
+                .all(|&i| uvs[i][1] == rim_v || (base_cap && uvs[i][1] == 0.0)));
+
+            // The base cap faces down, out of the capsule.
+            let base_triangles = mesh
+                .triangles()
+                .unwrap()
+                .filter(|triangle| triangle.vertices.iter().all(|vertex| vertex.y == 0.0))
+                .map(|triangle| assert!(triangle.normal().unwrap().y < 0.0))
+                .count();
+            assert_eq!(base_cap, base_triangles > 0);
+        }
+    }
+
+    #[test]
+    fn clipped_capsule_has_no_degenerate_triangles() {
+        for height_fraction in [0.0, 0.25, 0.5, 0.75, 1.0] {
+            for caps in [CapStyle::Rounded, CapStyle::Flat] {
+                let mesh = Capsule3d::new(0.5, 1.0)
+                    .mesh()
+                    .caps(caps)
+                    .height_fraction(height_fraction)
+                    .with_base_cap(true)
+                    .build();
+
+                for triangle in mesh.triangles().unwrap() {
+                    assert!(triangle.normal().is_ok());
+                }
+                if height_fraction == 0.0 {
+                    assert_eq!(0, mesh.count_vertices());
+                }
+            }
+        }
+
+        let full = Capsule3d::new(0.5, 1.0).mesh().build();
+        let unclipped = Capsule3d::new(0.5, 1.0)
+            .mesh()
+            .height_fraction(1.0)
+            .with_base_cap(true)
+            .build();
+        assert_eq!(full.count_vertices(), unclipped.count_vertices());
+    }
+
+    #[test]
+    fn capsule_sections_partition_indices() {
+        for caps in [CapStyle::Rounded, CapStyle::Flat] {
+            for height_fraction in [1.0, 0.8, 0.5, 0.2] {
+                let (mesh, sections) = Capsule3d::new(0.5, 1.0)
+                    .mesh()
+                    .rings(2)
+                    .caps(caps)
+                    .height_fraction(height_fraction)
+                    .with_base_cap(true)
+                    .build_with_sections();
+                let CapsuleSections {
+                    north_cap,
+                    cylinder,
+                    south_cap,
+                    base_cap,
+                } = sections;
+
+                // The sections are contiguous and cover the whole index buffer.
+                assert_eq!(0, north_cap.start);
+                assert_eq!(north_cap.end, cylinder.start);
+                assert_eq!(cylinder.end, south_cap.start);
+                assert_eq!(south_cap.end, base_cap.start);
+                assert_eq!(mesh.indices().unwrap().len(), base_cap.end as usize);
+                for range in [&north_cap, &cylinder, &south_cap, &base_cap] {
+                    assert!(range.start <= range.end);
+                    assert_eq!(0, range.start % 3);
+                }
+
+                // Sections below the clipping plane are empty.
+                let rounded = matches!(caps, CapStyle::Rounded);
+                assert!(!north_cap.is_empty());
+                assert_eq!(!rounded || height_fraction > 0.25, !cylinder.is_empty());
+                assert_eq!(
+                    height_fraction == 1.0 || (rounded && height_fraction > 0.75),
+                    !south_cap.is_empty()
+                );
+                assert_eq!(height_fraction < 1.0, !base_cap.is_empty());
+
+                // Every cylinder vertex lies on the side of the capsule.
+                let Some(VertexAttributeValues::Float32x3(positions)) =
+                    mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                else {
+                    panic!("Expected positions f32x3");
+                };
+                let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+                for &i in &indices[cylinder.start as usize..cylinder.end as usize] {
+                    assert!(positions[i][1].abs() <= 0.5 + 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn capsule_uv_mapper() {
+        for caps in [CapStyle::Rounded, CapStyle::Flat] {
+            let uv = Vec2::new(0.25, 0.75);
+            let mesh = Capsule3d::new(0.5, 1.0)
+                .mesh()
+                .rings(2)
+                .caps(caps)
+                .uv_mapper(move |_| uv)
+                .build();
+
+            let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+            else {
+                panic!("Expected uvs f32x2");
+            };
+            assert!(uvs.iter().all(|vt| Vec2::from(*vt) == uv));
+        }
+
+        // Every vertex is reported in the section and at the latitude it lies at,
+        // along with the UV coordinates of the profile.
+        let builder = Capsule3d::new(0.5, 1.0).mesh().rings(2);
+        let mesh = builder
+            .clone()
+            .uv_mapper(|point| {
+                let y = point.position.y;
+                match point.section {
+                    CapsuleSection::Cylinder => assert!(y.abs() <= 0.5 + 1e-6),
+                    _ => {
+                        assert_eq!(point.section == CapsuleSection::NorthCap, y > 0.0);
+                        let sin_latitude = (y - 0.5 * y.signum()) / 0.5;
+                        assert!((ops::sin(point.latitude) - sin_latitude).abs() < 1e-5);
+                    }
+                }
+                point.uv
+            })
+            .build();
+        assert_eq!(
+            builder
+                .build()
+                .attribute(Mesh::ATTRIBUTE_UV_0)
+                .unwrap()
+                .get_bytes(),
+            mesh.attribute(Mesh::ATTRIBUTE_UV_0).unwrap().get_bytes()
+        );
+    }
+
+    #[test]
+    fn capsule_lods() {
+        let levels = [(32, 16), (16, 8), (8, 4)];
+        let lods = Capsule3d::new(0.5, 1.0).mesh().rings(2).build_lods(&levels);
+
+        assert_eq!(levels.len(), lods.len());
+        for (lod, &(longitudes, latitudes)) in lods.iter().zip(&levels) {
+            let expected = Capsule3d::new(0.5, 1.0)
+                .mesh()
+                .rings(2)
+                .longitudes(longitudes)
+                .latitudes(latitudes)
+                .build();
+            assert_eq!(expected.count_vertices(), lod.count_vertices());
+        }
+        for pair in lods.windows(2) {
+            assert!(pair[1].count_vertices() < pair[0].count_vertices());
+        }
+    }
+}
@@ -29,6 +29,14 @@ pub struct ConeMeshBuilder {
     /// The anchor point for the cone mesh, defaults to the midpoint between
     /// the tip of the cone and the center of its base
     pub anchor: ConeAnchor,
+    /// If set to `true`, the base of the cone (a flat disc) is built.
+    ///
+    /// The default is `true`.
+    pub base_cap: bool,
+    /// If set to `true`, the lateral surface of the cone (its slanted side) is built.
+    ///
+    /// The default is `true`.
+    pub lateral: bool,
 }
 
 impl Default for ConeMeshBuilder {
@@ -37,6 +45,8 @@ impl Default for ConeMeshBuilder {
             cone: Cone::default(),
             resolution: 32,
             anchor: ConeAnchor::default(),
+            base_cap: true,
+            lateral: true,
         }
     }
 }
@@ -50,6 +60,8 @@ impl ConeMeshBuilder {
             cone: Cone { radius, height },
             resolution,
             anchor: ConeAnchor::MidPoint,
+            base_cap: true,
+            lateral: true,
         }
     }
 
@@ -66,6 +78,24 @@ impl ConeMeshBuilder {
         self.anchor = anchor;
         self
     }
+
+    /// Sets whether the base of the cone is built.
+    ///
+    /// Without it, the cone is open at the bottom, like a megaphone.
+    #[inline]
+    pub const fn base_cap(mut self, base_cap: bool) -> Self {
+        self.base_cap = base_cap;
+        self
+    }
+
+    /// Sets whether the lateral surface of the cone is built.
+    ///
+    /// Without it, only the base of the cone is left, as a disc facing down.
+    #[inline]
+    pub const fn lateral(mut self, lateral: bool) -> Self {
+        self.lateral = lateral;
+        self
+    }
 }
 
 impl MeshBuilder for ConeMeshBuilder {
@@ -74,83 +104,96 @@ impl MeshBuilder for ConeMeshBuilder {
 
         // `resolution` vertices for the base, `resolution` vertices for the bottom of the lateral surface,
         // and one vertex for the tip.
-        let num_vertices = self.resolution as usize * 2 + 1;
-        let num_indices = self.resolution as usize * 6 - 6;
+        let resolution = self.resolution as usize;
+        let mut num_vertices = 0;
+        let mut num_indices = 0;
+        if self.lateral {
+            num_vertices += resolution + 1;
+            num_indices += resolution * 3;
+        }
+        if self.base_cap {
+            num_vertices += resolution;
+            num_indices += resolution.saturating_sub(2) * 3;
+        }
 
         let mut positions = Vec::with_capacity(num_vertices);
         let mut normals = Vec::with_capacity(num_vertices);
         let mut uvs = Vec::with_capacity(num_vertices);
         let mut indices = Vec::with_capacity(num_indices);
 
-        // Tip
-        positions.push([0.0, half_height, 0.0]);
-
-        // The tip doesn't have a singular normal that works correctly.
-        // We use an invalid normal here so that it becomes NaN in the fragment shader
-        // and doesn't affect the overall shading. This might seem hacky, but it's one of
-        // the only ways to get perfectly smooth cones without creases or other shading artifacts.
-        //
-        // Note that this requires that normals are not normalized in the vertex shader,
-        // as that would make the entire triangle invalid and make the cone appear as black.
-        normals.push([0.0, 0.0, 0.0]);
-
-        // The UVs of the cone are in polar coordinates, so it's like projecting a circle texture from above.
-        // The center of the texture is at the center of the lateral surface, at the tip of the cone.
-        uvs.push([0.5, 0.5]);
-
-        // Now we build the lateral surface, the side of the cone.
-
-        // The vertex normals will be perpendicular to the surface.
-        //
-        // Here we get the slope of a normal and use it for computing
-        // the multiplicative inverse of the length of a vector in the direction
-        // of the normal. This allows us to normalize vertex normals efficiently.
-        let normal_slope = self.cone.radius / self.cone.height;
-        // Equivalent to Vec2::new(1.0, slope).length().recip()
-        let normalization_factor = (1.0 + normal_slope * normal_slope).sqrt().recip();
-
         // How much the angle changes at each step
         let step_theta = core::f32::consts::TAU / self.resolution as f32;
 
-        // Add vertices for the bottom of the lateral surface.
-        for segment in 0..self.resolution {
-            let theta = segment as f32 * step_theta;
-            let (sin, cos) = ops::sin_cos(theta);
-
-            // The vertex normal perpendicular to the side
-            let normal = Vec3::new(cos, normal_slope, sin) * normalization_factor;
+        if self.lateral {
+            // Tip
+            positions.push([0.0, half_height, 0.0]);
+
+            // The tip doesn't have a singular normal that works correctly.
+            // We use an invalid normal here so that it becomes NaN in the fragment shader
+            // and doesn't affect the overall shading. This might seem hacky, but it's one of
+            // the only ways to get perfectly smooth cones without creases or other shading artifacts.
+            //
+            // Note that this requires that normals are not normalized in the vertex shader,
+            // as that would make the entire triangle invalid and make the cone appear as black.
+            normals.push([0.0, 0.0, 0.0]);
+
+            // The UVs of the cone are in polar coordinates, so it's like projecting a circle texture from above.
+            // The center of the texture is at the center of the lateral surface, at the tip of the cone.
+            uvs.push([0.5, 0.5]);
+
+            // Now we build the lateral surface, the side of the cone.
+
+            // The vertex normals will be perpendicular to the surface.
+            //
+            // Here we get the slope of a normal and use it for computing
+            // the multiplicative inverse of the length of a vector in the direction
+            // of the normal. This allows us to normalize vertex normals efficiently.
+            let normal_slope = self.cone.radius / self.cone.height;
+            // Equivalent to Vec2::new(1.0, slope).length().recip()
+            let normalization_factor = (1.0 + normal_slope * normal_slope).sqrt().recip();
+
+            // Add vertices for the bottom of the lateral surface.
+            for segment in 0..self.resolution {
+                let theta = segment as f32 * step_theta;
+                let (sin, cos) = ops::sin_cos(theta);
+
+                // The vertex normal perpendicular to the side
+                let normal = Vec3::new(cos, normal_slope, sin) * normalization_factor;
+
+                positions.push([self.cone.radius * cos, -half_height, self.cone.radius * sin]);
+                normals.push(normal.to_array());
+                uvs.push([0.5 + cos * 0.5, 0.5 + sin * 0.5]);
+            }
 
-            positions.push([self.cone.radius * cos, -half_height, self.cone.radius * sin]);
-            normals.push(normal.to_array());
-            uvs.push([0.5 + cos * 0.5, 0.5 + sin * 0.5]);
-        }
+            // Add indices for the lateral surface. Each triangle is formed by the tip
+            // and two vertices at the base.
+            for j in 1..self.resolution {
+                indices.extend_from_slice(&[0, j + 1, j]);
+            }
 
-        // Add indices for the lateral surface. Each triangle is formed by the tip
-        // and two vertices at the base.
-        for j in 1..self.resolution {
-            indices.extend_from_slice(&[0, j + 1, j]);
+            // Close the surface with a triangle between the tip, first base vertex, and last base vertex.
+            indices.extend_from_slice(&[0, 1, self.resolution]);
         }
 
-        // Close the surface with a triangle between the tip, first base vertex, and last base vertex.
-        indices.extend_from_slice(&[0, 1, self.resolution]);
-
-        // Now we build the actual base of the cone.
+        if self.base_cap {
+            // Now we build the actual base of the cone.
 
-        let index_offset = positions.len() as u32;
+            let index_offset = positions.len() as u32;
 
-        // Add base vertices.
-        for i in 0..self.resolution {
-            let theta = i as f32 * step_theta;
-            let (sin, cos) = ops::sin_cos(theta);
+            // Add base vertices.
+            for i in 0..self.resolution {
+                let theta = i as f32 * step_theta;
+                let (sin, cos) = ops::sin_cos(theta);
 
-            positions.push([cos * self.cone.radius, -half_height, sin * self.cone.radius]);
-            normals.push([0.0, -1.0, 0.0]);
-            uvs.push([0.5 * (cos + 1.0), 1.0 - 0.5 * (sin + 1.0)]);
-        }
+                positions.push([cos * self.cone.radius, -half_height, sin * self.cone.radius]);
+                normals.push([0.0, -1.0, 0.0]);
+                uvs.push([0.5 * (cos + 1.0), 1.0 - 0.5 * (sin + 1.0)]);
+            }
 
-        // Add base indices.
-        for i in 1..(self.resolution - 1) {
-            indices.extend_from_slice(&[index_offset, index_offset + i, index_offset + i + 1]);
+            // Add base indices.
+            for i in 1..(self.resolution - 1) {
+                indices.extend_from_slice(&[index_offset, index_offset + i, index_offset + i + 1]);
+            }
         }
 
         // Offset the vertex positions Y axis to match the anchor
@@ -192,6 +235,7 @@ impl From<Cone> for Mesh {
 mod tests {
     use crate::{Mesh, MeshBuilder, Meshable, VertexAttributeValues};
     use bevy_math::{primitives::Cone, Vec2};
+    use bevy_platform::collections::HashMap;
 
     /// Rounds floats to handle floating point error in tests.
     fn round_floats<const N: usize>(points: &mut [[f32; N]]) {
@@ -268,4 +312,57 @@ mod tests {
             &normals[..]
         );
     }
+
+    /// Returns the number of edges that belong to a single triangle of the mesh, comparing
+    /// vertices by position, so that seams between surfaces with different normals don't count.
+    fn open_edge_count(mesh: &Mesh) -> usize {
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .unwrap();
+        let key = |index: usize| positions[index].map(|coord| (coord * 1e4).round() as i32);
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+
+        let mut edges = HashMap::<_, usize>::default();
+        for triangle in indices.chunks_exact(3) {
+            for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+                let (a, b) = (key(triangle[a]), key(triangle[b]));
+                *edges
+                    .entry(if a < b { (a, b) } else { (b, a) })
+                    .or_default() += 1;
+            }
+        }
+        edges.values().filter(|&&count| count == 1).count()
+    }
+
+    #[test]
+    fn cone_mesh_parts() {
+        let resolution = 8;
+        for (base_cap, lateral, vertices, indices, open_edges) in [
+            (true, true, 17, 42, 0),
+            (false, true, 9, 24, 8),
+            (true, false, 8, 18, 8),
+            (false, false, 0, 0, 0),
+        ] {
+            let mesh = Cone::default()
+                .mesh()
+                .resolution(resolution)
+                .base_cap(base_cap)
+                .lateral(lateral)
+                .build();
+
+            assert_eq!(mesh.count_vertices(), vertices, "{base_cap} {lateral}");
+            let mesh_indices = mesh.indices().unwrap();
+            assert_eq!(mesh_indices.len(), indices, "{base_cap} {lateral}");
+
+            // Every vertex is used by a triangle.
+            let mut used = vec![false; vertices];
+            mesh_indices.iter().for_each(|index| used[index] = true);
+            assert!(used.iter().all(|&used| used), "{base_cap} {lateral}");
+
+            // The full cone is watertight, and the open ones only have a hole at the base.
+            assert_eq!(open_edge_count(&mesh), open_edges, "{base_cap} {lateral}");
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Indices, Mesh, MeshBuilder, Meshable, PrimitiveTopology};
+use crate::{Facing, FacingMeshBuilder, Indices, Mesh, MeshBuilder, Meshable, PrimitiveTopology};
 use bevy_asset::RenderAssetUsages;
 use bevy_math::{ops, primitives::Cylinder};
 use bevy_reflect::prelude::*;
@@ -31,12 +31,26 @@ pub struct CylinderMeshBuilder {
     ///
     /// The default is `1`.
     pub segments: u32,
-    /// If set to `true`, the cylinder caps (flat circle faces) are built,
-    /// otherwise the mesh will be a shallow tube
-    pub caps: bool,
+    /// If set to `true`, the top cap of the cylinder (a flat circle face) is built,
+    /// otherwise the cylinder is open at the top.
+    ///
+    /// The default is `true`.
+    pub top_cap: bool,
+    /// If set to `true`, the bottom cap of the cylinder (a flat circle face) is built,
+    /// otherwise the cylinder is open at the bottom.
+    ///
+    /// The default is `true`.
+    pub bottom_cap: bool,
     /// The anchor point for the cylinder mesh, defaults to the midpoint between
     /// the top and bottom caps
     pub anchor: CylinderAnchor,
+    /// If set to `true`, [`Mesh::ATTRIBUTE_TANGENT`] is computed analytically from the surface
+    /// of the cylinder and inserted into the mesh.
+    /// The default is `false`.
+    pub generate_tangents: bool,
+    /// The direction that the faces of the cylinder point in.
+    /// The default is [`Facing::Outward`].
+    pub facing: Facing,
 }
 
 impl Default for CylinderMeshBuilder {
@@ -45,8 +59,11 @@ impl Default for CylinderMeshBuilder {
             cylinder: Cylinder::default(),
             resolution: 32,
             segments: 1,
-            caps: true,
+            top_cap: true,
+            bottom_cap: true,
             anchor: CylinderAnchor::default(),
+            generate_tangents: false,
+            facing: Facing::default(),
         }
     }
 }
@@ -81,7 +98,22 @@ impl CylinderMeshBuilder {
     /// Ignore the cylinder caps, making the mesh a shallow tube instead
     #[inline]
     pub const fn without_caps(mut self) -> Self {
-        self.caps = false;
+        self.top_cap = false;
+        self.bottom_cap = false;
+        self
+    }
+
+    /// Sets whether the top cap of the cylinder is built.
+    #[inline]
+    pub const fn top_cap(mut self, top_cap: bool) -> Self {
+        self.top_cap = top_cap;
+        self
+    }
+
+    /// Sets whether the bottom cap of the cylinder is built.
+    #[inline]
+    pub const fn bottom_cap(mut self, bottom_cap: bool) -> Self {
+        self.bottom_cap = bottom_cap;
         self
     }
 
@@ -91,6 +123,14 @@ impl CylinderMeshBuilder {
         self.anchor = anchor;
         self
     }
+
+    /// Sets whether [`Mesh::ATTRIBUTE_TANGENT`] is computed analytically from the surface of
+    /// the cylinder and inserted into the mesh.
+    #[inline]
+    pub const fn with_generated_tangents(mut self, generate_tangents: bool) -> Self {
+        self.generate_tangents = generate_tangents;
+        self
+    }
 }
 
 impl MeshBuilder for CylinderMeshBuilder {
@@ -102,13 +142,14 @@ impl MeshBuilder for CylinderMeshBuilder {
         debug_assert!(segments > 0);
 
         let num_rings = segments + 1;
-        let num_vertices = resolution * 2 + num_rings * (resolution + 1);
-        let num_faces = resolution * (num_rings - 2);
-        let num_indices = (2 * num_faces + 2 * (resolution - 1) * 2) * 3;
+        let num_caps = self.top_cap as u32 + self.bottom_cap as u32;
+        let num_vertices = num_caps * resolution + num_rings * (resolution + 1);
+        let num_indices = (segments * resolution * 2 + num_caps * (resolution - 2)) * 3;
 
         let mut positions = Vec::with_capacity(num_vertices as usize);
         let mut normals = Vec::with_capacity(num_vertices as usize);
         let mut uvs = Vec::with_capacity(num_vertices as usize);
+        let mut tangents = Vec::with_capacity(num_vertices as usize);
         let mut indices = Vec::with_capacity(num_indices as usize);
 
         let step_theta = core::f32::consts::TAU / resolution as f32;
@@ -125,6 +166,9 @@ impl MeshBuilder for CylinderMeshBuilder {
 
                 positions.push([self.cylinder.radius * cos, y, self.cylinder.radius * sin]);
                 normals.push([cos, 0., sin]);
+                // `u` increases around the barrel and `v` increases upwards. Like
+                // `Mesh::generate_tangents`, the bitangent points towards decreasing `v`.
+                tangents.push([-sin, 0., cos, 1.]);
                 uvs.push([
                     segment as f32 / resolution as f32,
                     ring as f32 / segments as f32,
@@ -151,34 +195,41 @@ impl MeshBuilder for CylinderMeshBuilder {
         }
 
         // caps
-        if self.caps {
-            let mut build_cap = |top: bool| {
-                let offset = positions.len() as u32;
-                let (y, normal_y, winding) = if top {
-                    (self.cylinder.half_height, 1., (1, 0))
-                } else {
-                    (-self.cylinder.half_height, -1., (0, 1))
-                };
-
-                for i in 0..self.resolution {
-                    let theta = i as f32 * step_theta;
-                    let (sin, cos) = ops::sin_cos(theta);
-
-                    positions.push([cos * self.cylinder.radius, y, sin * self.cylinder.radius]);
-                    normals.push([0.0, normal_y, 0.0]);
-                    uvs.push([0.5 * (cos + 1.0), 1.0 - 0.5 * (sin + 1.0)]);
-                }
-
-                for i in 1..(self.resolution - 1) {
-                    indices.extend_from_slice(&[
-                        offset,
-                        offset + i + winding.0,
-                        offset + i + winding.1,
-                    ]);
-                }
+        //
+        // The caps have their own vertices and are planar mapped, so the UVs of the barrel cover
+        // the whole texture whether or not the caps are built.
+        let mut build_cap = |top: bool| {
+            let offset = positions.len() as u32;
+            // The caps are planar mapped, with `u` increasing along +X and `v` along -Z.
+            let (y, normal_y, handedness, winding) = if top {
+                (self.cylinder.half_height, 1., -1., (1, 0))
+            } else {
+                (-self.cylinder.half_height, -1., 1., (0, 1))
             };
 
+            for i in 0..self.resolution {
+                let theta = i as f32 * step_theta;
+                let (sin, cos) = ops::sin_cos(theta);
+
+                positions.push([cos * self.cylinder.radius, y, sin * self.cylinder.radius]);
+                normals.push([0.0, normal_y, 0.0]);
+                tangents.push([1.0, 0.0, 0.0, handedness]);
+                uvs.push([0.5 * (cos + 1.0), 1.0 - 0.5 * (sin + 1.0)]);
+            }
+
+            for i in 1..(self.resolution - 1) {
+                indices.extend_from_slice(&[
+                    offset,
+                    offset + i + winding.0,
+                    offset + i + winding.1,
+                ]);
+            }
+        };
+
+        if self.top_cap {
             build_cap(true);
+        }
+        if self.bottom_cap {
             build_cap(false);
         }
 
@@ -193,14 +244,29 @@ impl MeshBuilder for CylinderMeshBuilder {
             CylinderAnchor::MidPoint => (),
         };
 
-        Mesh::new(
+        let mesh = Mesh::new(
             PrimitiveTopology::TriangleList,
             RenderAssetUsages::default(),
         )
         .with_inserted_indices(Indices::U32(indices))
         .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
         .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
-        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+
+        let mesh = if self.generate_tangents {
+            mesh.with_inserted_attribute(Mesh::ATTRIBUTE_TANGENT, tangents)
+        } else {
+            mesh
+        };
+
+        self.facing.apply(mesh)
+    }
+}
+
+impl FacingMeshBuilder for CylinderMeshBuilder {
+    fn facing(mut self, facing: Facing) -> Self {
+        self.facing = facing;
+        self
     }
 }
 
@@ -220,3 +286,117 @@ impl From<Cylinder> for Mesh {
         cylinder.mesh().build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Mesh, MeshBuilder, Meshable, VertexAttributeValues};
+    use bevy_math::{primitives::Cylinder, Vec3, Vec4};
+
+    #[test]
+    fn cylinder_tangents() {
+        let mesh = Cylinder::new(0.5, 2.0)
+            .mesh()
+            .resolution(8)
+            .segments(2)
+            .with_generated_tangents(true)
+            .build();
+
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("Expected normals f32x3");
+        };
+        let Some(VertexAttributeValues::Float32x4(tangents)) =
+            mesh.attribute(Mesh::ATTRIBUTE_TANGENT)
+        else {
+            panic!("Expected tangents f32x4");
+        };
+
+        assert_eq!(normals.len(), tangents.len());
+        for (normal, tangent) in normals.iter().zip(tangents) {
+            let tangent = Vec4::from(*tangent);
+            assert!(tangent.truncate().is_normalized());
+            assert!(tangent.truncate().dot(Vec3::from(*normal)).abs() < 1e-6);
+        }
+        mesh.validate_tangent_space().unwrap();
+
+        let mesh = Cylinder::new(0.5, 2.0).mesh().build();
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_TANGENT).is_none());
+    }
+
+    #[test]
+    fn cylinder_caps() {
+        let (resolution, segments) = (8, 2);
+        let barrel_vertices = (segments as usize + 1) * (resolution as usize + 1);
+        let barrel_indices = (segments * resolution) as usize * 6;
+        let cap_vertices = resolution as usize;
+        let cap_indices = (resolution as usize - 2) * 3;
+
+        for (top_cap, bottom_cap) in [(true, true), (true, false), (false, true), (false, false)] {
+            let mesh = Cylinder::new(0.5, 2.0)
+                .mesh()
+                .resolution(resolution)
+                .segments(segments)
+                .top_cap(top_cap)
+                .bottom_cap(bottom_cap)
+                .build();
+            let caps = top_cap as usize + bottom_cap as usize;
+
+            assert_eq!(
+                mesh.count_vertices(),
+                barrel_vertices + caps * cap_vertices,
+                "{top_cap} {bottom_cap}"
+            );
+            assert_eq!(
+                mesh.indices().unwrap().len(),
+                barrel_indices + caps * cap_indices,
+                "{top_cap} {bottom_cap}"
+            );
+
+            let positions = mesh
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3()
+                .unwrap();
+            let normals = mesh
+                .attribute(Mesh::ATTRIBUTE_NORMAL)
+                .unwrap()
+                .as_float3()
+                .unwrap();
+            let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+            else {
+                panic!("Expected uvs f32x2");
+            };
+
+            // The barrel comes first, with normals pointing away from the axis, and UVs covering
+            // the whole texture.
+            for (position, normal) in positions.iter().zip(normals).take(barrel_vertices) {
+                let radial = Vec3::new(position[0], 0.0, position[2]).normalize();
+                assert!(Vec3::from(*normal).abs_diff_eq(radial, 1e-6));
+            }
+            assert_eq!(uvs[0], [0.0, 0.0]);
+            assert_eq!(uvs[barrel_vertices - 1], [1.0, 1.0]);
+
+            // Then come the caps that are built, facing up and down.
+            let cap_normals: Vec<[f32; 3]> = normals[barrel_vertices..]
+                .chunks(cap_vertices)
+                .map(|cap| cap[0])
+                .collect();
+            let expected: Vec<[f32; 3]> =
+                [(top_cap, [0.0, 1.0, 0.0]), (bottom_cap, [0.0, -1.0, 0.0])]
+                    .into_iter()
+                    .filter_map(|(built, normal)| built.then_some(normal))
+                    .collect();
+            assert_eq!(cap_normals, expected, "{top_cap} {bottom_cap}");
+        }
+
+        // `without_caps` removes both caps.
+        let mesh = Cylinder::default().mesh().without_caps().build();
+        let tube = Cylinder::default()
+            .mesh()
+            .top_cap(false)
+            .bottom_cap(false)
+            .build();
+        assert_eq!(mesh.count_vertices(), tube.count_vertices());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{Mesh, MeshBuilder, VertexAttributeValues};
+use bevy_reflect::prelude::*;
+
+/// The direction that the faces of a mesh built for a closed 3D shape point in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub enum Facing {
+    /// The faces point out of the shape, so that it is visible from the outside.
+    #[default]
+    Outward,
+    /// The faces point into the shape, so that it is visible from the inside,
+    /// for example when used as a skydome.
+    Inward,
+}
+
+impl Facing {
+    /// Makes an outward-facing `mesh` face in this direction.
+    ///
+    /// For [`Facing::Inward`], this reverses the winding of the triangles and negates the
+    /// [normals](Mesh::ATTRIBUTE_NORMAL). The handedness of any [tangents](Mesh::ATTRIBUTE_TANGENT)
+    /// is flipped as well, so that normal maps keep their orientation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the winding of `mesh` cannot be inverted, see [`Mesh::invert_winding`].
+    pub fn apply(self, mut mesh: Mesh) -> Mesh {
+        if self == Facing::Outward {
+            return mesh;
+        }
+
+        mesh.invert_winding()
+            .expect("Primitive meshes should have a valid index count");
+
+        if let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+        {
+            for normal in normals.iter_mut().flatten() {
+                *normal = -*normal;
+            }
+        }
+
+        if let Some(VertexAttributeValues::Float32x4(tangents)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_TANGENT)
+        {
+            for tangent in tangents.iter_mut() {
+                tangent[3] = -tangent[3];
+            }
+        }
+
+        mesh
+    }
+}
+
+/// A [`MeshBuilder`] for a closed 3D shape whose faces can point either out of or into the
+/// shape.
+pub trait FacingMeshBuilder: MeshBuilder {
+    /// Sets the direction that the faces of the mesh point in.
+    ///
+    /// The default is [`Facing::Outward`].
+    fn facing(self, facing: Facing) -> Self;
+}
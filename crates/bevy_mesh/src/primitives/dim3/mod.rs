@@ -3,7 +3,9 @@ mod cone;
 mod conical_frustum;
 mod cuboid;
 mod cylinder;
+mod facing;
 mod plane;
+mod rounded_cuboid;
 mod sphere;
 mod tetrahedron;
 mod torus;
@@ -14,7 +16,9 @@ pub use cone::*;
 pub use conical_frustum::*;
 pub use cuboid::*;
 pub use cylinder::*;
+pub use facing::*;
 pub use plane::*;
+pub use rounded_cuboid::*;
 pub use sphere::*;
 pub use tetrahedron::*;
 pub use torus::*;
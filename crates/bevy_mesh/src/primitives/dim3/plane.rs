@@ -3,6 +3,31 @@ use bevy_asset::RenderAssetUsages;
 use bevy_math::{primitives::Plane3d, Dir3, Quat, Vec2, Vec3};
 use bevy_reflect::prelude::*;
 
+/// Where the origin of a plane mesh lies within the plane.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub enum PlaneOrigin {
+    /// The origin is at the center of the plane, so its local X and Z positions lie in
+    /// `[-size / 2, size / 2]`.
+    #[default]
+    Center,
+    /// The origin is at the corner of the plane with a UV of `(0, 0)`, so its local X and Z
+    /// positions lie in `[0, size]`.
+    ///
+    /// This is useful for tiling planes on a grid.
+    Corner,
+}
+
+impl PlaneOrigin {
+    /// Returns the local position of the corner with a UV of `(0, 0)`, as a fraction of the size.
+    fn min_corner(self) -> Vec2 {
+        match self {
+            PlaneOrigin::Center => Vec2::splat(-0.5),
+            PlaneOrigin::Corner => Vec2::ZERO,
+        }
+    }
+}
+
 /// A builder used for creating a [`Mesh`] with a [`Plane3d`] shape.
 #[derive(Clone, Copy, Debug, Default, Reflect)]
 #[reflect(Default, Debug, Clone)]
@@ -19,6 +44,8 @@ pub struct PlaneMeshBuilder {
     ///
     /// and so on...
     pub subdivisions: u32,
+    /// Where the origin of the mesh lies within the plane.
+    pub origin: PlaneOrigin,
 }
 
 impl PlaneMeshBuilder {
@@ -31,6 +58,7 @@ impl PlaneMeshBuilder {
                 half_size: size / 2.0,
             },
             subdivisions: 0,
+            origin: PlaneOrigin::Center,
         }
     }
 
@@ -43,6 +71,7 @@ impl PlaneMeshBuilder {
                 ..Default::default()
             },
             subdivisions: 0,
+            origin: PlaneOrigin::Center,
         }
     }
 
@@ -56,6 +85,7 @@ impl PlaneMeshBuilder {
                 ..Default::default()
             },
             subdivisions: 0,
+            origin: PlaneOrigin::Center,
         }
     }
 
@@ -91,6 +121,15 @@ impl PlaneMeshBuilder {
         self.subdivisions = subdivisions;
         self
     }
+
+    /// Sets where the origin of the plane mesh lies within the plane.
+    ///
+    /// The UVs follow the positions, so a UV of `(0, 0)` stays at the same corner of the plane.
+    #[inline]
+    pub fn origin(mut self, origin: PlaneOrigin) -> Self {
+        self.origin = origin;
+        self
+    }
 }
 
 impl MeshBuilder for PlaneMeshBuilder {
@@ -107,12 +146,18 @@ impl MeshBuilder for PlaneMeshBuilder {
 
         let rotation = Quat::from_rotation_arc(Vec3::Y, *self.plane.normal);
         let size = self.plane.half_size * 2.0;
+        let min_corner = self.origin.min_corner();
 
         for z in 0..z_vertex_count {
             for x in 0..x_vertex_count {
                 let tx = x as f32 / (x_vertex_count - 1) as f32;
                 let tz = z as f32 / (z_vertex_count - 1) as f32;
-                let pos = rotation * Vec3::new((-0.5 + tx) * size.x, 0.0, (-0.5 + tz) * size.y);
+                let pos = rotation
+                    * Vec3::new(
+                        (min_corner.x + tx) * size.x,
+                        0.0,
+                        (min_corner.y + tz) * size.y,
+                    );
                 positions.push(pos);
                 normals.push(self.plane.normal.to_array());
                 uvs.push([tx, tz]);
@@ -149,6 +194,7 @@ impl Meshable for Plane3d {
         PlaneMeshBuilder {
             plane: *self,
             subdivisions: 0,
+            origin: PlaneOrigin::Center,
         }
     }
 }
@@ -158,3 +204,49 @@ impl From<Plane3d> for Mesh {
         plane.mesh().build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VertexAttributeValues;
+
+    fn positions_and_uvs(mesh: &Mesh) -> (&[[f32; 3]], &[[f32; 2]]) {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("expected positions");
+        };
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("expected uvs");
+        };
+        (positions, uvs)
+    }
+
+    #[test]
+    fn corner_origin() {
+        let size = Vec2::new(4.0, 2.0);
+        let builder = PlaneMeshBuilder::from_size(size).subdivisions(2);
+        let centered = builder.build();
+        let cornered = builder.origin(PlaneOrigin::Corner).build();
+
+        let (positions, _) = positions_and_uvs(&centered);
+        for &[x, y, z] in positions {
+            assert!((-size.x / 2.0..=size.x / 2.0).contains(&x));
+            assert_eq!(y, 0.0);
+            assert!((-size.y / 2.0..=size.y / 2.0).contains(&z));
+        }
+
+        let (positions, uvs) = positions_and_uvs(&cornered);
+        assert!(positions.contains(&[0.0, 0.0, 0.0]));
+        assert!(positions.contains(&[size.x, 0.0, size.y]));
+        for (&[x, y, z], &[u, v]) in positions.iter().zip(uvs) {
+            assert!((0.0..=size.x).contains(&x));
+            assert_eq!(y, 0.0);
+            assert!((0.0..=size.y).contains(&z));
+            // The UVs map the corner at the origin to `(0, 0)`.
+            assert!((x / size.x - u).abs() < 1e-6);
+            assert!((z / size.y - v).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use crate::{Indices, Mesh, MeshBuilder, PrimitiveTopology};
+use bevy_asset::RenderAssetUsages;
+use bevy_math::{ops, primitives::Cuboid, Vec3};
+use bevy_reflect::prelude::*;
+use core::f32::consts::FRAC_PI_4;
+
+/// A builder used for creating a [`Mesh`] with the shape of a [`Cuboid`] with rounded edges and
+/// corners.
+///
+/// The edges are quarter cylinders and the corners are eighths of a sphere, all with the same
+/// [`radius`](Self::radius). Each face of the cuboid is textured with the whole texture, including
+/// the half of each rounded edge closest to it.
+#[derive(Clone, Copy, Debug, Reflect)]
+#[reflect(Default, Debug, Clone)]
+pub struct RoundedCuboidMeshBuilder {
+    /// Half of the width, height and depth of the cuboid, including its rounded edges.
+    pub half_size: Vec3,
+    /// The radius of the rounded edges and corners.
+    ///
+    /// It is clamped between `0` and the smallest component of
+    /// [`half_size`](Self::half_size), at which point the cuboid has no flat faces left
+    /// along that axis.
+    ///
+    /// The default is `0.1`.
+    pub radius: f32,
+    /// The number of segments each face uses to bend around each of its rounded edges.
+    ///
+    /// A rounded edge is shared by two faces, so it is made of twice as many segments. At least
+    /// one segment is used if the [`radius`](Self::radius) isn't `0`.
+    ///
+    /// The default is `4`.
+    pub subdivisions: u32,
+}
+
+impl Default for RoundedCuboidMeshBuilder {
+    /// Returns the default [`RoundedCuboidMeshBuilder`] with a width, height, and depth of `1.0`,
+    /// and a radius of `0.1`.
+    fn default() -> Self {
+        Self {
+            half_size: Vec3::splat(0.5),
+            radius: 0.1,
+            subdivisions: 4,
+        }
+    }
+}
+
+impl RoundedCuboidMeshBuilder {
+    /// Creates a new [`RoundedCuboidMeshBuilder`] from the half size of the cuboid, the radius
+    /// of its rounded edges, and the number of segments each face uses to bend around each of them.
+    #[inline]
+    pub const fn new(half_size: Vec3, radius: f32, subdivisions: u32) -> Self {
+        Self {
+            half_size,
+            radius,
+            subdivisions,
+        }
+    }
+
+    /// Sets the radius of the rounded edges and corners.
+    #[inline]
+    pub const fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the number of segments each face uses to bend around each of its rounded edges.
+    #[inline]
+    pub const fn subdivisions(mut self, subdivisions: u32) -> Self {
+        self.subdivisions = subdivisions;
+        self
+    }
+}
+
+impl From<Cuboid> for RoundedCuboidMeshBuilder {
+    fn from(cuboid: Cuboid) -> Self {
+        Self {
+            half_size: cuboid.half_size,
+            ..Default::default()
+        }
+    }
+}
+
+/// Samples of one axis of a face of a [`RoundedCuboidMeshBuilder`], as pairs of a coordinate
+/// on the face of the cuboid without rounding, and how far the surface is bent around the
+/// rounded edge there, as the tangent of the bending angle.
+fn axis_samples(
+    half_size: f32,
+    radius: f32,
+    subdivisions: u32,
+) -> impl Iterator<Item = (f32, f32)> {
+    let inner = half_size - radius;
+    let bend = move |i: u32| ops::tan(FRAC_PI_4 * i as f32 / subdivisions.max(1) as f32);
+    let start = (0..=subdivisions)
+        .rev()
+        .map(move |i| (-inner - radius * bend(i), -bend(i)));
+    let end = (0..=subdivisions).map(move |i| (inner + radius * bend(i), bend(i)));
+    start.chain(end)
+}
+
+impl MeshBuilder for RoundedCuboidMeshBuilder {
+    fn build(&self) -> Mesh {
+        let half_size = self.half_size.max(Vec3::ZERO);
+        let radius = self.radius.clamp(0.0, half_size.min_element());
+        let subdivisions = if radius > 0.0 {
+            self.subdivisions.max(1)
+        } else {
+            0
+        };
+        let inner = half_size - radius;
+
+        // The normal of each face, and the directions in which its U and V coordinates increase,
+        // such that the texture isn't mirrored when the face is seen from outside.
+        let faces = [
+            (Vec3::Z, Vec3::X, Vec3::NEG_Y),
+            (Vec3::NEG_Z, Vec3::NEG_X, Vec3::NEG_Y),
+            (Vec3::X, Vec3::NEG_Z, Vec3::NEG_Y),
+            (Vec3::NEG_X, Vec3::Z, Vec3::NEG_Y),
+            (Vec3::Y, Vec3::X, Vec3::Z),
+            (Vec3::NEG_Y, Vec3::X, Vec3::NEG_Z),
+        ];
+
+        let samples_per_axis = 2 * subdivisions + 2;
+        let vertices_per_face = (samples_per_axis * samples_per_axis) as usize;
+        let mut positions = Vec::with_capacity(6 * vertices_per_face);
+        let mut normals = Vec::with_capacity(6 * vertices_per_face);
+        let mut uvs = Vec::with_capacity(6 * vertices_per_face);
+        let mut indices = Vec::with_capacity(6 * 6 * (samples_per_axis as usize - 1).pow(2));
+
+        for (normal, u_dir, v_dir) in faces {
+            let first_vertex = positions.len() as u32;
+            let half_u = u_dir.abs().dot(half_size);
+            let half_v = v_dir.abs().dot(half_size);
+            let inner_u = u_dir.abs().dot(inner);
+            let inner_v = v_dir.abs().dot(inner);
+
+            for (v, v_bend) in axis_samples(half_v, radius, subdivisions) {
+                for (u, u_bend) in axis_samples(half_u, radius, subdivisions) {
+                    // The point the surface is at `radius` from, on the cuboid shrunk by `radius`.
+                    let center = normal * normal.abs().dot(inner)
+                        + u_dir * u.clamp(-inner_u, inner_u)
+                        + v_dir * v.clamp(-inner_v, inner_v);
+                    let vertex_normal = (normal + u_dir * u_bend + v_dir * v_bend).normalize();
+
+                    positions.push((center + vertex_normal * radius).to_array());
+                    normals.push(vertex_normal.to_array());
+                    uvs.push([
+                        0.5 + 0.5 * u / half_u.max(f32::EPSILON),
+                        0.5 + 0.5 * v / half_v.max(f32::EPSILON),
+                    ]);
+                }
+            }
+
+            for row in 0..samples_per_axis - 1 {
+                for column in 0..samples_per_axis - 1 {
+                    let i = first_vertex + row * samples_per_axis + column;
+                    let below = i + samples_per_axis;
+                    indices.extend_from_slice(&[i, below, i + 1, i + 1, below, below + 1]);
+                }
+            }
+        }
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RoundedCuboidMeshBuilder;
+    use crate::{Mesh, MeshBuilder, Meshable, VertexAttributeValues};
+    use bevy_math::{primitives::Cuboid, FloatOrd, Vec3};
+    use bevy_platform::collections::HashMap;
+
+    fn positions(mesh: &Mesh) -> Vec<Vec3> {
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .unwrap()
+            .iter()
+            .map(|&position| Vec3::from_array(position))
+            .collect()
+    }
+
+    fn bounds(positions: &[Vec3]) -> (Vec3, Vec3) {
+        positions.iter().fold(
+            (Vec3::INFINITY, Vec3::NEG_INFINITY),
+            |(min, max), &position| (min.min(position), max.max(position)),
+        )
+    }
+
+    #[test]
+    fn rounded_cuboid_is_watertight() {
+        for radius in [0.0, 0.1, 0.2] {
+            for subdivisions in [1, 4] {
+                let mesh =
+                    RoundedCuboidMeshBuilder::new(Vec3::new(1.0, 0.5, 0.25), radius, subdivisions)
+                        .build();
+                let positions = positions(&mesh);
+
+                // Faces have their own vertices, so identify vertices by their position.
+                let key = |index: usize| {
+                    positions[index].to_array().map(|coord| {
+                        // Turn -0.0 into 0.0.
+                        FloatOrd(coord + 0.0)
+                    })
+                };
+
+                // In a closed mesh, every edge is used once in each direction.
+                let mut edges = HashMap::<_, i32>::default();
+                let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+                for triangle in indices.chunks_exact(3) {
+                    for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+                        let (a, b) = (key(triangle[a]), key(triangle[b]));
+                        if a < b {
+                            *edges.entry((a, b)).or_default() += 1;
+                        } else {
+                            *edges.entry((b, a)).or_default() -= 1;
+                        }
+                    }
+                }
+                assert!(edges.values().all(|&count| count == 0));
+
+                // Triangles face the same way as the normals of their vertices.
+                let Some(VertexAttributeValues::Float32x3(normals)) =
+                    mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+                else {
+                    panic!("Expected normals f32x3");
+                };
+                for triangle in indices.chunks_exact(3) {
+                    let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i]]);
+                    let face_normal = (b - a).cross(c - a);
+                    if face_normal.length() < 1e-6 {
+                        continue;
+                    }
+                    for &i in triangle {
+                        let normal = Vec3::from_array(normals[i]);
+                        assert!((normal.length() - 1.0).abs() < 1e-5);
+                        assert!(face_normal.dot(normal) > 0.0);
+                    }
+                }
+
+                let Some(VertexAttributeValues::Float32x2(uvs)) =
+                    mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                else {
+                    panic!("Expected uvs f32x2");
+                };
+                for uv in uvs {
+                    assert!(uv.iter().all(|coord| (0.0..=1.0).contains(coord)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rounded_cuboid_without_radius_is_cuboid() {
+        let half_size = Vec3::new(1.0, 0.5, 0.25);
+        let cuboid = Cuboid::from_size(2.0 * half_size).mesh().build();
+        let rounded = RoundedCuboidMeshBuilder::new(half_size, 0.0, 4).build();
+
+        assert_eq!(rounded.count_vertices(), cuboid.count_vertices());
+        assert_eq!(bounds(&positions(&rounded)), bounds(&positions(&cuboid)));
+    }
+
+    #[test]
+    fn rounded_cuboid_clamps_radius() {
+        let half_size = Vec3::new(1.0, 0.5, 0.25);
+        let mesh = RoundedCuboidMeshBuilder::new(half_size, 10.0, 4).build();
+        let positions = positions(&mesh);
+
+        let (min, max) = bounds(&positions);
+        assert!(min.abs_diff_eq(-half_size, 1e-5));
+        assert!(max.abs_diff_eq(half_size, 1e-5));
+
+        // The radius is clamped to the smallest half extent, so the corners are rounded by
+        // spheres of that radius.
+        let inner = half_size - 0.25;
+        for position in positions {
+            let center = position.clamp(-inner, inner);
+            assert!((position.distance(center) - 0.25).abs() < 1e-5);
+        }
+    }
+}
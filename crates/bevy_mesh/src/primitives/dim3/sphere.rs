@@ -1,8 +1,8 @@
-use crate::{Indices, Mesh, MeshBuilder, Meshable, PrimitiveTopology};
+use crate::{Facing, FacingMeshBuilder, Indices, Mesh, MeshBuilder, Meshable, PrimitiveTopology};
 use bevy_asset::RenderAssetUsages;
 use bevy_math::{ops, primitives::Sphere};
 use bevy_reflect::prelude::*;
-use core::f32::consts::PI;
+use core::f32::consts::{PI, TAU};
 use hexasphere::shapes::IcoSphere;
 use thiserror::Error;
 
@@ -47,6 +47,43 @@ impl Default for SphereKind {
     }
 }
 
+/// Manner in which UV coordinates of a sphere mesh are distributed vertically.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub enum SphereUvProfile {
+    /// V is proportional to the latitude, so every stack of a UV sphere gets the same
+    /// amount of texture.
+    #[default]
+    Latitude,
+    /// V is proportional to the height along the axis of the poles, so that every part of
+    /// the texture covers the same area of the sphere. This squashes the texture near the poles.
+    Height,
+}
+
+impl SphereUvProfile {
+    /// Returns the V coordinate of a point of the sphere, given the cosine of its angle from the
+    /// north pole.
+    fn v(self, cos_inclination: f32) -> f32 {
+        match self {
+            Self::Latitude => ops::acos(cos_inclination.clamp(-1.0, 1.0)) / PI,
+            Self::Height => (1.0 - cos_inclination) / 2.0,
+        }
+    }
+}
+
+/// How the poles of a UV sphere mesh are built.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub enum SpherePoleStyle {
+    /// Each sector has its own vertex at the poles, with a U coordinate in the middle of the
+    /// sector. This keeps textures from pinching and twisting around the poles.
+    #[default]
+    Fan,
+    /// All sectors share a single vertex at each pole, which saves vertices, but pinches textures
+    /// around the poles.
+    SharedApex,
+}
+
 /// A builder used for creating a [`Mesh`] with an [`Sphere`] shape.
 #[derive(Clone, Copy, Debug, Default, Reflect)]
 #[reflect(Default, Debug, Clone)]
@@ -55,6 +92,15 @@ pub struct SphereMeshBuilder {
     pub sphere: Sphere,
     /// The type of sphere mesh that will be built.
     pub kind: SphereKind,
+    /// The manner in which UV coordinates are distributed vertically.
+    /// The default is [`SphereUvProfile::Latitude`].
+    pub uv_profile: SphereUvProfile,
+    /// How the poles of a [`SphereKind::Uv`] sphere are built.
+    /// The default is [`SpherePoleStyle::Fan`].
+    pub pole_style: SpherePoleStyle,
+    /// The direction that the faces of the sphere point in.
+    /// The default is [`Facing::Outward`].
+    pub facing: Facing,
 }
 
 impl SphereMeshBuilder {
@@ -64,6 +110,9 @@ impl SphereMeshBuilder {
         Self {
             sphere: Sphere { radius },
             kind,
+            uv_profile: SphereUvProfile::Latitude,
+            pole_style: SpherePoleStyle::Fan,
+            facing: Facing::Outward,
         }
     }
 
@@ -74,6 +123,20 @@ impl SphereMeshBuilder {
         self
     }
 
+    /// Sets the manner in which UV coordinates are distributed vertically.
+    #[inline]
+    pub const fn uv_profile(mut self, uv_profile: SphereUvProfile) -> Self {
+        self.uv_profile = uv_profile;
+        self
+    }
+
+    /// Sets how the poles of a [`SphereKind::Uv`] sphere are built.
+    #[inline]
+    pub const fn pole_style(mut self, pole_style: SpherePoleStyle) -> Self {
+        self.pole_style = pole_style;
+        self
+    }
+
     /// Creates an icosphere mesh with the given number of subdivisions.
     ///
     /// The number of faces quadruples with each subdivision.
@@ -118,12 +181,12 @@ impl SphereMeshBuilder {
                 number_of_resulting_points,
             });
         }
+        let uv_profile = self.uv_profile;
         let generated = IcoSphere::new(subdivisions as usize, |point| {
-            let inclination = ops::acos(point.y);
             let azimuth = ops::atan2(point.z, point.x);
 
-            let norm_inclination = inclination / PI;
-            let norm_azimuth = 0.5 - (azimuth / core::f32::consts::TAU);
+            let norm_inclination = uv_profile.v(point.y);
+            let norm_azimuth = 0.5 - (azimuth / TAU);
 
             [norm_azimuth, norm_inclination]
         });
@@ -164,59 +227,97 @@ impl SphereMeshBuilder {
     /// Creates a UV sphere [`Mesh`] with the given number of
     /// longitudinal sectors and latitudinal stacks, aka horizontal and vertical resolution.
     ///
+    /// The poles are built according to the [`pole_style`](Self::pole_style), and the UV
+    /// coordinates are distributed according to the [`uv_profile`](Self::uv_profile).
+    ///
     /// A good default is `32` sectors and `18` stacks.
     pub fn uv(&self, sectors: u32, stacks: u32) -> Mesh {
         // Largely inspired from http://www.songho.ca/opengl/gl_sphere.html
 
+        let radius = self.sphere.radius;
         let sectors_f32 = sectors as f32;
-        let stacks_f32 = stacks as f32;
-        let length_inv = 1. / self.sphere.radius;
-        let sector_step = 2. * PI / sectors_f32;
-        let stack_step = PI / stacks_f32;
-
-        let n_vertices = (stacks * sectors) as usize;
+        let sector_step = TAU / sectors_f32;
+        let stack_step = PI / stacks as f32;
+
+        // The poles are on the Z axis, with rings of `sectors + 1` vertices between them,
+        // duplicating the vertices of the seam so that the texture can wrap around.
+        let rings = stacks.saturating_sub(1);
+        let pole_vertices = match self.pole_style {
+            SpherePoleStyle::Fan => sectors,
+            SpherePoleStyle::SharedApex => 1,
+        };
+        let n_vertices = (rings * (sectors + 1) + 2 * pole_vertices) as usize;
         let mut vertices: Vec<[f32; 3]> = Vec::with_capacity(n_vertices);
         let mut normals: Vec<[f32; 3]> = Vec::with_capacity(n_vertices);
         let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(n_vertices);
-        let mut indices: Vec<u32> = Vec::with_capacity(n_vertices * 2 * 3);
+        let mut indices: Vec<u32> = Vec::with_capacity((sectors * rings * 6) as usize);
 
         for i in 0..stacks + 1 {
             let stack_angle = PI / 2. - (i as f32) * stack_step;
-            let xy = self.sphere.radius * ops::cos(stack_angle);
-            let z = self.sphere.radius * ops::sin(stack_angle);
+
+            if i == 0 || i == stacks {
+                // The poles are placed exactly on the axis, at the ends of the V range.
+                let (z, v) = if i == 0 { (1.0, 0.0) } else { (-1.0, 1.0) };
+                for j in 0..pole_vertices {
+                    let u = match self.pole_style {
+                        SpherePoleStyle::Fan => (j as f32 + 0.5) / sectors_f32,
+                        SpherePoleStyle::SharedApex => 0.5,
+                    };
+                    vertices.push([0.0, 0.0, z * radius]);
+                    normals.push([0.0, 0.0, z]);
+                    uvs.push([u, v]);
+                }
+                continue;
+            }
+
+            let xy = ops::cos(stack_angle);
+            let z = ops::sin(stack_angle);
+            let v = self.uv_profile.v(z);
 
             for j in 0..sectors + 1 {
                 let sector_angle = (j as f32) * sector_step;
                 let x = xy * ops::cos(sector_angle);
                 let y = xy * ops::sin(sector_angle);
 
-                vertices.push([x, y, z]);
-                normals.push([x * length_inv, y * length_inv, z * length_inv]);
-                uvs.push([(j as f32) / sectors_f32, (i as f32) / stacks_f32]);
+                vertices.push([x * radius, y * radius, z * radius]);
+                normals.push([x, y, z]);
+                uvs.push([(j as f32) / sectors_f32, v]);
             }
         }
 
-        // indices
-        //  k1--k1+1
-        //  |  / |
-        //  | /  |
-        //  k2--k2+1
-        for i in 0..stacks {
-            let mut k1 = i * (sectors + 1);
-            let mut k2 = k1 + sectors + 1;
-            for _j in 0..sectors {
-                if i != 0 {
-                    indices.push(k1);
-                    indices.push(k2);
-                    indices.push(k1 + 1);
-                }
-                if i != stacks - 1 {
-                    indices.push(k1 + 1);
-                    indices.push(k2);
-                    indices.push(k2 + 1);
+        // Without any rings, the poles aren't connected by any triangles.
+        if rings > 0 {
+            let ring_start = |ring: u32| pole_vertices + (ring - 1) * (sectors + 1);
+            let pole = |start: u32, sector: u32| match self.pole_style {
+                SpherePoleStyle::Fan => start + sector,
+                SpherePoleStyle::SharedApex => start,
+            };
+            let south_pole_start = ring_start(rings) + sectors + 1;
+
+            // North pole fan
+            let k2 = ring_start(1);
+            for j in 0..sectors {
+                indices.extend([pole(0, j), k2 + j, k2 + j + 1]);
+            }
+
+            // indices
+            //  k1--k1+1
+            //  |  / |
+            //  | /  |
+            //  k2--k2+1
+            for i in 1..rings {
+                let k1 = ring_start(i);
+                let k2 = ring_start(i + 1);
+                for j in 0..sectors {
+                    indices.extend([k1 + j, k2 + j, k1 + j + 1]);
+                    indices.extend([k1 + j + 1, k2 + j, k2 + j + 1]);
                 }
-                k1 += 1;
-                k2 += 1;
+            }
+
+            // South pole fan
+            let k1 = ring_start(rings);
+            for j in 0..sectors {
+                indices.extend([k1 + j, pole(south_pole_start, j), k1 + j + 1]);
             }
         }
 
@@ -239,10 +340,18 @@ impl MeshBuilder for SphereMeshBuilder {
     /// Panics if the sphere is a [`SphereKind::Ico`] with a subdivision count
     /// that is greater than or equal to `80` because there will be too many vertices.
     fn build(&self) -> Mesh {
-        match self.kind {
+        let mesh = match self.kind {
             SphereKind::Ico { subdivisions } => self.ico(subdivisions).unwrap(),
             SphereKind::Uv { sectors, stacks } => self.uv(sectors, stacks),
-        }
+        };
+        self.facing.apply(mesh)
+    }
+}
+
+impl FacingMeshBuilder for SphereMeshBuilder {
+    fn facing(mut self, facing: Facing) -> Self {
+        self.facing = facing;
+        self
     }
 }
 
@@ -262,3 +371,101 @@ impl From<Sphere> for Mesh {
         sphere.mesh().build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{SpherePoleStyle, SphereUvProfile};
+    use crate::{Mesh, Meshable, VertexAttributeValues};
+    use bevy_math::{ops, primitives::Sphere};
+
+    fn positions_and_uvs(mesh: &Mesh) -> (&[[f32; 3]], &[[f32; 2]]) {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("Expected positions f32x3");
+        };
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("Expected uvs f32x2");
+        };
+        (positions, uvs)
+    }
+
+    #[test]
+    fn uv_sphere_pole_styles() {
+        let (sectors, stacks) = (8, 6);
+        let sphere = Sphere::new(2.0);
+        let fan = sphere
+            .mesh()
+            .pole_style(SpherePoleStyle::Fan)
+            .uv(sectors, stacks);
+        let apex = sphere
+            .mesh()
+            .pole_style(SpherePoleStyle::SharedApex)
+            .uv(sectors, stacks);
+
+        let pole_uvs = |mesh: &Mesh, z: f32| {
+            let (positions, uvs) = positions_and_uvs(mesh);
+            positions
+                .iter()
+                .zip(uvs)
+                .filter(|(position, _)| **position == [0.0, 0.0, z])
+                .map(|(_, uv)| *uv)
+                .collect::<Vec<_>>()
+        };
+
+        // A fan has a vertex per sector at each pole, in the middle of the sector.
+        for (z, v) in [(2.0, 0.0), (-2.0, 1.0)] {
+            let expected = (0..sectors)
+                .map(|j| [(j as f32 + 0.5) / sectors as f32, v])
+                .collect::<Vec<_>>();
+            assert_eq!(pole_uvs(&fan, z), expected);
+            assert_eq!(pole_uvs(&apex, z), [[0.5, v]]);
+        }
+
+        // Both styles have the same rings and triangles, and every vertex is used.
+        let ring_vertices = (stacks - 1) * (sectors + 1);
+        assert_eq!(fan.count_vertices() as u32, ring_vertices + 2 * sectors);
+        assert_eq!(apex.count_vertices() as u32, ring_vertices + 2);
+        for mesh in [&fan, &apex] {
+            let indices = mesh.indices().unwrap();
+            assert_eq!(indices.len() as u32, sectors * (stacks - 1) * 6);
+            let mut used = vec![false; mesh.count_vertices()];
+            for index in indices.iter() {
+                used[index] = true;
+            }
+            assert!(used.into_iter().all(|used| used));
+        }
+    }
+
+    #[test]
+    fn sphere_uv_profiles() {
+        let sphere = Sphere::new(2.0);
+        let latitude = sphere.mesh().uv(4, 4);
+        let height = sphere.mesh().uv_profile(SphereUvProfile::Height).uv(4, 4);
+
+        // V follows the angle from the north pole, or the height below it.
+        let (positions, uvs) = positions_and_uvs(&latitude);
+        for (position, uv) in positions.iter().zip(uvs) {
+            let expected = ops::acos(position[2] / 2.0) / core::f32::consts::PI;
+            assert!((uv[1] - expected).abs() < 1e-5, "{uv:?} at {position:?}");
+        }
+        let (positions, uvs) = positions_and_uvs(&height);
+        for (position, uv) in positions.iter().zip(uvs) {
+            let expected = (1.0 - position[2] / 2.0) / 2.0;
+            assert!((uv[1] - expected).abs() < 1e-5, "{uv:?} at {position:?}");
+        }
+
+        // The profile also applies to icospheres, whose poles are on the Y axis.
+        let ico = sphere
+            .mesh()
+            .uv_profile(SphereUvProfile::Height)
+            .ico(2)
+            .unwrap();
+        let (positions, uvs) = positions_and_uvs(&ico);
+        for (position, uv) in positions.iter().zip(uvs) {
+            let expected = (1.0 - position[1] / 2.0) / 2.0;
+            assert!((uv[1] - expected).abs() < 1e-5, "{uv:?} at {position:?}");
+        }
+    }
+}
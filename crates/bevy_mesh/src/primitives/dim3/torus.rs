@@ -2,7 +2,7 @@ use crate::{Indices, Mesh, MeshBuilder, Meshable, PrimitiveTopology};
 use bevy_asset::RenderAssetUsages;
 use bevy_math::{ops, primitives::Torus, Vec3};
 use bevy_reflect::prelude::*;
-use core::ops::RangeInclusive;
+use core::{f32::consts::TAU, ops::RangeInclusive};
 
 /// A builder used for creating a [`Mesh`] with a [`Torus`] shape.
 #[derive(Clone, Debug, Reflect)]
@@ -20,9 +20,15 @@ pub struct TorusMeshBuilder {
     /// A resolution of `4` would make the torus appear rectangular,
     /// while a resolution of `32` resembles a circular ring.
     ///
+    /// If the [`angle_range`](Self::angle_range) only covers part of the ring,
+    /// the number of segments is reduced proportionally.
+    ///
     /// The default is `32`.
     pub major_resolution: usize,
     /// Optional angle range in radians, defaults to a full circle (0.0..=2 * PI)
+    ///
+    /// If the range covers less than a full circle, the ends of the tube are closed with flat
+    /// caps, and the texture coordinates match those of the corresponding part of a full torus.
     pub angle_range: RangeInclusive<f32>,
 }
 
@@ -32,7 +38,7 @@ impl Default for TorusMeshBuilder {
             torus: Torus::default(),
             minor_resolution: 24,
             major_resolution: 32,
-            angle_range: (0.0..=2.0 * core::f32::consts::PI),
+            angle_range: (0.0..=TAU),
         }
     }
 }
@@ -80,18 +86,32 @@ impl MeshBuilder for TorusMeshBuilder {
     fn build(&self) -> Mesh {
         // code adapted from http://apparat-engine.blogspot.com/2013/04/procedural-meshes-torus.html
 
-        let n_vertices = (self.major_resolution + 1) * (self.minor_resolution + 1);
+        let start_angle = *self.angle_range.start();
+        let end_angle = *self.angle_range.end();
+        let sweep = end_angle - start_angle;
+        let is_full_circle = sweep.abs() >= TAU - f32::EPSILON;
+
+        // Keep the segments the same size as those of a full torus with the same resolution.
+        let major_segments = if is_full_circle {
+            self.major_resolution
+        } else {
+            ops::ceil(self.major_resolution as f32 * sweep.abs() / TAU).max(1.0) as usize
+        };
+
+        let n_vertices = (major_segments + 1) * (self.minor_resolution + 1)
+            + if is_full_circle {
+                0
+            } else {
+                2 * (self.minor_resolution + 1)
+            };
         let mut positions: Vec<[f32; 3]> = Vec::with_capacity(n_vertices);
         let mut normals: Vec<[f32; 3]> = Vec::with_capacity(n_vertices);
         let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(n_vertices);
 
-        let start_angle = self.angle_range.start();
-        let end_angle = self.angle_range.end();
+        let segment_stride = sweep / major_segments as f32;
+        let side_stride = TAU / self.minor_resolution as f32;
 
-        let segment_stride = (end_angle - start_angle) / self.major_resolution as f32;
-        let side_stride = 2.0 * core::f32::consts::PI / self.minor_resolution as f32;
-
-        for segment in 0..=self.major_resolution {
+        for segment in 0..=major_segments {
             let theta = start_angle + segment_stride * segment as f32;
 
             for side in 0..=self.minor_resolution {
@@ -116,20 +136,25 @@ impl MeshBuilder for TorusMeshBuilder {
                 positions.push(position.into());
                 normals.push(normal.into());
                 uvs.push([
-                    segment as f32 / self.major_resolution as f32,
+                    (theta - start_angle).abs() / TAU,
                     side as f32 / self.minor_resolution as f32,
                 ]);
             }
         }
 
-        let n_faces = (self.major_resolution) * (self.minor_resolution);
-        let n_triangles = n_faces * 2;
+        let n_faces = major_segments * self.minor_resolution;
+        let n_triangles = n_faces * 2
+            + if is_full_circle {
+                0
+            } else {
+                2 * (self.minor_resolution - 2)
+            };
         let n_indices = n_triangles * 3;
 
         let mut indices: Vec<u32> = Vec::with_capacity(n_indices);
 
         let n_vertices_per_row = self.minor_resolution + 1;
-        for segment in 0..self.major_resolution {
+        for segment in 0..major_segments {
             for side in 0..self.minor_resolution {
                 let lt = side + segment * n_vertices_per_row;
                 let rt = (side + 1) + segment * n_vertices_per_row;
@@ -137,16 +162,54 @@ impl MeshBuilder for TorusMeshBuilder {
                 let lb = side + (segment + 1) * n_vertices_per_row;
                 let rb = (side + 1) + (segment + 1) * n_vertices_per_row;
 
-                indices.push(lt as u32);
-                indices.push(rt as u32);
-                indices.push(lb as u32);
-
-                indices.push(rt as u32);
-                indices.push(rb as u32);
-                indices.push(lb as u32);
+                // Keep the faces pointing outward when sweeping in the negative direction.
+                if sweep < 0.0 {
+                    indices.extend_from_slice(&[lt as u32, lb as u32, rt as u32]);
+                    indices.extend_from_slice(&[rt as u32, lb as u32, rb as u32]);
+                } else {
+                    indices.extend_from_slice(&[lt as u32, rt as u32, lb as u32]);
+                    indices.extend_from_slice(&[rt as u32, rb as u32, lb as u32]);
+                }
             }
         }
 
+        // Close the ends of a partial torus with flat caps.
+        if !is_full_circle {
+            let mut build_cap = |theta: f32, is_start: bool| {
+                let offset = positions.len() as u32;
+                let (sin_theta, cos_theta) = ops::sin_cos(theta);
+                // The cap faces away from the rest of the tube, along the ring's tangent.
+                let tangent = Vec3::new(-sin_theta, 0.0, cos_theta) * sweep.signum();
+                let normal = if is_start { -tangent } else { tangent };
+
+                for side in 0..self.minor_resolution {
+                    let phi = side_stride * side as f32;
+                    let (sin_phi, cos_phi) = ops::sin_cos(phi);
+                    let radius = self.torus.major_radius + self.torus.minor_radius * cos_phi;
+
+                    positions.push([
+                        cos_theta * radius,
+                        self.torus.minor_radius * sin_phi,
+                        sin_theta * radius,
+                    ]);
+                    normals.push(normal.into());
+                    uvs.push([0.5 * (cos_phi + 1.0), 1.0 - 0.5 * (sin_phi + 1.0)]);
+                }
+
+                let facing_tangent = is_start == (sweep < 0.0);
+                for side in 1..(self.minor_resolution as u32 - 1) {
+                    if facing_tangent {
+                        indices.extend_from_slice(&[offset, offset + side, offset + side + 1]);
+                    } else {
+                        indices.extend_from_slice(&[offset, offset + side + 1, offset + side]);
+                    }
+                }
+            };
+
+            build_cap(start_angle, true);
+            build_cap(end_angle, false);
+        }
+
         Mesh::new(
             PrimitiveTopology::TriangleList,
             RenderAssetUsages::default(),
@@ -174,3 +237,57 @@ impl From<Torus> for Mesh {
         torus.mesh().build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Mesh, MeshBuilder, Meshable, VertexAttributeValues};
+    use bevy_math::{primitives::Torus, Vec3};
+    use core::f32::consts::{PI, TAU};
+
+    #[test]
+    fn partial_torus_counts() {
+        let builder = Torus::new(0.5, 1.0)
+            .mesh()
+            .minor_resolution(8)
+            .major_resolution(16);
+
+        let full = builder.clone().build();
+        assert_eq!(17 * 9, full.count_vertices());
+        assert_eq!(16 * 8 * 6, full.indices().unwrap().len());
+
+        // Three quarters of the ring use three quarters of the segments,
+        // plus a cap of 8 vertices and 6 triangles at each end.
+        let partial = builder.clone().angle_range(0.0..=1.5 * PI).build();
+        assert_eq!(13 * 9 + 2 * 8, partial.count_vertices());
+        assert_eq!(12 * 8 * 6 + 2 * 6 * 3, partial.indices().unwrap().len());
+
+        let half = builder.angle_range(0.0..=PI).build();
+        assert_eq!(9 * 9 + 2 * 8, half.count_vertices());
+        assert_eq!(8 * 8 * 6 + 2 * 6 * 3, half.indices().unwrap().len());
+    }
+
+    #[test]
+    fn partial_torus_caps_face_outward() {
+        for angle_range in [0.0..=1.5 * PI, 1.5 * PI..=0.25 * PI, 0.0..=TAU] {
+            let mesh = Torus::new(0.5, 1.0)
+                .mesh()
+                .minor_resolution(8)
+                .major_resolution(16)
+                .angle_range(angle_range)
+                .build();
+
+            let Some(VertexAttributeValues::Float32x3(normals)) =
+                mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+            else {
+                panic!("Expected normals f32x3");
+            };
+            let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+            for (triangle, chunk) in mesh.triangles().unwrap().zip(indices.chunks_exact(3)) {
+                let face_normal = triangle.normal().unwrap();
+                for &i in chunk {
+                    assert!(face_normal.dot(Vec3::from(normals[i])) > 0.0);
+                }
+            }
+        }
+    }
+}
@@ -45,6 +45,13 @@ pub trait MeshBuilder {
     fn build(&self) -> Mesh;
 }
 
+/// A trait for shapes that can be recovered from a [`Mesh`] of them, for example to derive a
+/// collider from a mesh.
+pub trait TryFromMesh: Sized {
+    /// Returns the shape that the [`Mesh`] is a mesh of, or `None` if it doesn't look like one.
+    fn try_from_mesh(mesh: &Mesh) -> Option<Self>;
+}
+
 impl<T: MeshBuilder> From<T> for Mesh {
     fn from(builder: T) -> Self {
         builder.build()
@@ -252,6 +252,56 @@ impl VertexAttributeValues {
         }
     }
 
+    /// Converts the values to 3D vectors, as positions or directions, if they have at least 3
+    /// components.
+    ///
+    /// The fourth component of 4-component formats is ignored. Normalized formats, like the
+    /// quantized positions of `KHR_mesh_quantization`, are mapped to `[-1.0, 1.0]` or
+    /// `[0.0, 1.0]`, and integer formats are converted as is.
+    ///
+    /// Returns `None` for formats with fewer than 3 components. Half-float formats, like
+    /// [`VertexFormat::Float16x4`], aren't representable in [`VertexAttributeValues`] at all, so
+    /// there are no half-float values to convert.
+    pub fn to_vec3s(&self) -> Option<Vec<Vec3>> {
+        fn snorm16(value: i16) -> f32 {
+            (value as f32 / i16::MAX as f32).max(-1.0)
+        }
+        fn unorm16(value: u16) -> f32 {
+            value as f32 / u16::MAX as f32
+        }
+        fn snorm8(value: i8) -> f32 {
+            (value as f32 / i8::MAX as f32).max(-1.0)
+        }
+        fn unorm8(value: u8) -> f32 {
+            value as f32 / u8::MAX as f32
+        }
+        fn convert<T: Copy, const N: usize>(values: &[[T; N]], f: impl Fn(T) -> f32) -> Vec<Vec3> {
+            values
+                .iter()
+                .map(|value| Vec3::new(f(value[0]), f(value[1]), f(value[2])))
+                .collect()
+        }
+
+        let vectors = match self {
+            VertexAttributeValues::Float32x3(values) => convert(values, |value| value),
+            VertexAttributeValues::Float32x4(values) => convert(values, |value| value),
+            VertexAttributeValues::Sint32x3(values) => convert(values, |value| value as f32),
+            VertexAttributeValues::Uint32x3(values) => convert(values, |value| value as f32),
+            VertexAttributeValues::Sint32x4(values) => convert(values, |value| value as f32),
+            VertexAttributeValues::Uint32x4(values) => convert(values, |value| value as f32),
+            VertexAttributeValues::Sint16x4(values) => convert(values, f32::from),
+            VertexAttributeValues::Snorm16x4(values) => convert(values, snorm16),
+            VertexAttributeValues::Uint16x4(values) => convert(values, f32::from),
+            VertexAttributeValues::Unorm16x4(values) => convert(values, unorm16),
+            VertexAttributeValues::Sint8x4(values) => convert(values, f32::from),
+            VertexAttributeValues::Snorm8x4(values) => convert(values, snorm8),
+            VertexAttributeValues::Uint8x4(values) => convert(values, f32::from),
+            VertexAttributeValues::Unorm8x4(values) => convert(values, unorm8),
+            _ => return None,
+        };
+        Some(vectors)
+    }
+
     // TODO: add vertex format as parameter here and perform type conversions
     /// Flattens the [`VertexAttributeValues`] into a sequence of bytes. This is
     /// useful for serialization and sending to the GPU.
@@ -0,0 +1,128 @@
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::Assets;
+use bevy_color::{Color, ColorRange, Gradient};
+use bevy_ecs::prelude::*;
+use bevy_time::Time;
+
+use crate::{MeshMaterial3d, StandardMaterial};
+
+/// Adds support for [`AnimatedColor`].
+///
+/// This is included in [`PbrPlugin`](crate::PbrPlugin).
+pub struct AnimatedColorPlugin;
+
+impl Plugin for AnimatedColorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, animate_material_colors);
+    }
+}
+
+/// Cycles the [`base_color`](StandardMaterial::base_color) of an entity's
+/// [`MeshMaterial3d<StandardMaterial>`] through a gradient over time.
+///
+/// The color follows the gradient from position `0.0` to `1.0`, then starts over, `speed` times
+/// per second. A negative `speed` runs through the gradient backwards.
+///
+/// The material asset itself is changed, so every entity using the same material changes color
+/// along with it. Give each animated entity its own material to animate them independently.
+///
+/// ```
+/// # use bevy_color::{palettes::basic::{BLUE, RED}, Color, Gradient};
+/// # use bevy_pbr::AnimatedColor;
+/// // Pulse from red to blue and back, once every two seconds.
+/// let pulse = AnimatedColor {
+///     range: Gradient::new([
+///         (0.0, Color::from(RED)),
+///         (0.5, Color::from(BLUE)),
+///         (1.0, Color::from(RED)),
+///     ])
+///     .unwrap(),
+///     speed: 0.5,
+/// };
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct AnimatedColor {
+    /// The gradient the color is taken from.
+    pub range: Gradient<Color>,
+    /// How many times per second the color cycles through the gradient.
+    pub speed: f32,
+}
+
+impl AnimatedColor {
+    /// Returns the color after `elapsed` seconds.
+    pub fn color_at(&self, elapsed: f32) -> Color {
+        self.range.at((elapsed * self.speed).rem_euclid(1.0))
+    }
+}
+
+/// Updates the base color of the materials of entities with an [`AnimatedColor`].
+pub fn animate_material_colors(
+    time: Res<Time>,
+    animated: Query<(&AnimatedColor, &MeshMaterial3d<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let elapsed = time.elapsed_secs();
+    for (animated_color, material) in &animated {
+        let color = animated_color.color_at(elapsed);
+        // Avoid marking the material as changed, and re-uploading it, if the color is the same.
+        if materials
+            .get(material)
+            .is_none_or(|material| material.base_color == color)
+        {
+            continue;
+        }
+        if let Some(material) = materials.get_mut(material) {
+            material.base_color = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_color::palettes::basic::{BLUE, LIME, RED};
+    use bevy_ecs::system::RunSystemOnce;
+    use core::time::Duration;
+
+    #[test]
+    fn material_color_follows_gradient() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let mut materials = Assets::<StandardMaterial>::default();
+        let material = materials.add(StandardMaterial::default());
+        world.insert_resource(materials);
+
+        let gradient = Gradient::new([
+            (0.0, Color::from(RED)),
+            (0.5, Color::from(LIME)),
+            (1.0, Color::from(BLUE)),
+        ])
+        .unwrap();
+        world.spawn((
+            AnimatedColor {
+                range: gradient.clone(),
+                speed: 0.25,
+            },
+            MeshMaterial3d(material.clone()),
+        ));
+
+        let mut base_color_after = |seconds: f32| {
+            world
+                .resource_mut::<Time>()
+                .advance_to(Duration::from_secs_f32(seconds));
+            world.run_system_once(animate_material_colors).unwrap();
+            world
+                .resource::<Assets<StandardMaterial>>()
+                .get(&material)
+                .unwrap()
+                .base_color
+        };
+
+        assert_eq!(base_color_after(0.0), gradient.at(0.0));
+        assert_eq!(base_color_after(1.0), gradient.at(0.25));
+        assert_eq!(base_color_after(2.0), gradient.at(0.5));
+        assert_eq!(base_color_after(3.0), gradient.at(0.75));
+        // The color starts over after a full cycle.
+        assert_eq!(base_color_after(5.0), gradient.at(0.25));
+    }
+}
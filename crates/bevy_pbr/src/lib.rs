@@ -24,6 +24,7 @@ pub mod experimental {
     }
 }
 
+mod animated_color;
 mod atmosphere;
 mod cluster;
 mod components;
@@ -47,6 +48,7 @@ mod volumetric_fog;
 
 use bevy_color::{Color, LinearRgba};
 
+pub use animated_color::*;
 pub use atmosphere::*;
 pub use cluster::*;
 pub use components::*;
@@ -261,6 +263,7 @@ impl Plugin for PbrPlugin {
             ))
             .add_plugins((
                 decal::ForwardDecalPlugin,
+                AnimatedColorPlugin,
                 SyncComponentPlugin::<DirectionalLight>::default(),
                 SyncComponentPlugin::<PointLight>::default(),
                 SyncComponentPlugin::<SpotLight>::default(),
@@ -13,8 +13,8 @@ use bevy_render::{
     mesh::Mesh3d,
     primitives::{Aabb, CascadesFrusta, CubemapFrusta, Frustum, Sphere},
     view::{
-        InheritedVisibility, NoFrustumCulling, PreviousVisibleEntities, RenderLayers,
-        ViewVisibility, VisibilityClass, VisibilityRange, VisibleEntityRanges,
+        InheritedVisibility, NoFrustumCulling, NoFrustumCullingFor, PreviousVisibleEntities,
+        RenderLayers, ViewVisibility, VisibilityClass, VisibilityRange, VisibleEntityRanges,
     },
 };
 use bevy_transform::components::{GlobalTransform, Transform};
@@ -691,6 +691,17 @@ fn shrink_entities(visible_entities: &mut Vec<Entity>) {
     visible_entities.shrink_to(reserved);
 }
 
+/// Returns `true` if frustum culling is disabled for [`Mesh3d`] entities with
+/// the given [`NoFrustumCulling`] and [`NoFrustumCullingFor`] components.
+pub(crate) fn mesh_has_no_frustum_culling(
+    (has_no_frustum_culling, maybe_no_frustum_culling_for): (bool, Option<&NoFrustumCullingFor>),
+) -> bool {
+    has_no_frustum_culling
+        || maybe_no_frustum_culling_for.is_some_and(|no_frustum_culling_for| {
+            no_frustum_culling_for.applies_to(core::any::TypeId::of::<Mesh3d>())
+        })
+}
+
 pub fn check_dir_light_mesh_visibility(
     mut commands: Commands,
     mut directional_lights: Query<
@@ -711,7 +722,7 @@ pub fn check_dir_light_mesh_visibility(
             Option<&Aabb>,
             Option<&GlobalTransform>,
             Has<VisibilityRange>,
-            Has<NoFrustumCulling>,
+            (Has<NoFrustumCulling>, Option<&NoFrustumCullingFor>),
         ),
         (
             Without<NotShadowCaster>,
@@ -771,8 +782,11 @@ pub fn check_dir_light_mesh_visibility(
                     maybe_aabb,
                     maybe_transform,
                     has_visibility_range,
-                    has_no_frustum_culling,
+                    maybe_no_frustum_culling,
                 )| {
+                    let has_no_frustum_culling =
+                        mesh_has_no_frustum_culling(maybe_no_frustum_culling);
+
                     if !inherited_visibility.get() {
                         return;
                     }
@@ -890,7 +904,7 @@ pub fn check_point_light_mesh_visibility(
             Option<&Aabb>,
             Option<&GlobalTransform>,
             Has<VisibilityRange>,
-            Has<NoFrustumCulling>,
+            (Has<NoFrustumCulling>, Option<&NoFrustumCullingFor>),
         ),
         (
             Without<NotShadowCaster>,
@@ -948,8 +962,11 @@ pub fn check_point_light_mesh_visibility(
                         maybe_aabb,
                         maybe_transform,
                         has_visibility_range,
-                        has_no_frustum_culling,
+                        maybe_no_frustum_culling,
                     )| {
+                        let has_no_frustum_culling =
+                            mesh_has_no_frustum_culling(maybe_no_frustum_culling);
+
                         if !inherited_visibility.get() {
                             return;
                         }
@@ -1047,8 +1064,11 @@ pub fn check_point_light_mesh_visibility(
                         maybe_aabb,
                         maybe_transform,
                         has_visibility_range,
-                        has_no_frustum_culling,
+                        maybe_no_frustum_culling,
                     )| {
+                        let has_no_frustum_culling =
+                            mesh_has_no_frustum_culling(maybe_no_frustum_culling);
+
                         if !inherited_visibility.get() {
                             return;
                         }
@@ -39,8 +39,8 @@ use bevy_render::{
     sync_world::MainEntityHashSet,
     texture::{DefaultImageSampler, GpuImage},
     view::{
-        self, NoFrustumCulling, NoIndirectDrawing, RenderVisibilityRanges, RetainedViewEntity,
-        ViewTarget, ViewUniformOffset, ViewVisibility, VisibilityRange,
+        self, NoFrustumCulling, NoFrustumCullingFor, NoIndirectDrawing, RenderVisibilityRanges,
+        RetainedViewEntity, ViewTarget, ViewUniformOffset, ViewVisibility, VisibilityRange,
     },
     Extract,
 };
@@ -1341,7 +1341,7 @@ pub fn extract_meshes_for_cpu_building(
             Option<&PreviousGlobalTransform>,
             &Mesh3d,
             Option<&MeshTag>,
-            Has<NoFrustumCulling>,
+            (Has<NoFrustumCulling>, Option<&NoFrustumCullingFor>),
             Has<NotShadowReceiver>,
             Has<TransmittedShadowReceiver>,
             Has<NotShadowCaster>,
@@ -1379,7 +1379,7 @@ pub fn extract_meshes_for_cpu_building(
             let mesh_flags = MeshFlags::from_components(
                 transform,
                 lod_index,
-                no_frustum_culling,
+                mesh_has_no_frustum_culling(no_frustum_culling),
                 not_shadow_receiver,
                 transmitted_receiver,
             );
@@ -1445,7 +1445,7 @@ type GpuMeshExtractionQuery = (
     Option<Read<Aabb>>,
     Read<Mesh3d>,
     Option<Read<MeshTag>>,
-    Has<NoFrustumCulling>,
+    (Has<NoFrustumCulling>, Option<Read<NoFrustumCullingFor>>),
     Has<NotShadowReceiver>,
     Has<TransmittedShadowReceiver>,
     Has<NotShadowCaster>,
@@ -1476,6 +1476,7 @@ pub fn extract_meshes_for_gpu_building(
                 Changed<Aabb>,
                 Changed<Mesh3d>,
                 Changed<NoFrustumCulling>,
+                Changed<NoFrustumCullingFor>,
                 Changed<NotShadowReceiver>,
                 Changed<TransmittedShadowReceiver>,
                 Changed<NotShadowCaster>,
@@ -1595,7 +1596,7 @@ fn extract_mesh_for_gpu_building(
     let mesh_flags = MeshFlags::from_components(
         transform,
         lod_index,
-        no_frustum_culling,
+        mesh_has_no_frustum_culling(no_frustum_culling),
         not_shadow_receiver,
         transmitted_receiver,
     );
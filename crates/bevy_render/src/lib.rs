@@ -62,8 +62,8 @@ pub mod prelude {
             Projection,
         },
         mesh::{
-            morph::MorphWeights, primitives::MeshBuilder, primitives::Meshable, Mesh, Mesh2d,
-            Mesh3d,
+            morph::MorphWeights, primitives::MeshBuilder, primitives::Meshable,
+            primitives::TryFromMesh, Mesh, Mesh2d, Mesh3d,
         },
         render_resource::Shader,
         texture::ImagePlugin,
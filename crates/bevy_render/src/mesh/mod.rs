@@ -115,20 +115,24 @@ pub fn inherit_weights(
 pub trait MeshAabb {
     /// Compute the Axis-Aligned Bounding Box of the mesh vertices in model space
     ///
-    /// Returns `None` if `self` doesn't have [`Mesh::ATTRIBUTE_POSITION`] of
-    /// type [`VertexAttributeValues::Float32x3`], or if `self` doesn't have any vertices.
+    /// Positions that aren't stored as [`VertexAttributeValues::Float32x3`], such
+    /// as quantized positions, are converted with [`VertexAttributeValues::to_vec3s`].
+    ///
+    /// Returns `None` if `self` doesn't have [`Mesh::ATTRIBUTE_POSITION`] in a
+    /// format with at least 3 components, or if `self` doesn't have any vertices.
     fn compute_aabb(&self) -> Option<Aabb>;
 }
 
 impl MeshAabb for Mesh {
     fn compute_aabb(&self) -> Option<Aabb> {
-        let Some(VertexAttributeValues::Float32x3(values)) =
-            self.attribute(Mesh::ATTRIBUTE_POSITION)
-        else {
-            return None;
-        };
-
-        Aabb::enclosing(values.iter().map(|p| Vec3::from_slice(p)))
+        match self.attribute(Mesh::ATTRIBUTE_POSITION)? {
+            VertexAttributeValues::Float32x3(values) => {
+                Aabb::enclosing(values.iter().map(|p| Vec3::from_slice(p)))
+            }
+            // Positions stored with a custom attribute that shares the id of
+            // `Mesh::ATTRIBUTE_POSITION`, such as quantized positions.
+            values => Aabb::enclosing(values.to_vec3s()?),
+        }
     }
 }
 
@@ -246,3 +250,38 @@ impl RenderAsset for RenderMesh {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Vec3A;
+    use wgpu::{PrimitiveTopology, VertexFormat};
+
+    #[test]
+    fn compute_aabb_of_quantized_positions() {
+        // Quantized positions, stored under the id of `Mesh::ATTRIBUTE_POSITION`.
+        const ATTRIBUTE_QUANTIZED_POSITION: MeshVertexAttribute =
+            MeshVertexAttribute::new("Vertex_Position", 0, VertexFormat::Snorm16x4);
+
+        let mut mesh = Mesh::new(PrimitiveTopology::PointList, RenderAssetUsages::default());
+        mesh.insert_attribute(
+            ATTRIBUTE_QUANTIZED_POSITION,
+            VertexAttributeValues::Snorm16x4(vec![
+                [i16::MIN, 0, i16::MAX / 2, 0],
+                [i16::MAX, i16::MAX, 0, 0],
+            ]),
+        );
+        let aabb = mesh.compute_aabb().unwrap();
+        assert!(aabb.min().abs_diff_eq(Vec3A::new(-1.0, 0.0, 0.0), 1e-4));
+        assert!(aabb.max().abs_diff_eq(Vec3A::new(1.0, 1.0, 0.5), 1e-4));
+
+        // A mesh without vertices has no bounding box, whatever the format.
+        mesh.insert_attribute(
+            ATTRIBUTE_QUANTIZED_POSITION,
+            VertexAttributeValues::Snorm16x4(Vec::new()),
+        );
+        assert!(mesh.compute_aabb().is_none());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<[f32; 3]>::new());
+        assert!(mesh.compute_aabb().is_none());
+    }
+}
@@ -1,8 +1,17 @@
 use crate::define_atomic_id;
-use crate::renderer::WgpuWrapper;
+use crate::gpu_readback::align_byte_size;
+use crate::render_resource::{Buffer, BufferUsages};
+use crate::renderer::{RenderDevice, RenderQueue, WgpuWrapper};
+use alloc::sync::Arc;
+use async_channel::Receiver;
+use bevy_asset::RenderAssetUsages;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::resource::Resource;
+use bevy_image::Image;
+use bevy_platform::sync::OnceLock;
 use core::ops::Deref;
+use thiserror::Error;
+use wgpu::BufferAsyncError;
 
 define_atomic_id!(TextureId);
 
@@ -23,19 +32,234 @@ define_atomic_id!(TextureId);
 pub struct Texture {
     id: TextureId,
     value: WgpuWrapper<wgpu::Texture>,
+    descriptor: Option<Arc<OwnedTextureDescriptor>>,
+    default_view: OnceLock<TextureView>,
+}
+
+/// A copy of the [`wgpu::TextureDescriptor`] a [`Texture`] was created with, owning the data
+/// the descriptor borrows.
+#[derive(Debug)]
+struct OwnedTextureDescriptor {
+    /// The descriptor, with its label and view formats left empty.
+    descriptor: wgpu::TextureDescriptor<'static>,
+    label: Option<String>,
+    view_formats: Vec<wgpu::TextureFormat>,
 }
 
 impl Texture {
+    /// Creates a [`Texture`] that remembers the descriptor it was created with.
+    pub(crate) fn with_descriptor(value: wgpu::Texture, desc: &wgpu::TextureDescriptor) -> Self {
+        let descriptor = OwnedTextureDescriptor {
+            descriptor: wgpu::TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            label: desc.label.map(String::from),
+            view_formats: desc.view_formats.to_vec(),
+        };
+        Texture {
+            descriptor: Some(Arc::new(descriptor)),
+            ..Texture::from(value)
+        }
+    }
+
     /// Returns the [`TextureId`].
     #[inline]
     pub fn id(&self) -> TextureId {
         self.id
     }
 
+    /// Returns the descriptor this texture was created with.
+    ///
+    /// This is only available for textures created through
+    /// [`RenderDevice::create_texture`](crate::renderer::RenderDevice::create_texture) or
+    /// [`RenderDevice::create_texture_with_data`](crate::renderer::RenderDevice::create_texture_with_data),
+    /// and is `None` for textures converted from a wgpu [`Texture`](wgpu::Texture).
+    pub fn descriptor(&self) -> Option<wgpu::TextureDescriptor<'_>> {
+        self.descriptor
+            .as_deref()
+            .map(|owned| wgpu::TextureDescriptor {
+                label: owned.label.as_deref(),
+                view_formats: &owned.view_formats,
+                ..owned.descriptor.clone()
+            })
+    }
+
+    /// Returns the size of this texture.
+    #[inline]
+    pub fn size(&self) -> wgpu::Extent3d {
+        self.value.size()
+    }
+
     /// Creates a view of this texture.
     pub fn create_view(&self, desc: &wgpu::TextureViewDescriptor) -> TextureView {
         TextureView::from(self.value.create_view(desc))
     }
+
+    /// Creates a view of this texture with the default [`TextureViewDescriptor`](wgpu::TextureViewDescriptor).
+    pub fn create_default_view(&self) -> TextureView {
+        self.create_view(&wgpu::TextureViewDescriptor::default())
+    }
+
+    /// Returns a view of this texture with the default [`TextureViewDescriptor`](wgpu::TextureViewDescriptor),
+    /// creating it on the first call.
+    ///
+    /// Later calls return the same view, with the same [`TextureViewId`], instead of creating a
+    /// new one every time. Clones of this texture keep the view created so far, since they refer to
+    /// the same wgpu [`Texture`](wgpu::Texture), while a [`Texture`] created from another wgpu
+    /// texture starts without one.
+    pub fn create_view_cached(&self) -> &TextureView {
+        self.default_view.get_or_init(|| self.create_default_view())
+    }
+
+    /// Reads the first mip level of this texture back to the CPU as an [`Image`].
+    ///
+    /// The texture must have been created with [`TextureUsages::COPY_SRC`](wgpu::TextureUsages::COPY_SRC)
+    /// and have an uncompressed format with a single aspect. All of its layers are read.
+    ///
+    /// The copy is submitted to `render_queue` right away, but the returned future only completes
+    /// once the device has been polled, which the renderer does every frame. Outside of the
+    /// renderer, call [`RenderDevice::poll`] before awaiting it.
+    pub fn read_to_image(
+        &self,
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+    ) -> impl Future<Output = Result<Image, ReadbackError>> + use<> {
+        let size = self.size();
+        let dimension = self.dimension();
+        let format = self.format();
+        let pending = self.copy_to_readback_buffer(render_device, render_queue);
+
+        async move {
+            let pending = pending?;
+            pending
+                .mapped
+                .recv()
+                .await
+                .or(Err(ReadbackError::Canceled))??;
+
+            let mapped = pending.buffer.slice(..).get_mapped_range();
+            // Rows in the buffer are padded to `wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`, which the
+            // image doesn't expect.
+            let data = mapped
+                .chunks_exact(pending.padded_row_bytes)
+                .flat_map(|row| &row[..pending.row_bytes])
+                .copied()
+                .collect();
+            drop(mapped);
+            pending.buffer.unmap();
+
+            Ok(Image::new(
+                size,
+                dimension,
+                data,
+                format,
+                RenderAssetUsages::default(),
+            ))
+        }
+    }
+
+    /// Submits a copy of the first mip level of this texture to a new buffer, and starts mapping it.
+    fn copy_to_readback_buffer(
+        &self,
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+    ) -> Result<PendingReadback, ReadbackError> {
+        if !self.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            return Err(ReadbackError::MissingCopySrc);
+        }
+        let format = self.format();
+        let ((1, 1), Some(pixel_size)) = (format.block_dimensions(), format.block_copy_size(None))
+        else {
+            return Err(ReadbackError::UnsupportedFormat(format));
+        };
+
+        let size = self.size();
+        let row_bytes = size.width * pixel_size;
+        let padded_row_bytes = align_byte_size(row_bytes);
+        let buffer = render_device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("texture_readback_buffer"),
+            size: u64::from(padded_row_bytes)
+                * u64::from(size.height)
+                * u64::from(size.depth_or_array_layers),
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = render_device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("texture_readback"),
+        });
+        encoder.copy_texture_to_buffer(
+            self.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(size.height),
+                },
+            },
+            size,
+        );
+        render_queue.submit([encoder.finish()]);
+
+        let (tx, rx) = async_channel::bounded(1);
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                // The receiver is gone if the future was dropped, in which case nobody cares.
+                let _ = tx.try_send(result);
+            });
+
+        Ok(PendingReadback {
+            buffer,
+            mapped: rx,
+            row_bytes: row_bytes as usize,
+            padded_row_bytes: padded_row_bytes as usize,
+        })
+    }
+}
+
+/// A copy of a [`Texture`] to a buffer that is being mapped for [`Texture::read_to_image`].
+struct PendingReadback {
+    buffer: Buffer,
+    mapped: Receiver<Result<(), BufferAsyncError>>,
+    /// The number of bytes of each row of the texture.
+    row_bytes: usize,
+    /// The number of bytes of each row in `buffer`, including padding.
+    padded_row_bytes: usize,
+}
+
+/// An error that occurs when reading a [`Texture`] back with [`Texture::read_to_image`].
+#[derive(Error, Debug)]
+pub enum ReadbackError {
+    /// The texture wasn't created with [`TextureUsages::COPY_SRC`](wgpu::TextureUsages::COPY_SRC).
+    #[error("the texture wasn't created with `TextureUsages::COPY_SRC`")]
+    MissingCopySrc,
+    /// The format of the texture is compressed or has several aspects.
+    #[error("reading back textures with format {0:?} isn't supported")]
+    UnsupportedFormat(wgpu::TextureFormat),
+    /// The buffer the texture was copied to couldn't be mapped.
+    #[error("failed to map the readback buffer: {0}")]
+    Map(#[from] BufferAsyncError),
+    /// The device dropped the readback before mapping the buffer, for example because it was lost.
+    #[error("the readback was canceled before the buffer was mapped")]
+    Canceled,
+}
+
+impl PartialEq for Texture {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Texture {}
+
+impl core::hash::Hash for Texture {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.0.hash(state);
+    }
 }
 
 impl From<wgpu::Texture> for Texture {
@@ -43,6 +267,8 @@ impl From<wgpu::Texture> for Texture {
         Texture {
             id: TextureId::new(),
             value: WgpuWrapper::new(value),
+            descriptor: None,
+            default_view: OnceLock::new(),
         }
     }
 }
@@ -83,6 +309,20 @@ impl TextureView {
     }
 }
 
+impl PartialEq for TextureView {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for TextureView {}
+
+impl core::hash::Hash for TextureView {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.0.hash(state);
+    }
+}
+
 impl From<wgpu::TextureView> for TextureView {
     fn from(value: wgpu::TextureView) -> Self {
         TextureView {
@@ -129,14 +369,69 @@ define_atomic_id!(SamplerId);
 pub struct Sampler {
     id: SamplerId,
     value: WgpuWrapper<wgpu::Sampler>,
+    descriptor: Option<Arc<OwnedSamplerDescriptor>>,
+}
+
+/// A copy of the [`wgpu::SamplerDescriptor`] a [`Sampler`] was created with, owning its label.
+#[derive(Debug)]
+struct OwnedSamplerDescriptor {
+    /// The descriptor, with its label left empty.
+    descriptor: wgpu::SamplerDescriptor<'static>,
+    label: Option<String>,
 }
 
 impl Sampler {
+    /// Creates a [`Sampler`] that remembers the descriptor it was created with.
+    pub(crate) fn with_descriptor(value: wgpu::Sampler, desc: &wgpu::SamplerDescriptor) -> Self {
+        let descriptor = OwnedSamplerDescriptor {
+            descriptor: wgpu::SamplerDescriptor {
+                label: None,
+                ..desc.clone()
+            },
+            label: desc.label.map(String::from),
+        };
+        Sampler {
+            descriptor: Some(Arc::new(descriptor)),
+            ..Sampler::from(value)
+        }
+    }
+
     /// Returns the [`SamplerId`].
     #[inline]
     pub fn id(&self) -> SamplerId {
         self.id
     }
+
+    /// Returns the descriptor this sampler was created with.
+    ///
+    /// This is only available for samplers created through
+    /// [`RenderDevice::create_sampler`](crate::renderer::RenderDevice::create_sampler), and is
+    /// `None` for samplers converted from a wgpu [`Sampler`](wgpu::Sampler).
+    ///
+    /// Unlike their [`SamplerId`]s, the descriptors of two samplers can be compared to find out
+    /// whether they sample in the same way.
+    pub fn descriptor(&self) -> Option<wgpu::SamplerDescriptor<'_>> {
+        self.descriptor
+            .as_deref()
+            .map(|owned| wgpu::SamplerDescriptor {
+                label: owned.label.as_deref(),
+                ..owned.descriptor.clone()
+            })
+    }
+}
+
+impl PartialEq for Sampler {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Sampler {}
+
+impl core::hash::Hash for Sampler {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.0.hash(state);
+    }
 }
 
 impl From<wgpu::Sampler> for Sampler {
@@ -144,6 +439,7 @@ impl From<wgpu::Sampler> for Sampler {
         Sampler {
             id: SamplerId::new(),
             value: WgpuWrapper::new(value),
+            descriptor: None,
         }
     }
 }
@@ -164,3 +460,240 @@ impl Deref for Sampler {
 /// image sampler.
 #[derive(Resource, Debug, Clone, Deref, DerefMut)]
 pub struct DefaultImageSampler(pub(crate) Sampler);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_platform::collections::HashSet;
+    use wgpu::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+
+    /// Returns a [`RenderDevice`] and its [`RenderQueue`], or `None` if no adapter is available to
+    /// create them.
+    fn render_device_and_queue() -> Option<(RenderDevice, RenderQueue)> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        futures_lite::future::block_on(async {
+            let adapter = instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    force_fallback_adapter: false,
+                    ..Default::default()
+                })
+                .await?;
+            let (device, queue) = adapter
+                .request_device(&wgpu::DeviceDescriptor::default(), None)
+                .await
+                .ok()?;
+            Some((
+                RenderDevice::from(device),
+                RenderQueue(Arc::new(WgpuWrapper::new(queue))),
+            ))
+        })
+    }
+
+    /// Returns a [`RenderDevice`], or `None` if no adapter is available to create one.
+    fn render_device() -> Option<RenderDevice> {
+        render_device_and_queue().map(|(render_device, _)| render_device)
+    }
+
+    #[test]
+    fn texture_descriptor() {
+        let Some(render_device) = render_device() else {
+            return;
+        };
+
+        let size = Extent3d {
+            width: 64,
+            height: 32,
+            depth_or_array_layers: 1,
+        };
+        let texture = render_device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("texture_descriptor"),
+            size,
+            mip_level_count: 3,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+
+        assert_eq!(texture.size(), size);
+        let descriptor = texture.descriptor().unwrap();
+        assert_eq!(descriptor.label, Some("texture_descriptor"));
+        assert_eq!(descriptor.size, size);
+        assert_eq!(descriptor.mip_level_count, 3);
+        assert_eq!(descriptor.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(descriptor.usage, TextureUsages::TEXTURE_BINDING);
+
+        // Clones share the descriptor.
+        assert_eq!(texture.clone().descriptor(), Some(descriptor));
+
+        // Textures converted from wgpu don't know their descriptor.
+        let texture = Texture::from(render_device.wgpu_device().create_texture(
+            &wgpu::TextureDescriptor {
+                label: None,
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        ));
+        assert_eq!(texture.size(), size);
+        assert!(texture.descriptor().is_none());
+    }
+
+    #[test]
+    fn cached_default_view() {
+        let Some(render_device) = render_device() else {
+            return;
+        };
+
+        let texture = render_device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+
+        let view = texture.create_view_cached().id();
+        assert_eq!(texture.create_view_cached().id(), view);
+        assert_eq!(texture.clone().create_view_cached().id(), view);
+        assert_ne!(texture.create_default_view().id(), view);
+
+        // A texture converted from the same wgpu texture doesn't share the cache.
+        let converted = Texture::from((*texture).clone());
+        assert_ne!(converted.create_view_cached().id(), view);
+    }
+
+    #[test]
+    fn sampler_descriptor() {
+        let Some(render_device) = render_device() else {
+            return;
+        };
+
+        let descriptor = wgpu::SamplerDescriptor {
+            label: Some("sampler_descriptor"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        };
+        let a = render_device.create_sampler(&descriptor);
+        let b = render_device.create_sampler(&descriptor);
+        assert_ne!(a.id(), b.id());
+        assert_eq!(a.descriptor(), Some(descriptor.clone()));
+        assert_eq!(a.descriptor(), b.descriptor());
+
+        let c = render_device.create_sampler(&wgpu::SamplerDescriptor {
+            mag_filter: wgpu::FilterMode::Nearest,
+            ..descriptor
+        });
+        assert_ne!(a.descriptor(), c.descriptor());
+    }
+
+    #[test]
+    fn equality_by_id() {
+        let Some(render_device) = render_device() else {
+            return;
+        };
+
+        let descriptor = wgpu::TextureDescriptor {
+            label: None,
+            size: Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+        let texture = render_device.create_texture(&descriptor);
+        let other_texture = render_device.create_texture(&descriptor);
+        assert_eq!(texture.clone(), texture);
+        assert_ne!(texture, other_texture);
+        // The same wgpu texture converted twice gets two ids.
+        assert_ne!(Texture::from((*texture).clone()), texture);
+
+        let view = texture.create_default_view();
+        assert_eq!(view.clone(), view);
+        assert_ne!(texture.create_default_view(), view);
+
+        let sampler = render_device.create_sampler(&wgpu::SamplerDescriptor::default());
+        let other_sampler = render_device.create_sampler(&wgpu::SamplerDescriptor::default());
+        assert_eq!(sampler.clone(), sampler);
+        assert_ne!(sampler, other_sampler);
+
+        let textures: HashSet<Texture> = [texture.clone(), texture.clone(), other_texture].into();
+        assert_eq!(textures.len(), 2);
+        assert!(textures.contains(&texture));
+        let views: HashSet<TextureView> = [view.clone(), view.clone()].into();
+        assert_eq!(views.len(), 1);
+        let samplers: HashSet<Sampler> = [sampler.clone(), other_sampler, sampler].into();
+        assert_eq!(samplers.len(), 2);
+    }
+
+    #[test]
+    fn read_to_image() {
+        let Some((render_device, render_queue)) = render_device_and_queue() else {
+            return;
+        };
+
+        // 3 pixels of 4 bytes don't fill the 256 bytes a row is padded to when copied.
+        let size = Extent3d {
+            width: 3,
+            height: 2,
+            depth_or_array_layers: 1,
+        };
+        let descriptor = wgpu::TextureDescriptor {
+            label: None,
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::COPY_SRC | TextureUsages::COPY_DST,
+            view_formats: &[],
+        };
+        let texture = render_device.create_texture(&descriptor);
+        let pixels: Vec<u8> = (0..24).collect();
+        render_queue.write_texture(
+            texture.as_image_copy(),
+            &pixels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(12),
+                rows_per_image: None,
+            },
+            size,
+        );
+
+        let readback = texture.read_to_image(&render_device, &render_queue);
+        render_device.poll(wgpu::Maintain::Wait);
+        let image = futures_lite::future::block_on(readback).unwrap();
+        assert_eq!(image.texture_descriptor.size, size);
+        assert_eq!(image.texture_descriptor.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(image.data, Some(pixels));
+
+        let texture = render_device.create_texture(&wgpu::TextureDescriptor {
+            usage: TextureUsages::TEXTURE_BINDING,
+            ..descriptor
+        });
+        assert!(matches!(
+            futures_lite::future::block_on(texture.read_to_image(&render_device, &render_queue)),
+            Err(ReadbackError::MissingCopySrc)
+        ));
+    }
+}
@@ -224,7 +224,7 @@ impl RenderDevice {
         let wgpu_texture =
             self.device
                 .create_texture_with_data(render_queue.as_ref(), desc, order, data);
-        Texture::from(wgpu_texture)
+        Texture::with_descriptor(wgpu_texture, desc)
     }
 
     /// Creates a new [`Texture`].
@@ -232,7 +232,7 @@ impl RenderDevice {
     /// `desc` specifies the general format of the texture.
     pub fn create_texture(&self, desc: &wgpu::TextureDescriptor) -> Texture {
         let wgpu_texture = self.device.create_texture(desc);
-        Texture::from(wgpu_texture)
+        Texture::with_descriptor(wgpu_texture, desc)
     }
 
     /// Creates a new [`Sampler`].
@@ -240,7 +240,7 @@ impl RenderDevice {
     /// `desc` specifies the behavior of the sampler.
     pub fn create_sampler(&self, desc: &wgpu::SamplerDescriptor) -> Sampler {
         let wgpu_sampler = self.device.create_sampler(desc);
-        Sampler::from(wgpu_sampler)
+        Sampler::with_descriptor(wgpu_sampler, desc)
     }
 
     /// Initializes [`Surface`](wgpu::Surface) for presentation.
@@ -2,6 +2,7 @@ mod fallback_image;
 mod gpu_image;
 mod texture_attachment;
 mod texture_cache;
+mod uv_debug_image;
 
 pub use crate::render_resource::DefaultImageSampler;
 #[cfg(feature = "basis-universal")]
@@ -13,6 +14,7 @@ pub use fallback_image::*;
 pub use gpu_image::*;
 pub use texture_attachment::*;
 pub use texture_cache::*;
+pub use uv_debug_image::*;
 
 use crate::{
     render_asset::RenderAssetPlugin, renderer::RenderDevice, Render, RenderApp, RenderSystems,
@@ -4,7 +4,7 @@ use crate::{
 };
 use bevy_ecs::{prelude::ResMut, resource::Resource};
 use bevy_platform::collections::{hash_map::Entry, HashMap};
-use wgpu::{TextureDescriptor, TextureViewDescriptor};
+use wgpu::TextureDescriptor;
 
 /// The internal representation of a [`CachedTexture`] used to track whether it was recently used
 /// and is currently taken.
@@ -54,7 +54,7 @@ impl TextureCache {
                 }
 
                 let texture = render_device.create_texture(&entry.key().clone());
-                let default_view = texture.create_view(&TextureViewDescriptor::default());
+                let default_view = texture.create_default_view();
                 entry.get_mut().push(CachedTextureMeta {
                     texture: texture.clone(),
                     default_view: default_view.clone(),
@@ -68,7 +68,7 @@ impl TextureCache {
             }
             Entry::Vacant(entry) => {
                 let texture = render_device.create_texture(entry.key());
-                let default_view = texture.create_view(&TextureViewDescriptor::default());
+                let default_view = texture.create_default_view();
                 entry.insert(vec![CachedTextureMeta {
                     texture: texture.clone(),
                     default_view: default_view.clone(),
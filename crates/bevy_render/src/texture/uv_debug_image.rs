@@ -0,0 +1,93 @@
+use crate::render_asset::RenderAssetUsages;
+use bevy_image::Image;
+use bevy_math::UVec2;
+use wgpu::{Extent3d, TextureDimension, TextureFormat};
+
+/// The colors of the cells of [`uv_debug_image`], in sRGB.
+const UV_DEBUG_PALETTE: [[u8; 4]; 8] = [
+    [255, 102, 159, 255],
+    [255, 159, 102, 255],
+    [236, 255, 102, 255],
+    [121, 255, 102, 255],
+    [102, 255, 198, 255],
+    [102, 198, 255, 255],
+    [121, 102, 255, 255],
+    [236, 102, 255, 255],
+];
+
+/// Creates a colorful test pattern of the given size, which makes it easy to see how UVs are laid
+/// out on a mesh.
+///
+/// The image is split into 8 by 8 cells. Each row has the same 8 colors, shifted one cell to the
+/// right compared to the row above, so that diagonal stripes run across the image. Cells are
+/// stretched to cover the whole image, so the pattern looks the same at any size, with sharper
+/// edges at higher resolutions or with nearest-neighbor sampling.
+///
+/// The image has the [`TextureFormat::Rgba8UnormSrgb`] format, and is kept in both the main and
+/// the render world.
+///
+/// # Panics
+///
+/// Panics if `size` is zero along either axis.
+pub fn uv_debug_image(size: UVec2) -> Image {
+    assert!(
+        size.x > 0 && size.y > 0,
+        "the size of a UV debug image must not be zero"
+    );
+
+    let cells = UV_DEBUG_PALETTE.len() as u64;
+    let mut data = Vec::with_capacity(size.x as usize * size.y as usize * 4);
+    for y in 0..size.y {
+        let row = y as u64 * cells / size.y as u64;
+        for x in 0..size.x {
+            let column = x as u64 * cells / size.x as u64;
+            let color = (column + cells - row) % cells;
+            data.extend_from_slice(&UV_DEBUG_PALETTE[color as usize]);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uv_debug_image_size_and_format() {
+        for size in [UVec2::splat(8), UVec2::new(100, 37), UVec2::ONE] {
+            let image = uv_debug_image(size);
+            assert_eq!(image.size(), size);
+            assert_eq!(
+                image.texture_descriptor.format,
+                TextureFormat::Rgba8UnormSrgb
+            );
+            assert_eq!(
+                image.data.as_ref().unwrap().len(),
+                size.x as usize * size.y as usize * 4
+            );
+        }
+
+        // Each row is the one above shifted one cell to the right.
+        let image = uv_debug_image(UVec2::splat(16));
+        let pixel = |x: u32, y: u32| {
+            let start = (y * 16 + x) as usize * 4;
+            &image.data.as_ref().unwrap()[start..start + 4]
+        };
+        assert_eq!(pixel(0, 0), UV_DEBUG_PALETTE[0]);
+        assert_eq!(pixel(1, 1), UV_DEBUG_PALETTE[0]);
+        assert_eq!(pixel(2, 0), UV_DEBUG_PALETTE[1]);
+        assert_eq!(pixel(0, 2), UV_DEBUG_PALETTE[7]);
+        assert_eq!(pixel(15, 15), UV_DEBUG_PALETTE[0]);
+    }
+}
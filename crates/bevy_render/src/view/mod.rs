@@ -107,6 +107,7 @@ impl Plugin for ViewPlugin {
             .register_type::<ViewVisibility>()
             .register_type::<Msaa>()
             .register_type::<NoFrustumCulling>()
+            .register_type::<NoFrustumCullingFor>()
             .register_type::<RenderLayers>()
             .register_type::<Visibility>()
             .register_type::<VisibleEntities>()
@@ -118,6 +119,7 @@ impl Plugin for ViewPlugin {
                 ExtractComponentPlugin::<OcclusionCulling>::default(),
                 VisibilityPlugin,
                 VisibilityRangePlugin,
+                VisibilityFadePlugin,
             ));
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
@@ -0,0 +1,146 @@
+//! Fading entities in when they become visible, instead of having them pop into
+//! view.
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    change_detection::DetectChangesMut as _,
+    component::Component,
+    reflect::ReflectComponent,
+    schedule::IntoScheduleConfigs as _,
+    system::{Query, Res},
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render_macros::ExtractComponent;
+use bevy_time::Time;
+
+use super::{ViewVisibility, VisibilitySystems};
+use crate::extract_component::ExtractComponentPlugin;
+
+/// A plugin that enables [`VisibilityFade`], which fades entities in as they
+/// become visible.
+pub struct VisibilityFadePlugin;
+
+impl Plugin for VisibilityFadePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<VisibilityFade>()
+            .register_type::<VisibilityFadeAlpha>()
+            .add_plugins(ExtractComponentPlugin::<VisibilityFadeAlpha>::default())
+            .add_systems(
+                PostUpdate,
+                update_visibility_fades.after(VisibilitySystems::MarkNewlyHiddenEntitiesInvisible),
+            );
+    }
+}
+
+/// Fades an entity in over `duration` seconds whenever its [`ViewVisibility`]
+/// goes from hidden to visible.
+///
+/// The opacity of the fade is stored in the entity's [`VisibilityFadeAlpha`],
+/// which is extracted to the render world for materials and shaders to use.
+/// Entities stop being rendered as soon as they're hidden, so there's no
+/// matching fade-out: the opacity drops back to `0.0` right away, ready for the
+/// next time the entity becomes visible.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq, Clone)]
+#[require(VisibilityFadeAlpha)]
+pub struct VisibilityFade {
+    /// How many seconds it takes the entity to fade in completely.
+    ///
+    /// If this is zero or negative, the entity appears at full opacity right
+    /// away.
+    pub duration: f32,
+}
+
+/// The opacity of an entity with a [`VisibilityFade`], from `0.0` when it's
+/// hidden or just became visible, to `1.0` once it has been visible for the
+/// whole duration of the fade.
+///
+/// This is updated by [`update_visibility_fades`] each frame, after
+/// [`ViewVisibility`] has been computed.
+#[derive(Component, ExtractComponent, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct VisibilityFadeAlpha {
+    alpha: f32,
+    /// Whether the entity was visible in the previous frame.
+    was_visible: bool,
+}
+
+impl VisibilityFadeAlpha {
+    /// Returns the opacity of the entity, between `0.0` and `1.0`.
+    #[inline]
+    pub fn get(self) -> f32 {
+        self.alpha
+    }
+}
+
+/// Updates the [`VisibilityFadeAlpha`] of entities with a [`VisibilityFade`]
+/// from their [`ViewVisibility`] in this frame and the previous one.
+///
+/// This system runs in [`PostUpdate`], after
+/// [`VisibilitySystems::MarkNewlyHiddenEntitiesInvisible`].
+pub fn update_visibility_fades(
+    time: Res<Time>,
+    mut fades: Query<(&VisibilityFade, &ViewVisibility, &mut VisibilityFadeAlpha)>,
+) {
+    let delta = time.delta_secs();
+    for (fade, view_visibility, mut fade_alpha) in &mut fades {
+        let visible = view_visibility.get();
+        let alpha = if !visible {
+            0.0
+        } else if fade.duration <= 0.0 {
+            1.0
+        } else if !fade_alpha.was_visible {
+            // The fade starts on the frame the entity becomes visible.
+            0.0
+        } else {
+            (fade_alpha.alpha + delta / fade.duration).min(1.0)
+        };
+        fade_alpha.set_if_neq(VisibilityFadeAlpha {
+            alpha,
+            was_visible: visible,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{system::RunSystemOnce as _, world::World};
+    use core::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn fade_in_ramps_over_duration() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let entity = world
+            .spawn((VisibilityFade { duration: 1.0 }, ViewVisibility::HIDDEN))
+            .id();
+
+        let mut alpha_after = |seconds: f32, visible: bool| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(seconds));
+            let mut view_visibility = world.get_mut::<ViewVisibility>(entity).unwrap();
+            *view_visibility = ViewVisibility::HIDDEN;
+            if visible {
+                view_visibility.set();
+            }
+            world.run_system_once(update_visibility_fades).unwrap();
+            world.get::<VisibilityFadeAlpha>(entity).unwrap().get()
+        };
+
+        assert_eq!(alpha_after(0.25, false), 0.0);
+        // The fade starts when the entity becomes visible, regardless of the
+        // time that passed while it was hidden.
+        assert_eq!(alpha_after(0.25, true), 0.0);
+        assert_eq!(alpha_after(0.25, true), 0.25);
+        assert_eq!(alpha_after(0.5, true), 0.75);
+        assert_eq!(alpha_after(0.5, true), 1.0);
+        assert_eq!(alpha_after(0.25, true), 1.0);
+        // Hiding the entity resets the fade.
+        assert_eq!(alpha_after(0.25, false), 0.0);
+        assert_eq!(alpha_after(0.25, true), 0.0);
+        assert_eq!(alpha_after(0.5, true), 0.5);
+    }
+}
@@ -0,0 +1,174 @@
+//! A coarse, precomputed spatial partition of static entities that lets
+//! [`check_visibility`](super::check_visibility) skip whole cells of entities
+//! at once.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    resource::Resource,
+};
+use bevy_math::{Affine3A, IVec3};
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_transform::components::GlobalTransform;
+
+use crate::primitives::{Aabb, Frustum};
+
+/// A coarse potentially visible set for large static scenes.
+///
+/// Entities registered with the grid are bucketed into cubic cells by the
+/// center of their world-space bounds. Each cell tracks the union of the
+/// bounds of the entities it contains. When this resource is present,
+/// [`check_visibility`](super::check_visibility) first tests every cell
+/// against the view frustum, then skips any registered entity whose cell
+/// doesn't overlap it without running the per-entity frustum test.
+///
+/// Entities that aren't registered are culled as usual, as are entities with
+/// [`NoFrustumCulling`](super::NoFrustumCulling) and all entities in views with
+/// [`NoCpuCulling`](crate::view::NoCpuCulling).
+///
+/// The grid is never updated automatically. It's intended for entities that
+/// don't move: if a registered entity moves or changes bounds, call
+/// [`SpatialVisibilityGrid::insert`] again to update it.
+#[derive(Resource)]
+pub struct SpatialVisibilityGrid {
+    cell_size: f32,
+    cells: HashMap<IVec3, GridCell>,
+    entity_cells: EntityHashMap<IVec3>,
+    skipped: AtomicUsize,
+}
+
+#[derive(Default)]
+struct GridCell {
+    /// The union of the world-space bounds of every entity in the cell.
+    bounds: Option<Aabb>,
+    /// The world-space bounds of each entity in the cell.
+    entities: EntityHashMap<Aabb>,
+}
+
+impl GridCell {
+    fn recompute_bounds(&mut self) {
+        self.bounds = self.entities.values().copied().reduce(|a, b| union(&a, &b));
+    }
+}
+
+fn union(a: &Aabb, b: &Aabb) -> Aabb {
+    Aabb::from_min_max(a.min().min(b.min()).into(), a.max().max(b.max()).into())
+}
+
+impl SpatialVisibilityGrid {
+    /// Creates an empty grid with cubic cells of the given edge length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cell_size` isn't positive and finite.
+    pub fn new(cell_size: f32) -> Self {
+        assert!(
+            cell_size.is_finite() && cell_size > 0.0,
+            "cell size must be positive and finite"
+        );
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+            entity_cells: EntityHashMap::default(),
+            skipped: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the edge length of the grid's cells.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Registers an entity with the given model-space bounds and transform,
+    /// replacing any previous registration.
+    pub fn insert(&mut self, entity: Entity, model_aabb: &Aabb, transform: &GlobalTransform) {
+        let world_from_local = transform.affine();
+        let world_aabb = Aabb {
+            center: world_from_local.transform_point3a(model_aabb.center),
+            half_extents: world_from_local.matrix3.abs() * model_aabb.half_extents.abs(),
+        };
+        self.insert_world_aabb(entity, world_aabb);
+    }
+
+    /// Registers an entity with the given world-space bounds, replacing any
+    /// previous registration.
+    pub fn insert_world_aabb(&mut self, entity: Entity, world_aabb: Aabb) {
+        self.remove(entity);
+
+        let key = (world_aabb.center / self.cell_size).floor().as_ivec3();
+        let cell = self.cells.entry(key).or_default();
+        cell.entities.insert(entity, world_aabb);
+        cell.bounds = Some(match cell.bounds {
+            Some(bounds) => union(&bounds, &world_aabb),
+            None => world_aabb,
+        });
+        self.entity_cells.insert(entity, key);
+    }
+
+    /// Removes an entity from the grid, returning `true` if it was registered.
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let Some(key) = self.entity_cells.remove(&entity) else {
+            return false;
+        };
+        if let Some(cell) = self.cells.get_mut(&key) {
+            cell.entities.remove(&entity);
+            if cell.entities.is_empty() {
+                self.cells.remove(&key);
+            } else {
+                cell.recompute_bounds();
+            }
+        }
+        true
+    }
+
+    /// Removes every entity from the grid.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.entity_cells.clear();
+    }
+
+    /// Returns the cell the entity is registered in, if any.
+    #[inline]
+    pub fn cell_of(&self, entity: Entity) -> Option<IVec3> {
+        self.entity_cells.get(&entity).copied()
+    }
+
+    /// Returns the number of registered entities.
+    pub fn len(&self) -> usize {
+        self.entity_cells.len()
+    }
+
+    /// Returns `true` if no entities are registered.
+    pub fn is_empty(&self) -> bool {
+        self.entity_cells.is_empty()
+    }
+
+    /// Returns the number of entity-view pairs that the last run of
+    /// [`check_visibility`](super::check_visibility) skipped because the
+    /// entity's cell didn't overlap the view frustum.
+    pub fn skipped_count(&self) -> usize {
+        self.skipped.load(Ordering::Relaxed)
+    }
+
+    /// Fills `cells` with the keys of every non-empty cell whose bounds
+    /// intersect the frustum.
+    pub(super) fn cells_in_frustum(&self, frustum: &Frustum, cells: &mut HashSet<IVec3>) {
+        cells.clear();
+        cells.extend(self.cells.iter().filter_map(|(key, cell)| {
+            let bounds = cell.bounds.as_ref()?;
+            frustum
+                .intersects_obb(bounds, &Affine3A::IDENTITY, true, false)
+                .then_some(*key)
+        }));
+    }
+
+    pub(super) fn reset_skipped(&self) {
+        self.skipped.store(0, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(super) fn record_skipped(&self) {
+        self.skipped.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -1,5 +1,9 @@
+mod fade;
+mod grid;
 mod range;
 mod render_layers;
+#[cfg(any(test, feature = "visibility_testing"))]
+pub mod testing;
 
 use core::any::TypeId;
 
@@ -7,12 +11,16 @@ use bevy_ecs::component::HookContext;
 use bevy_ecs::entity::EntityHashSet;
 use bevy_ecs::world::DeferredWorld;
 use derive_more::derive::{Deref, DerefMut};
+pub use fade::*;
+pub use grid::*;
 pub use range::*;
 pub use render_layers::*;
 
 use bevy_app::{Plugin, PostUpdate};
 use bevy_asset::Assets;
 use bevy_ecs::{hierarchy::validate_parent_has_component, prelude::*};
+use bevy_math::{FloatOrd, IVec3, Vec3A};
+use bevy_platform::collections::HashSet;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_transform::{components::GlobalTransform, TransformSystems};
 use bevy_utils::{Parallel, TypeIdMap};
@@ -127,6 +135,36 @@ impl InheritedVisibility {
     }
 }
 
+/// Generates a [`Condition`]-satisfying closure that returns `true` if the
+/// given entity is visible in the hierarchy, as reported by its
+/// [`InheritedVisibility`].
+///
+/// If the entity doesn't exist or has no [`InheritedVisibility`], the
+/// condition returns `false`.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::view::{entity_is_visible, Visibility};
+/// # let mut schedule = Schedule::default();
+/// # let mut world = World::new();
+/// # fn my_system() {}
+/// let entity = world.spawn(Visibility::default()).id();
+/// schedule.add_systems(my_system.run_if(entity_is_visible(entity)));
+/// ```
+///
+/// [`Condition`]: bevy_ecs::schedule::Condition
+pub fn entity_is_visible(
+    entity: Entity,
+) -> impl FnMut(Query<&InheritedVisibility>) -> bool + Clone {
+    move |query: Query<&InheritedVisibility>| {
+        query
+            .get(entity)
+            .is_ok_and(|inherited_visibility| inherited_visibility.get())
+    }
+}
+
 /// A bucket into which we group entities for the purposes of visibility.
 ///
 /// Bevy's various rendering subsystems (3D, 2D, UI, etc.) want to be able to
@@ -147,7 +185,10 @@ impl InheritedVisibility {
 ///
 /// When adding a new renderable component, you'll typically want to write an
 /// add-component hook that adds the type ID of that component to the
-/// [`VisibilityClass`] array. See `custom_phase_item` for an example.
+/// [`VisibilityClass`] array. See `custom_phase_item` for an example. For
+/// components that can't have a hook, such as components created at runtime,
+/// the class can be added with [`VisibilityClass::register`] or
+/// [`InsertVisibilityClassExt::insert_visibility_class`] instead.
 //
 // Note: This can't be a `ComponentId` because the visibility classes are copied
 // into the render world, and component IDs are per-world.
@@ -155,6 +196,45 @@ impl InheritedVisibility {
 #[reflect(Component, Default, Clone)]
 pub struct VisibilityClass(pub SmallVec<[TypeId; 1]>);
 
+impl VisibilityClass {
+    /// Adds `C` to the visibility classes of the entity, unless it's already
+    /// one of them.
+    pub fn register<C: 'static>(&mut self) {
+        let class = TypeId::of::<C>();
+        if !self.contains(&class) {
+            self.push(class);
+        }
+    }
+}
+
+/// Methods to add a visibility class to an entity at runtime, without an
+/// add-component hook like [`add_visibility_class`].
+pub trait InsertVisibilityClassExt {
+    /// Adds `C` to the [`VisibilityClass`] of this entity, inserting the
+    /// component if the entity doesn't have it yet.
+    ///
+    /// Adding the same class several times has no effect.
+    fn insert_visibility_class<C: 'static>(&mut self) -> &mut Self;
+}
+
+impl InsertVisibilityClassExt for EntityWorldMut<'_> {
+    fn insert_visibility_class<C: 'static>(&mut self) -> &mut Self {
+        if let Some(mut visibility_class) = self.get_mut::<VisibilityClass>() {
+            visibility_class.register::<C>();
+            return self;
+        }
+        self.insert(VisibilityClass(SmallVec::from_buf([TypeId::of::<C>()])))
+    }
+}
+
+impl InsertVisibilityClassExt for EntityCommands<'_> {
+    fn insert_visibility_class<C: 'static>(&mut self) -> &mut Self {
+        self.queue(|mut entity: EntityWorldMut| {
+            entity.insert_visibility_class::<C>();
+        })
+    }
+}
+
 /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering.
 ///
 /// Each frame, this will be reset to `false` during [`VisibilityPropagate`] systems in [`PostUpdate`].
@@ -205,10 +285,197 @@ impl ViewVisibility {
 /// - when a [`Mesh`] is updated but its [`Aabb`] is not, which might happen with animations,
 /// - when using some light effects, like wanting a [`Mesh`] out of the [`Frustum`]
 ///   to appear in the reflection of a [`Mesh`] within.
+///
+/// To only opt-out of frustum culling for some of the [`VisibilityClass`]es of
+/// an entity, use [`NoFrustumCullingFor`] instead.
 #[derive(Debug, Component, Default, Reflect)]
 #[reflect(Component, Default, Debug)]
 pub struct NoFrustumCulling;
 
+/// Use this component to opt-out of built-in frustum culling for some of the
+/// [`VisibilityClass`]es of an entity.
+///
+/// The entity is still frustum culled for its other visibility classes. If
+/// [`classes`](Self::classes) is empty, frustum culling is disabled for all of
+/// them, like with [`NoFrustumCulling`].
+///
+/// This is a separate component from [`NoFrustumCulling`] so that the latter
+/// stays a plain marker: it's cheap to check for in queries with
+/// [`Has`](bevy_ecs::query::Has) and `Without` filters, and it doesn't have to
+/// allocate or compare lists of visibility classes.
+#[derive(Debug, Component, Default, Reflect, Clone)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct NoFrustumCullingFor {
+    /// The visibility classes for which frustum culling is disabled, or all of
+    /// them if this is empty.
+    pub classes: SmallVec<[TypeId; 1]>,
+}
+
+impl NoFrustumCullingFor {
+    /// Creates a [`NoFrustumCullingFor`] that applies to the given visibility
+    /// classes, or to all of them if there are none.
+    pub fn new(classes: impl IntoIterator<Item = TypeId>) -> Self {
+        Self {
+            classes: classes.into_iter().collect(),
+        }
+    }
+
+    /// Returns `true` if frustum culling is disabled for the given visibility
+    /// class.
+    #[inline]
+    pub fn applies_to(&self, class: TypeId) -> bool {
+        self.classes.is_empty() || self.classes.contains(&class)
+    }
+}
+
+/// A local-space bounding sphere used for frustum culling of entities that
+/// have no [`Aabb`].
+///
+/// This is a cheap alternative to an [`Aabb`] for entities such as particles
+/// or point sprites. If an entity has both, the [`Aabb`] is used.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect, PartialEq)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct BoundingSphere {
+    /// The center of the sphere, relative to the entity's [`GlobalTransform`].
+    pub center: Vec3A,
+    /// The radius of the sphere, before scaling by the entity's [`GlobalTransform`].
+    pub radius: f32,
+}
+
+impl BoundingSphere {
+    /// Creates a new [`BoundingSphere`] from its local-space center and radius.
+    #[inline]
+    pub fn new(center: impl Into<Vec3A>, radius: f32) -> Self {
+        Self {
+            center: center.into(),
+            radius,
+        }
+    }
+
+    /// Returns the world-space [`Sphere`] enclosing this bounding sphere when
+    /// placed at the given transform.
+    #[inline]
+    pub fn to_world(&self, transform: &GlobalTransform) -> Sphere {
+        let matrix3 = transform.affine().matrix3;
+        let max_scale = matrix3
+            .x_axis
+            .length_squared()
+            .max(matrix3.y_axis.length_squared())
+            .max(matrix3.z_axis.length_squared())
+            .sqrt();
+        Sphere {
+            center: transform.transform_point(self.center.into()).into(),
+            radius: self.radius * max_scale,
+        }
+    }
+}
+
+/// A user-supplied culling test, see [`CullingPredicate`].
+///
+/// Returns `true` if the entity should be kept, or `false` if it should be
+/// culled from the view with the given [`Frustum`].
+pub type CullingPredicateFn =
+    Box<dyn Fn(Entity, &GlobalTransform, &Frustum) -> bool + Send + Sync + 'static>;
+
+/// Additional culling tests consulted by [`check_visibility`].
+///
+/// Each predicate is called for every entity that passed the built-in
+/// visibility range and frustum culling tests for a view, before its
+/// [`ViewVisibility`] is set. If any predicate returns `false`, the entity is
+/// culled from that view.
+///
+/// This can be used to implement custom culling schemes, such as portal or
+/// occlusion culling, without replacing [`check_visibility`]. By default, no
+/// predicates are registered.
+#[derive(Resource, Default)]
+pub struct CullingPredicate {
+    predicates: Vec<CullingPredicateFn>,
+}
+
+impl CullingPredicate {
+    /// Registers a new culling predicate.
+    pub fn push(
+        &mut self,
+        predicate: impl Fn(Entity, &GlobalTransform, &Frustum) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.predicates.push(Box::new(predicate));
+        self
+    }
+
+    /// Returns `true` if no culling predicates are registered.
+    pub fn is_empty(&self) -> bool {
+        self.predicates.is_empty()
+    }
+
+    /// Removes all registered culling predicates.
+    pub fn clear(&mut self) {
+        self.predicates.clear();
+    }
+
+    /// Returns `true` if every registered predicate accepts the entity.
+    #[inline]
+    pub fn accepts(&self, entity: Entity, transform: &GlobalTransform, frustum: &Frustum) -> bool {
+        self.predicates
+            .iter()
+            .all(|predicate| predicate(entity, transform, frustum))
+    }
+}
+
+/// A user-supplied callback, see [`VisibleEntityCallback`].
+///
+/// Called with the visible entity and the view it's visible from.
+pub type VisibleEntityCallbackFn = Box<dyn Fn(Entity, Entity) + Send + Sync + 'static>;
+
+/// Callbacks that [`check_visibility`] calls for each entity it finds visible from a view.
+///
+/// Each callback is called once per visible entity and view, with the entity and then the view,
+/// while the visible entities of the view are being collected into its [`VisibleEntities`]. This
+/// happens serially, after the parallel culling, so callbacks don't slow culling down. They can
+/// be used to gather statistics or fill custom data structures without iterating over the
+/// visible entities again. By default, no callbacks are registered.
+#[derive(Resource, Default)]
+pub struct VisibleEntityCallback {
+    callbacks: Vec<VisibleEntityCallbackFn>,
+}
+
+impl VisibleEntityCallback {
+    /// Registers a new callback.
+    pub fn push(&mut self, callback: impl Fn(Entity, Entity) + Send + Sync + 'static) -> &mut Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// Returns `true` if no callbacks are registered.
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+
+    /// Removes all registered callbacks.
+    pub fn clear(&mut self) {
+        self.callbacks.clear();
+    }
+
+    /// Calls every registered callback with the visible `entity` and the `view` it's visible from.
+    #[inline]
+    pub fn call(&self, entity: Entity, view: Entity) {
+        for callback in &self.callbacks {
+            callback(entity, view);
+        }
+    }
+}
+
+/// Add this component to a view to sort its [`VisibleEntities`] front-to-back.
+///
+/// After [`check_visibility`] has collected the visible entities, the
+/// `sort_visible_entities` system sorts every visibility class list by the
+/// squared distance from the view to the center of each entity's [`Aabb`]
+/// (or its [`GlobalTransform`] translation if it has no [`Aabb`]).
+///
+/// Without this component, the order of [`VisibleEntities`] is unspecified.
+#[derive(Debug, Component, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct SortVisibleEntities;
+
 /// Collection of entities visible from the current view.
 ///
 /// This component contains all entities which are visible from the currently
@@ -221,8 +488,18 @@ pub struct NoFrustumCulling;
 #[derive(Clone, Component, Default, Debug, Reflect)]
 #[reflect(Component, Default, Debug, Clone)]
 pub struct VisibleEntities {
+    /// The entities visible in each visibility class.
+    ///
+    /// Entities added or removed directly through this field aren't tracked by
+    /// [`Self::contains`].
     #[reflect(ignore, clone)]
     pub entities: TypeIdMap<Vec<Entity>>,
+    /// A set mirroring [`Self::entities`], for fast membership tests.
+    ///
+    /// This is kept in sync by the methods of [`VisibleEntities`] other than
+    /// [`Self::get_mut`], and by [`check_visibility`].
+    #[reflect(ignore, clone)]
+    lookup: TypeIdMap<EntityHashSet>,
 }
 
 impl VisibleEntities {
@@ -233,6 +510,12 @@ impl VisibleEntities {
         }
     }
 
+    /// Returns the list of entities visible in the given visibility class, for
+    /// modification.
+    ///
+    /// Entities added or removed through this list aren't tracked by
+    /// [`Self::contains`]; use [`Self::push`], [`Self::remove`], or
+    /// [`Self::set`] instead.
     pub fn get_mut(&mut self, type_id: TypeId) -> &mut Vec<Entity> {
         self.entities.entry(type_id).or_default()
     }
@@ -249,8 +532,22 @@ impl VisibleEntities {
         self.get(type_id).is_empty()
     }
 
+    /// Returns `true` if the given entity is visible from this view in the
+    /// given visibility class.
+    ///
+    /// Entities added directly through [`Self::get_mut`] or [`Self::entities`]
+    /// aren't tracked by this method; use [`Self::push`] instead.
+    pub fn contains(&self, entity: Entity, type_id: TypeId) -> bool {
+        self.lookup
+            .get(&type_id)
+            .is_some_and(|entities| entities.contains(&entity))
+    }
+
     pub fn clear(&mut self, type_id: TypeId) {
         self.get_mut(type_id).clear();
+        if let Some(entities) = self.lookup.get_mut(&type_id) {
+            entities.clear();
+        }
     }
 
     pub fn clear_all(&mut self) {
@@ -258,10 +555,60 @@ impl VisibleEntities {
         for entities in self.entities.values_mut() {
             entities.clear();
         }
+        for entities in self.lookup.values_mut() {
+            entities.clear();
+        }
+    }
+
+    /// Replaces the entities visible in the given visibility class.
+    ///
+    /// Like [`Self::clear`], this keeps the allocation of the list of
+    /// entities.
+    pub fn set(&mut self, type_id: TypeId, entities: impl IntoIterator<Item = Entity>) {
+        let visible_entities = self.entities.entry(type_id).or_default();
+        let lookup = self.lookup.entry(type_id).or_default();
+        visible_entities.clear();
+        lookup.clear();
+        for entity in entities {
+            visible_entities.push(entity);
+            lookup.insert(entity);
+        }
+    }
+
+    /// Removes all the entities visible in the given visibility class, and
+    /// returns them in order.
+    ///
+    /// Like [`Self::clear`], this keeps the allocation of the list of
+    /// entities, so that it can be reused the next frame. The entities are
+    /// removed even if the iterator is dropped before reaching the end.
+    pub fn drain(&mut self, type_id: TypeId) -> impl DoubleEndedIterator<Item = Entity> + '_ {
+        if let Some(entities) = self.lookup.get_mut(&type_id) {
+            entities.clear();
+        }
+        self.get_mut(type_id).drain(..)
     }
 
     pub fn push(&mut self, entity: Entity, type_id: TypeId) {
         self.get_mut(type_id).push(entity);
+        self.lookup.entry(type_id).or_default().insert(entity);
+    }
+
+    /// Removes the given entity from the entities visible in the given
+    /// visibility class, returning `true` if it was visible.
+    ///
+    /// The order of the remaining entities is preserved.
+    pub fn remove(&mut self, entity: Entity, type_id: TypeId) -> bool {
+        let was_visible = self
+            .lookup
+            .get_mut(&type_id)
+            .is_some_and(|entities| entities.remove(&entity));
+        if was_visible {
+            let entities = self.get_mut(type_id);
+            if let Some(index) = entities.iter().position(|&visible| visible == entity) {
+                entities.remove(index);
+            }
+        }
+        was_visible
     }
 }
 
@@ -338,6 +685,8 @@ impl Plugin for VisibilityPlugin {
         use VisibilitySystems::*;
 
         app.register_type::<VisibilityClass>()
+            .register_type::<SortVisibleEntities>()
+            .register_type::<BoundingSphere>()
             .configure_sets(
                 PostUpdate,
                 (CalculateBounds, UpdateFrusta, VisibilityPropagate)
@@ -349,6 +698,8 @@ impl Plugin for VisibilityPlugin {
                 MarkNewlyHiddenEntitiesInvisible.after(CheckVisibility),
             )
             .init_resource::<PreviousVisibleEntities>()
+            .init_resource::<CullingPredicate>()
+            .init_resource::<VisibleEntityCallback>()
             .add_systems(
                 PostUpdate,
                 (
@@ -356,6 +707,9 @@ impl Plugin for VisibilityPlugin {
                     (visibility_propagate_system, reset_view_visibility)
                         .in_set(VisibilityPropagate),
                     check_visibility.in_set(CheckVisibility),
+                    sort_visible_entities
+                        .in_set(CheckVisibility)
+                        .after(check_visibility),
                     mark_newly_hidden_entities_invisible.in_set(MarkNewlyHiddenEntitiesInvisible),
                 ),
             );
@@ -366,16 +720,26 @@ impl Plugin for VisibilityPlugin {
 /// [`Mesh3d`] component and without a [`NoFrustumCulling`] component.
 ///
 /// This system is used in system set [`VisibilitySystems::CalculateBounds`].
+/// The bounds are computed in parallel, and inserted once all of them are
+/// known.
 pub fn calculate_bounds(
     mut commands: Commands,
+    mut thread_queues: Local<Parallel<Vec<(Entity, Aabb)>>>,
     meshes: Res<Assets<Mesh>>,
     without_aabb: Query<(Entity, &Mesh3d), (Without<Aabb>, Without<NoFrustumCulling>)>,
 ) {
-    for (entity, mesh_handle) in &without_aabb {
-        if let Some(mesh) = meshes.get(mesh_handle) {
-            if let Some(aabb) = mesh.compute_aabb() {
-                commands.entity(entity).try_insert(aabb);
+    without_aabb.par_iter().for_each_init(
+        || thread_queues.borrow_local_mut(),
+        |queue, (entity, mesh_handle)| {
+            if let Some(aabb) = meshes.get(mesh_handle).and_then(MeshAabb::compute_aabb) {
+                queue.push((entity, aabb));
             }
+        },
+    );
+
+    for queue in thread_queues.iter_mut() {
+        for (entity, aabb) in queue.drain(..) {
+            commands.entity(entity).try_insert(aabb);
         }
     }
 }
@@ -396,41 +760,61 @@ pub fn update_frusta(
 
 fn visibility_propagate_system(
     changed: Query<
-        (Entity, &Visibility, Option<&ChildOf>, Option<&Children>),
+        (Entity, &Visibility, Option<&ChildOf>),
         (
             With<InheritedVisibility>,
             Or<(Changed<Visibility>, Changed<ChildOf>)>,
         ),
     >,
+    ancestors: Query<&ChildOf>,
     mut visibility_query: Query<(&Visibility, &mut InheritedVisibility)>,
     children_query: Query<&Children, (With<Visibility>, With<InheritedVisibility>)>,
+    mut roots: Local<Vec<(usize, Entity)>>,
+    mut visited: Local<EntityHashSet>,
 ) {
-    for (entity, visibility, child_of, children) in &changed {
-        let is_visible = match visibility {
-            Visibility::Visible => true,
-            Visibility::Hidden => false,
-            // fall back to true if no parent is found or parent lacks components
+    // Process the changed entities from the top of the hierarchy down, so that each entity's
+    // parent is up to date by the time the entity itself is computed. Entities that were
+    // already reached while propagating from a changed ancestor are skipped, so that each
+    // subtree is only walked once, even when many entities in it changed during the same frame
+    // (for example, when reparenting many siblings at once).
+    //
+    // Finding the depth of each changed entity walks up its ancestors, which only costs
+    // `O(depth)` per entity that changed this frame, and nothing on frames without changes.
+    // Processing a descendant before its changed ancestor would instead compute it from stale
+    // data, and then walk its whole subtree a second time once the ancestor is processed.
+    roots.extend(
+        changed
+            .iter()
+            .map(|(entity, ..)| (ancestors.iter_ancestors(entity).count(), entity)),
+    );
+    roots.sort_unstable_by_key(|&(depth, _)| depth);
+
+    for (_, entity) in roots.drain(..) {
+        if visited.contains(&entity) {
+            continue;
+        }
+
+        let (_, visibility, child_of) = changed
+            .get(entity)
+            .expect("`roots` only contains entities from the `changed` query");
+        // fall back to true if no parent is found or parent lacks components
+        let parent_is_visible = match visibility {
             Visibility::Inherited => child_of
                 .and_then(|c| visibility_query.get(c.parent()).ok())
                 .is_none_or(|(_, x)| x.get()),
+            Visibility::Visible | Visibility::Hidden => true,
         };
-        let (_, mut inherited_visibility) = visibility_query
-            .get_mut(entity)
-            .expect("With<InheritedVisibility> ensures this query will return a value");
-
-        // Only update the visibility if it has changed.
-        // This will also prevent the visibility from propagating multiple times in the same frame
-        // if this entity's visibility has been updated recursively by its parent.
-        if inherited_visibility.get() != is_visible {
-            inherited_visibility.0 = is_visible;
-
-            // Recursively update the visibility of each child.
-            for &child in children.into_iter().flatten() {
-                let _ =
-                    propagate_recursive(is_visible, child, &mut visibility_query, &children_query);
-            }
-        }
+
+        let _ = propagate_recursive(
+            parent_is_visible,
+            entity,
+            &mut visibility_query,
+            &children_query,
+            &mut visited,
+        );
     }
+
+    visited.clear();
 }
 
 fn propagate_recursive(
@@ -438,12 +822,14 @@ fn propagate_recursive(
     entity: Entity,
     visibility_query: &mut Query<(&Visibility, &mut InheritedVisibility)>,
     children_query: &Query<&Children, (With<Visibility>, With<InheritedVisibility>)>,
+    visited: &mut EntityHashSet,
     // BLOCKED: https://github.com/rust-lang/rust/issues/31436
     // We use a result here to use the `?` operator. Ideally we'd use a try block instead
 ) -> Result<(), ()> {
     // Get the visibility components for the current entity.
     // If the entity does not have the required components, just return early.
     let (visibility, mut inherited_visibility) = visibility_query.get_mut(entity).map_err(drop)?;
+    visited.insert(entity);
 
     let is_visible = match visibility {
         Visibility::Visible => true,
@@ -452,12 +838,15 @@ fn propagate_recursive(
     };
 
     // Only update the visibility if it has changed.
+    // Changed descendants that aren't reached here are processed separately by
+    // `visibility_propagate_system`.
     if inherited_visibility.get() != is_visible {
         inherited_visibility.0 = is_visible;
 
         // Recursively update the visibility of each child.
         for &child in children_query.get(entity).ok().into_iter().flatten() {
-            let _ = propagate_recursive(is_visible, child, visibility_query, children_query);
+            let _ =
+                propagate_recursive(is_visible, child, visibility_query, children_query, visited);
         }
     }
 
@@ -514,14 +903,31 @@ pub fn check_visibility(
         &VisibilityClass,
         Option<&RenderLayers>,
         Option<&Aabb>,
+        Option<&BoundingSphere>,
         &GlobalTransform,
         Has<NoFrustumCulling>,
+        Option<&NoFrustumCullingFor>,
         Has<VisibilityRange>,
     )>,
     visible_entity_ranges: Option<Res<VisibleEntityRanges>>,
     mut previous_visible_entities: ResMut<PreviousVisibleEntities>,
+    culling_predicate: Option<Res<CullingPredicate>>,
+    spatial_grid: Option<Res<SpatialVisibilityGrid>>,
+    mut visible_cells: Local<HashSet<IVec3>>,
+    visible_entity_callback: Option<Res<VisibleEntityCallback>>,
+    mut reported_entities: Local<EntityHashSet>,
 ) {
     let visible_entity_ranges = visible_entity_ranges.as_deref();
+    let culling_predicate = culling_predicate
+        .as_deref()
+        .filter(|predicate| !predicate.is_empty());
+    let visible_entity_callback = visible_entity_callback
+        .as_deref()
+        .filter(|callback| !callback.is_empty());
+    let spatial_grid = spatial_grid.as_deref();
+    if let Some(spatial_grid) = spatial_grid {
+        spatial_grid.reset_skipped();
+    }
 
     for (view, mut visible_entities, frustum, maybe_view_mask, camera, no_cpu_culling) in
         &mut view_query
@@ -532,6 +938,22 @@ pub fn check_visibility(
 
         let view_mask = maybe_view_mask.unwrap_or_default();
 
+        // Find the cells of the spatial grid that overlap the frustum, so that
+        // we can skip the entities in all other cells.
+        let spatial_grid = spatial_grid.filter(|grid| !no_cpu_culling && !grid.is_empty());
+        if let Some(spatial_grid) = spatial_grid {
+            spatial_grid.cells_in_frustum(frustum, &mut visible_cells);
+        }
+        let culler = ViewCuller {
+            view,
+            frustum,
+            view_mask,
+            no_cpu_culling,
+            visible_entity_ranges,
+            culling_predicate,
+            spatial_grid: spatial_grid.map(|spatial_grid| (spatial_grid, &*visible_cells)),
+        };
+
         visible_aabb_query.par_iter_mut().for_each_init(
             || thread_queues.borrow_local_mut(),
             |queue, query_item| {
@@ -542,8 +964,10 @@ pub fn check_visibility(
                     visibility_class,
                     maybe_entity_mask,
                     maybe_model_aabb,
+                    maybe_bounding_sphere,
                     transform,
                     no_frustum_culling,
+                    maybe_no_frustum_culling_for,
                     has_visibility_range,
                 ) = query_item;
 
@@ -553,60 +977,44 @@ pub fn check_visibility(
                     return;
                 }
 
-                let entity_mask = maybe_entity_mask.unwrap_or_default();
-                if !view_mask.intersects(entity_mask) {
-                    return;
-                }
-
-                // If outside of the visibility range, cull.
-                if has_visibility_range
-                    && visible_entity_ranges.is_some_and(|visible_entity_ranges| {
-                        !visible_entity_ranges.entity_is_in_range_of_view(entity, view)
-                    })
-                {
-                    return;
-                }
-
-                // If we have an aabb, do frustum culling
-                if !no_frustum_culling && !no_cpu_culling {
-                    if let Some(model_aabb) = maybe_model_aabb {
-                        let world_from_local = transform.affine();
-                        let model_sphere = Sphere {
-                            center: world_from_local.transform_point3a(model_aabb.center),
-                            radius: transform.radius_vec3a(model_aabb.half_extents),
-                        };
-                        // Do quick sphere-based frustum culling
-                        if !frustum.intersects_sphere(&model_sphere, false) {
-                            return;
-                        }
-                        // Do aabb-based frustum culling
-                        if !frustum.intersects_obb(model_aabb, &world_from_local, true, false) {
-                            return;
-                        }
-                    }
-                }
+                // Add the entity to the queue for all visibility classes the
+                // entity is visible in.
+                let is_visible = culler.test(
+                    &EntityCullingData {
+                        entity,
+                        visibility_class,
+                        mask: maybe_entity_mask.unwrap_or_default(),
+                        model_aabb: maybe_model_aabb,
+                        bounding_sphere: maybe_bounding_sphere,
+                        transform,
+                        no_frustum_culling,
+                        no_frustum_culling_for: maybe_no_frustum_culling_for,
+                        has_visibility_range,
+                    },
+                    |visibility_class_id| {
+                        queue.entry(visibility_class_id).or_default().push(entity);
+                    },
+                );
 
                 // Make sure we don't trigger changed notifications
                 // unnecessarily by checking whether the flag is set before
                 // setting it.
-                if !**view_visibility {
+                if is_visible && !**view_visibility {
                     view_visibility.set();
                 }
-
-                // Add the entity to the queue for all visibility classes the
-                // entity is in.
-                for visibility_class_id in visibility_class.iter() {
-                    queue.entry(*visibility_class_id).or_default().push(entity);
-                }
             },
         );
 
         visible_entities.clear_all();
 
         // Drain all the thread queues into the `visible_entities` list.
+        let visible_entities = &mut *visible_entities;
+        reported_entities.clear();
         for class_queues in thread_queues.iter_mut() {
             for (class, entities) in class_queues {
-                let visible_entities_for_class = visible_entities.get_mut(*class);
+                let visible_entities_for_class =
+                    visible_entities.entities.entry(*class).or_default();
+                let lookup_for_class = visible_entities.lookup.entry(*class).or_default();
                 for entity in entities.drain(..) {
                     // As we mark entities as visible, we remove them from the
                     // `previous_visible_entities` list. At the end, all of the
@@ -616,12 +1024,177 @@ pub fn check_visibility(
                     previous_visible_entities.remove(&entity);
 
                     visible_entities_for_class.push(entity);
+                    lookup_for_class.insert(entity);
+
+                    // Entities can be visible in several classes, but should only be reported
+                    // once per view.
+                    if let Some(callback) = visible_entity_callback {
+                        if reported_entities.insert(entity) {
+                            callback.call(entity, view);
+                        }
+                    }
                 }
             }
         }
     }
 }
 
+/// A view that [`check_visibility`] tests entities against.
+struct ViewCuller<'a> {
+    view: Entity,
+    frustum: &'a Frustum,
+    view_mask: &'a RenderLayers,
+    no_cpu_culling: bool,
+    visible_entity_ranges: Option<&'a VisibleEntityRanges>,
+    culling_predicate: Option<&'a CullingPredicate>,
+    /// The spatial grid, and the cells of it that overlap the frustum.
+    spatial_grid: Option<(&'a SpatialVisibilityGrid, &'a HashSet<IVec3>)>,
+}
+
+/// The components of an entity that are tested against a [`ViewCuller`].
+struct EntityCullingData<'a> {
+    entity: Entity,
+    visibility_class: &'a VisibilityClass,
+    mask: &'a RenderLayers,
+    model_aabb: Option<&'a Aabb>,
+    bounding_sphere: Option<&'a BoundingSphere>,
+    transform: &'a GlobalTransform,
+    no_frustum_culling: bool,
+    no_frustum_culling_for: Option<&'a NoFrustumCullingFor>,
+    has_visibility_range: bool,
+}
+
+impl ViewCuller<'_> {
+    /// Tests whether an entity is visible from the view, calling `visible` with
+    /// each visibility class it's visible in.
+    ///
+    /// Returns `true` if the entity is visible in any of its classes. The
+    /// entity's [`InheritedVisibility`] isn't checked.
+    fn test(&self, entity: &EntityCullingData, mut visible: impl FnMut(TypeId)) -> bool {
+        if !self.view_mask.intersects(entity.mask) {
+            return false;
+        }
+
+        // If outside of the visibility range, cull.
+        if entity.has_visibility_range
+            && self
+                .visible_entity_ranges
+                .is_some_and(|visible_entity_ranges| {
+                    !visible_entity_ranges.entity_is_in_range_of_view(entity.entity, self.view)
+                })
+        {
+            return false;
+        }
+
+        // If the entity is in a cell of the spatial grid that doesn't
+        // overlap the frustum, cull it without testing its bounds.
+        if let Some((spatial_grid, visible_cells)) = self.spatial_grid {
+            if !entity.no_frustum_culling
+                && entity.no_frustum_culling_for.is_none()
+                && spatial_grid
+                    .cell_of(entity.entity)
+                    .is_some_and(|cell| !visible_cells.contains(&cell))
+            {
+                spatial_grid.record_skipped();
+                return false;
+            }
+        }
+
+        // If we have bounds, do frustum culling, unless it's
+        // disabled for all of the entity's visibility classes.
+        let in_frustum = self.no_cpu_culling
+            || entity.no_frustum_culling
+            || intersects_frustum(
+                self.frustum,
+                entity.transform,
+                entity.model_aabb,
+                entity.bounding_sphere,
+            );
+        if !in_frustum && entity.no_frustum_culling_for.is_none() {
+            return false;
+        }
+
+        // Give user-supplied culling predicates a chance to reject the entity.
+        if let Some(culling_predicate) = self.culling_predicate {
+            if !culling_predicate.accepts(entity.entity, entity.transform, self.frustum) {
+                return false;
+            }
+        }
+
+        // The entity is visible in all of its classes for which it wasn't
+        // frustum culled.
+        let mut is_visible = in_frustum;
+        for visibility_class_id in entity.visibility_class.iter() {
+            if in_frustum
+                || entity
+                    .no_frustum_culling_for
+                    .is_some_and(|no_frustum_culling_for| {
+                        no_frustum_culling_for.applies_to(*visibility_class_id)
+                    })
+            {
+                visible(*visibility_class_id);
+                is_visible = true;
+            }
+        }
+        is_visible
+    }
+}
+
+/// Returns `false` if the entity with the given bounds is outside of the
+/// frustum.
+///
+/// Entities with neither an [`Aabb`] nor a [`BoundingSphere`] are always
+/// considered to be inside the frustum.
+fn intersects_frustum(
+    frustum: &Frustum,
+    transform: &GlobalTransform,
+    maybe_model_aabb: Option<&Aabb>,
+    maybe_bounding_sphere: Option<&BoundingSphere>,
+) -> bool {
+    if let Some(model_aabb) = maybe_model_aabb {
+        let world_from_local = transform.affine();
+        let model_sphere = Sphere {
+            center: world_from_local.transform_point3a(model_aabb.center),
+            radius: transform.radius_vec3a(model_aabb.half_extents),
+        };
+        // Do quick sphere-based frustum culling, then aabb-based frustum
+        // culling
+        frustum.intersects_sphere(&model_sphere, false)
+            && frustum.intersects_obb(model_aabb, &world_from_local, true, false)
+    } else if let Some(bounding_sphere) = maybe_bounding_sphere {
+        // Without an aabb, fall back to sphere-based frustum culling
+        frustum.intersects_sphere(&bounding_sphere.to_world(transform), false)
+    } else {
+        true
+    }
+}
+
+/// Sorts the [`VisibleEntities`] of views with [`SortVisibleEntities`]
+/// front-to-back.
+///
+/// This system runs in [`VisibilitySystems::CheckVisibility`], after
+/// [`check_visibility`].
+pub fn sort_visible_entities(
+    mut view_query: Query<(&mut VisibleEntities, &GlobalTransform), With<SortVisibleEntities>>,
+    entity_query: Query<(&GlobalTransform, Option<&Aabb>)>,
+) {
+    for (mut visible_entities, view_transform) in &mut view_query {
+        let view_translation = view_transform.translation_vec3a();
+        for entities in visible_entities.entities.values_mut() {
+            entities.sort_by_cached_key(|entity| {
+                let Ok((transform, maybe_aabb)) = entity_query.get(*entity) else {
+                    return FloatOrd(f32::INFINITY);
+                };
+                let center = match maybe_aabb {
+                    Some(aabb) => transform.affine().transform_point3a(aabb.center),
+                    None => transform.translation_vec3a(),
+                };
+                FloatOrd(center.distance_squared(view_translation))
+            });
+        }
+    }
+}
+
 /// Marks any entities that weren't judged visible this frame as invisible.
 ///
 /// As visibility-determining systems run, they remove entities that they judge
@@ -662,14 +1235,120 @@ pub fn add_visibility_class<C>(
     C: 'static,
 {
     if let Some(mut visibility_class) = world.get_mut::<VisibilityClass>(entity) {
-        visibility_class.push(TypeId::of::<C>());
+        visibility_class.register::<C>();
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use alloc::sync::Arc;
     use bevy_app::prelude::*;
+    use bevy_ecs::system::{RunSystemOnce, ScheduleSystem};
+    use bevy_math::Vec3;
+    use std::sync::Mutex;
+
+    /// Creates a world with the resources needed by [`check_visibility`], and a schedule running
+    /// the given systems.
+    fn visibility_test_world<M>(
+        systems: impl IntoScheduleConfigs<ScheduleSystem, M>,
+    ) -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<PreviousVisibleEntities>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(systems);
+        (world, schedule)
+    }
+
+    /// Spawns a visible entity of the [`Mesh3d`] visibility class at the given depth.
+    fn spawn_mesh(world: &mut World, z: f32) -> EntityWorldMut<'_> {
+        world.spawn((
+            InheritedVisibility::VISIBLE,
+            ViewVisibility::default(),
+            VisibilityClass(SmallVec::from_buf([TypeId::of::<Mesh3d>()])),
+            GlobalTransform::from_xyz(0.0, 0.0, z),
+        ))
+    }
+
+    #[test]
+    fn visibility_propagation_batched_reparenting() {
+        let mut world = World::new();
+
+        // Spawn the descendants first, so that they are likely to be reported as changed before
+        // their ancestors.
+        let mut siblings = Vec::new();
+        let mut children = Vec::new();
+        let mut grandchildren = Vec::new();
+        for _ in 0..4 {
+            let grandchild = world.spawn(Visibility::default()).id();
+            let child = world.spawn(Visibility::Hidden).add_child(grandchild).id();
+            siblings.push(world.spawn(Visibility::default()).add_child(child).id());
+            children.push(child);
+            grandchildren.push(grandchild);
+        }
+        let parent = world.spawn(Visibility::Hidden).id();
+        world.run_system_once(visibility_propagate_system).unwrap();
+
+        // Reparent every sibling under the hidden parent, and make their children inherit their
+        // visibility, so that they are also reported as changed this frame.
+        for &sibling in &siblings {
+            world.entity_mut(sibling).insert(ChildOf(parent));
+        }
+        for &child in &children {
+            world.entity_mut(child).insert(Visibility::Inherited);
+        }
+
+        let change_ticks = |world: &World, entities: &[Entity]| {
+            entities
+                .iter()
+                .map(|&entity| {
+                    world
+                        .entity(entity)
+                        .get_change_ticks::<InheritedVisibility>()
+                        .unwrap()
+                        .changed
+                })
+                .collect::<Vec<_>>()
+        };
+        let descendants = [children.clone(), grandchildren.clone()].concat();
+        let descendant_ticks = change_ticks(&world, &descendants);
+
+        world.run_system_once(visibility_propagate_system).unwrap();
+
+        for entity in siblings.iter().chain(&descendants) {
+            assert!(!world.get::<InheritedVisibility>(*entity).unwrap().get());
+        }
+        // The descendants were already hidden. Had they been computed before the siblings, from
+        // their stale visibility, they would have been made visible and then hidden again.
+        assert_eq!(descendant_ticks, change_ticks(&world, &descendants));
+    }
+
+    #[test]
+    fn visibility_propagation_testing_harness() {
+        let mut world = World::new();
+        let mut schedule = testing::visibility_propagation_schedule();
+        let entities = testing::build_visibility_hierarchy(
+            &mut world,
+            &[
+                (Visibility::Inherited, None),
+                (Visibility::Inherited, Some(0)),
+                (Visibility::Hidden, Some(1)),
+                (Visibility::Inherited, Some(2)),
+                (Visibility::Visible, Some(2)),
+            ],
+        );
+
+        schedule.run(&mut world);
+        testing::assert_inherited_visibility(&world, &entities, &[true, true, false, false, true]);
+
+        world.entity_mut(entities[0]).insert(Visibility::Hidden);
+        schedule.run(&mut world);
+        testing::assert_inherited_visibility(
+            &world,
+            &entities,
+            &[false, false, false, false, true],
+        );
+    }
 
     #[test]
     fn visibility_propagation() {
@@ -965,6 +1644,395 @@ mod test {
         assert!(child_visible);
     }
 
+    #[test]
+    fn runtime_visibility_class_is_checked() {
+        struct RuntimeRenderable;
+
+        let (mut world, mut schedule) = visibility_test_world(check_visibility);
+
+        let camera = world.spawn(Camera::default()).id();
+        let spawn_renderable = |world: &mut World| {
+            world
+                .spawn((
+                    InheritedVisibility::VISIBLE,
+                    ViewVisibility::default(),
+                    GlobalTransform::IDENTITY,
+                ))
+                .id()
+        };
+        let from_world = spawn_renderable(&mut world);
+        world
+            .entity_mut(from_world)
+            .insert_visibility_class::<RuntimeRenderable>()
+            .insert_visibility_class::<RuntimeRenderable>();
+        let from_commands = spawn_renderable(&mut world);
+        world
+            .commands()
+            .entity(from_commands)
+            .insert_visibility_class::<Mesh3d>()
+            .insert_visibility_class::<RuntimeRenderable>();
+        world.flush();
+
+        // Adding a class twice doesn't duplicate it.
+        let visibility_class = world.get::<VisibilityClass>(from_world).unwrap();
+        assert_eq!(&visibility_class[..], [TypeId::of::<RuntimeRenderable>()]);
+
+        schedule.run(&mut world);
+
+        let visible_entities = world.get::<VisibleEntities>(camera).unwrap();
+        assert_eq!(
+            visible_entities.get(TypeId::of::<RuntimeRenderable>()),
+            &[from_world, from_commands]
+        );
+        assert_eq!(
+            visible_entities.get(TypeId::of::<Mesh3d>()),
+            &[from_commands]
+        );
+        assert!(world.get::<ViewVisibility>(from_world).unwrap().get());
+    }
+
+    #[test]
+    fn culling_predicate_culls_entities_behind_plane() {
+        let (mut world, mut schedule) = visibility_test_world(check_visibility);
+        world.init_resource::<CullingPredicate>();
+
+        // Cull everything behind the `z = 0` plane.
+        world
+            .resource_mut::<CullingPredicate>()
+            .push(|_, transform, _| transform.translation().z >= 0.0);
+
+        let camera = world.spawn(Camera::default()).id();
+        let in_front = spawn_mesh(&mut world, 1.0).id();
+        let behind = spawn_mesh(&mut world, -1.0).id();
+
+        schedule.run(&mut world);
+
+        let visible_entities = world.get::<VisibleEntities>(camera).unwrap();
+        assert_eq!(visible_entities.get(TypeId::of::<Mesh3d>()), &[in_front]);
+        assert!(world.get::<ViewVisibility>(in_front).unwrap().get());
+        assert!(!world.get::<ViewVisibility>(behind).unwrap().get());
+    }
+
+    #[test]
+    fn visible_entity_callback_reports_each_visible_entity() {
+        let (mut world, mut schedule) = visibility_test_world(check_visibility);
+        world.init_resource::<VisibleEntityCallback>();
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let reported_clone = reported.clone();
+        world
+            .resource_mut::<VisibleEntityCallback>()
+            .push(move |entity, view| reported_clone.lock().unwrap().push((entity, view)));
+
+        let spawn_camera = |world: &mut World| {
+            world
+                .spawn((
+                    Camera::default(),
+                    GlobalTransform::IDENTITY,
+                    Projection::default().compute_frustum(&GlobalTransform::IDENTITY),
+                ))
+                .id()
+        };
+        let camera_1 = spawn_camera(&mut world);
+        let camera_2 = spawn_camera(&mut world);
+        let spawn_bounded_mesh = |world: &mut World, z: f32| {
+            spawn_mesh(world, z)
+                .insert((
+                    // Being visible in several classes shouldn't report an entity twice.
+                    VisibilityClass(SmallVec::from_slice(&[
+                        TypeId::of::<Mesh3d>(),
+                        TypeId::of::<Camera>(),
+                    ])),
+                    Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5)),
+                ))
+                .id()
+        };
+        let visible_1 = spawn_bounded_mesh(&mut world, -5.0);
+        let visible_2 = spawn_bounded_mesh(&mut world, -10.0);
+        // Behind the cameras.
+        spawn_bounded_mesh(&mut world, 5.0);
+
+        schedule.run(&mut world);
+
+        let mut reported = reported.lock().unwrap().clone();
+        reported.sort();
+        let mut expected = vec![
+            (visible_1, camera_1),
+            (visible_2, camera_1),
+            (visible_1, camera_2),
+            (visible_2, camera_2),
+        ];
+        expected.sort();
+        assert_eq!(reported, expected);
+    }
+
+    #[test]
+    fn spatial_grid_skips_entities_in_cells_outside_frustum() {
+        let (mut world, mut schedule) = visibility_test_world(check_visibility);
+        world.insert_resource(SpatialVisibilityGrid::new(10.0));
+
+        let camera = world
+            .spawn((
+                Camera::default(),
+                GlobalTransform::IDENTITY,
+                Projection::default().compute_frustum(&GlobalTransform::IDENTITY),
+            ))
+            .id();
+        let aabb = Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5));
+        let spawn_bounded_mesh = |world: &mut World, z: f32, in_grid: bool| {
+            let entity = spawn_mesh(world, z).insert(aabb).id();
+            if in_grid {
+                world.resource_mut::<SpatialVisibilityGrid>().insert(
+                    entity,
+                    &aabb,
+                    &GlobalTransform::from_xyz(0.0, 0.0, z),
+                );
+            }
+            entity
+        };
+        // The camera looks down -Z, so the cell behind it is never visible.
+        let near = spawn_bounded_mesh(&mut world, -5.0, true);
+        let far = spawn_bounded_mesh(&mut world, 55.0, true);
+        let unregistered = spawn_bounded_mesh(&mut world, 55.0, false);
+
+        schedule.run(&mut world);
+
+        let grid = world.resource::<SpatialVisibilityGrid>();
+        assert_eq!(grid.skipped_count(), 1);
+        assert_ne!(grid.cell_of(near), grid.cell_of(far));
+        let visible_entities = world.get::<VisibleEntities>(camera).unwrap();
+        assert_eq!(visible_entities.get(TypeId::of::<Mesh3d>()), &[near]);
+        assert!(world.get::<ViewVisibility>(near).unwrap().get());
+        assert!(!world.get::<ViewVisibility>(far).unwrap().get());
+        assert!(!world.get::<ViewVisibility>(unregistered).unwrap().get());
+
+        // Removing the entity from the grid falls back to per-entity culling.
+        world.resource_mut::<SpatialVisibilityGrid>().remove(far);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<SpatialVisibilityGrid>().skipped_count(), 0);
+        assert!(!world.get::<ViewVisibility>(far).unwrap().get());
+    }
+
+    #[test]
+    fn entity_is_visible_run_condition() {
+        #[derive(Resource, Default)]
+        struct Counter(u32);
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let entity = world.spawn(Visibility::Visible).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                visibility_propagate_system,
+                (|mut counter: ResMut<Counter>| counter.0 += 1).run_if(entity_is_visible(entity)),
+            )
+                .chain(),
+        );
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        world.entity_mut(entity).insert(Visibility::Hidden);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        world.entity_mut(entity).insert(Visibility::Visible);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+
+    #[test]
+    fn sort_visible_entities_front_to_back() {
+        let (mut world, mut schedule) =
+            visibility_test_world((check_visibility, sort_visible_entities).chain());
+
+        let camera = world
+            .spawn((
+                Camera::default(),
+                SortVisibleEntities,
+                GlobalTransform::IDENTITY,
+                Projection::default().compute_frustum(&GlobalTransform::IDENTITY),
+            ))
+            .id();
+        let spawn_bounded_mesh = |world: &mut World, z: f32| {
+            spawn_mesh(world, z)
+                .insert(Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5)))
+                .id()
+        };
+        let middle = spawn_bounded_mesh(&mut world, -5.0);
+        let far = spawn_bounded_mesh(&mut world, -10.0);
+        let near = spawn_bounded_mesh(&mut world, -1.0);
+
+        schedule.run(&mut world);
+
+        let visible_entities = world.get::<VisibleEntities>(camera).unwrap();
+        assert_eq!(
+            visible_entities.get(TypeId::of::<Mesh3d>()),
+            &[near, middle, far]
+        );
+    }
+
+    #[test]
+    fn bounding_sphere_frustum_culling() {
+        let (mut world, mut schedule) = visibility_test_world(check_visibility);
+
+        let camera = world
+            .spawn((
+                Camera::default(),
+                Projection::default().compute_frustum(&GlobalTransform::IDENTITY),
+            ))
+            .id();
+        let near = match Projection::default() {
+            Projection::Perspective(perspective) => perspective.near,
+            _ => unreachable!(),
+        };
+        let spawn_sphere = |world: &mut World, radius: f32| {
+            spawn_mesh(world, 1.0)
+                .insert(BoundingSphere::new(Vec3::ZERO, radius))
+                .id()
+        };
+        // Both spheres sit behind the camera; only the larger one reaches past
+        // the near plane into the frustum.
+        let inside = spawn_sphere(&mut world, 1.0 + near + 0.01);
+        let outside = spawn_sphere(&mut world, 1.0 + near - 0.01);
+
+        schedule.run(&mut world);
+
+        let visible_entities = world.get::<VisibleEntities>(camera).unwrap();
+        assert_eq!(visible_entities.get(TypeId::of::<Mesh3d>()), &[inside]);
+        assert!(world.get::<ViewVisibility>(inside).unwrap().get());
+        assert!(!world.get::<ViewVisibility>(outside).unwrap().get());
+    }
+
+    #[test]
+    fn visible_entities_contains() {
+        struct OtherClass;
+
+        let (mut world, mut schedule) = visibility_test_world(check_visibility);
+
+        let camera = world.spawn(Camera::default()).id();
+        let visible = spawn_mesh(&mut world, 0.0).id();
+        let hidden = spawn_mesh(&mut world, 0.0)
+            .insert(InheritedVisibility::HIDDEN)
+            .id();
+
+        schedule.run(&mut world);
+
+        let visible_entities = world.get::<VisibleEntities>(camera).unwrap();
+        assert!(visible_entities.contains(visible, TypeId::of::<Mesh3d>()));
+        assert!(!visible_entities.contains(visible, TypeId::of::<OtherClass>()));
+        assert!(!visible_entities.contains(hidden, TypeId::of::<Mesh3d>()));
+
+        let mut visible_entities = visible_entities.clone();
+        visible_entities.push(hidden, TypeId::of::<OtherClass>());
+        assert!(visible_entities.contains(hidden, TypeId::of::<OtherClass>()));
+        visible_entities.clear(TypeId::of::<OtherClass>());
+        assert!(!visible_entities.contains(hidden, TypeId::of::<OtherClass>()));
+
+        visible_entities.set(TypeId::of::<OtherClass>(), [visible, hidden]);
+        assert_eq!(
+            visible_entities.get(TypeId::of::<OtherClass>()),
+            &[visible, hidden]
+        );
+        assert!(visible_entities.contains(hidden, TypeId::of::<OtherClass>()));
+        assert!(visible_entities.remove(hidden, TypeId::of::<OtherClass>()));
+        assert!(!visible_entities.remove(hidden, TypeId::of::<OtherClass>()));
+        assert!(!visible_entities.contains(hidden, TypeId::of::<OtherClass>()));
+        assert_eq!(visible_entities.get(TypeId::of::<OtherClass>()), &[visible]);
+
+        visible_entities.clear_all();
+        assert!(!visible_entities.contains(visible, TypeId::of::<Mesh3d>()));
+        assert!(!visible_entities.contains(visible, TypeId::of::<OtherClass>()));
+        assert!(visible_entities
+            .lookup
+            .values()
+            .all(EntityHashSet::is_empty));
+    }
+
+    #[test]
+    fn visible_entities_drain() {
+        let mut world = World::new();
+        let entities: Vec<Entity> = (0..4).map(|_| world.spawn_empty().id()).collect();
+        let mut visible_entities = VisibleEntities::default();
+        for &entity in &entities {
+            visible_entities.push(entity, TypeId::of::<Mesh3d>());
+        }
+        let capacity = visible_entities.get_mut(TypeId::of::<Mesh3d>()).capacity();
+
+        let drained: Vec<Entity> = visible_entities.drain(TypeId::of::<Mesh3d>()).collect();
+        assert_eq!(drained, entities);
+        assert_eq!(visible_entities.len(TypeId::of::<Mesh3d>()), 0);
+        assert!(!visible_entities.contains(entities[0], TypeId::of::<Mesh3d>()));
+        assert_eq!(
+            visible_entities.get_mut(TypeId::of::<Mesh3d>()).capacity(),
+            capacity
+        );
+
+        // Entities are removed even if the iterator isn't exhausted.
+        visible_entities.push(entities[0], TypeId::of::<Mesh3d>());
+        visible_entities.push(entities[1], TypeId::of::<Mesh3d>());
+        assert_eq!(
+            visible_entities.drain(TypeId::of::<Mesh3d>()).next_back(),
+            Some(entities[1])
+        );
+        assert!(visible_entities.is_empty(TypeId::of::<Mesh3d>()));
+    }
+
+    #[test]
+    fn no_frustum_culling_per_class() {
+        struct OtherClass;
+
+        let (mut world, mut schedule) = visibility_test_world(check_visibility);
+
+        let camera = world
+            .spawn((
+                Camera::default(),
+                Projection::default().compute_frustum(&GlobalTransform::IDENTITY),
+            ))
+            .id();
+        // Behind the camera, and thus outside of the frustum.
+        let entity = spawn_mesh(&mut world, 10.0)
+            .insert((
+                VisibilityClass(SmallVec::from_vec(vec![
+                    TypeId::of::<Mesh3d>(),
+                    TypeId::of::<OtherClass>(),
+                ])),
+                Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5)),
+                NoFrustumCullingFor::new([TypeId::of::<OtherClass>()]),
+            ))
+            .id();
+
+        schedule.run(&mut world);
+
+        let visible_entities = world.get::<VisibleEntities>(camera).unwrap();
+        assert!(visible_entities.get(TypeId::of::<Mesh3d>()).is_empty());
+        assert_eq!(visible_entities.get(TypeId::of::<OtherClass>()), &[entity]);
+        assert!(world.get::<ViewVisibility>(entity).unwrap().get());
+
+        // `NoFrustumCullingFor` without any classes applies to all of them.
+        world
+            .entity_mut(entity)
+            .insert(NoFrustumCullingFor::default());
+        schedule.run(&mut world);
+
+        let visible_entities = world.get::<VisibleEntities>(camera).unwrap();
+        assert_eq!(visible_entities.get(TypeId::of::<Mesh3d>()), &[entity]);
+        assert_eq!(visible_entities.get(TypeId::of::<OtherClass>()), &[entity]);
+
+        // So does `NoFrustumCulling`.
+        world
+            .entity_mut(entity)
+            .remove::<NoFrustumCullingFor>()
+            .insert(NoFrustumCulling);
+        schedule.run(&mut world);
+
+        let visible_entities = world.get::<VisibleEntities>(camera).unwrap();
+        assert_eq!(visible_entities.get(TypeId::of::<Mesh3d>()), &[entity]);
+        assert_eq!(visible_entities.get(TypeId::of::<OtherClass>()), &[entity]);
+    }
+
     #[test]
     fn ensure_visibility_enum_size() {
         assert_eq!(1, size_of::<Visibility>());
@@ -3,6 +3,7 @@
 
 use core::{
     hash::{Hash, Hasher},
+    mem,
     ops::Range,
 };
 
@@ -147,6 +148,21 @@ pub struct VisibilityRange {
     /// you aren't using crossfading, however, and your meshes aren't centered
     /// around their origins, then this flag may be useful.
     pub use_aabb: bool,
+
+    /// The distance, in world units, that the camera must move past a range
+    /// boundary before the entity toggles between being in and out of range.
+    ///
+    /// While an entity is in range of a view, its range is extended by this
+    /// amount on both ends; while it's out of range, its range is shrunk by
+    /// this amount. This prevents flickering when the camera sits right at a
+    /// boundary.
+    ///
+    /// The GPU fades entities in and out over margins that are moved outward
+    /// by this amount, so that it doesn't discard entities that are still in
+    /// range because of the hysteresis.
+    ///
+    /// Defaults to zero, which toggles exactly at the boundaries.
+    pub hysteresis: f32,
 }
 
 impl Eq for VisibilityRange {}
@@ -160,10 +176,33 @@ impl Hash for VisibilityRange {
         FloatOrd(self.start_margin.end).hash(state);
         FloatOrd(self.end_margin.start).hash(state);
         FloatOrd(self.end_margin.end).hash(state);
+        FloatOrd(self.hysteresis).hash(state);
     }
 }
 
 impl VisibilityRange {
+    /// Creates a new visibility range that fades in over `start_margin` and
+    /// fades out over `end_margin`, with no [`hysteresis`](Self::hysteresis).
+    ///
+    /// `start_margin.end` must be less than or equal to `end_margin.start`.
+    #[inline]
+    pub const fn new(start_margin: Range<f32>, end_margin: Range<f32>) -> Self {
+        Self {
+            start_margin,
+            end_margin,
+            use_aabb: false,
+            hysteresis: 0.0,
+        }
+    }
+
+    /// Returns this visibility range with the given
+    /// [`hysteresis`](Self::hysteresis) distance.
+    #[inline]
+    pub const fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
     /// Creates a new *abrupt* visibility range, with no crossfade.
     ///
     /// There will be no crossfade; the object will immediately vanish if the
@@ -173,11 +212,7 @@ impl VisibilityRange {
     /// The `start` value must be less than or equal to the `end` value.
     #[inline]
     pub fn abrupt(start: f32, end: f32) -> Self {
-        Self {
-            start_margin: start..start,
-            end_margin: end..end,
-            use_aabb: false,
-        }
+        Self::new(start..start, end..end)
     }
 
     /// Returns true if both the start and end transitions for this range are
@@ -198,6 +233,43 @@ impl VisibilityRange {
         camera_distance >= self.start_margin.start && camera_distance < self.end_margin.end
     }
 
+    /// Returns true if the object will be visible at all, given a camera
+    /// `camera_distance` units away, taking [`VisibilityRange::hysteresis`]
+    /// into account.
+    ///
+    /// `was_visible` is whether the object was visible at all from the same
+    /// camera on the previous frame. If so, the range is extended by the
+    /// hysteresis distance; otherwise, it's shrunk by that distance.
+    #[inline]
+    pub fn is_visible_at_all_with_hysteresis(
+        &self,
+        camera_distance: f32,
+        was_visible: bool,
+    ) -> bool {
+        let hysteresis = if was_visible {
+            self.hysteresis
+        } else {
+            -self.hysteresis
+        };
+        camera_distance >= self.start_margin.start - hysteresis
+            && camera_distance < self.end_margin.end + hysteresis
+    }
+
+    /// Returns the range that the shader fades the object in and out over, as
+    /// stored in the [`RenderVisibilityRanges`] buffer.
+    fn lod_range(&self) -> Vec4 {
+        // The view may keep the object in range for up to the hysteresis
+        // distance past either end of the range, so move the margins outward
+        // by that much. Otherwise, the shader would dither such objects out
+        // entirely.
+        vec4(
+            self.start_margin.start - self.hysteresis,
+            self.start_margin.end - self.hysteresis,
+            self.end_margin.start + self.hysteresis,
+            self.end_margin.end + self.hysteresis,
+        )
+    }
+
     /// Returns true if the object is completely invisible, given a camera
     /// `camera_distance` units away.
     ///
@@ -223,7 +295,8 @@ pub struct RenderVisibilityRanges {
     /// The GPU buffer that stores [`VisibilityRange`]s.
     ///
     /// Each [`Vec4`] contains the start margin start, start margin end, end
-    /// margin start, and end margin end distances, in that order.
+    /// margin start, and end margin end distances, in that order, with the
+    /// margins moved outward by the [`VisibilityRange::hysteresis`].
     buffer: BufferVec<Vec4>,
 
     /// True if the buffer has been changed since the last frame and needs to be
@@ -270,13 +343,8 @@ impl RenderVisibilityRanges {
             .range_to_index
             .entry(visibility_range.clone())
             .or_insert_with(|| {
-                NonMaxU16::try_from(self.buffer.push(vec4(
-                    visibility_range.start_margin.start,
-                    visibility_range.start_margin.end,
-                    visibility_range.end_margin.start,
-                    visibility_range.end_margin.end,
-                )) as u16)
-                .unwrap_or_default()
+                NonMaxU16::try_from(self.buffer.push(visibility_range.lod_range()) as u16)
+                    .unwrap_or_default()
             });
 
         self.entities.insert(
@@ -337,11 +405,24 @@ pub struct VisibleEntityRanges {
     /// A 0 bit for a view corresponds to "out of range"; a 1 bit corresponds to
     /// "in range".
     entities: EntityHashMap<u32>,
+
+    /// The contents of `views` from the previous frame.
+    ///
+    /// This is used to apply [`VisibilityRange::hysteresis`].
+    previous_views: EntityHashMap<u8>,
+
+    /// The contents of `entities` from the previous frame.
+    ///
+    /// This is used to apply [`VisibilityRange::hysteresis`].
+    previous_entities: EntityHashMap<u32>,
 }
 
 impl VisibleEntityRanges {
-    /// Clears out the [`VisibleEntityRanges`] in preparation for a new frame.
+    /// Clears out the [`VisibleEntityRanges`] in preparation for a new frame,
+    /// retaining the current tables as the previous frame's.
     fn clear(&mut self) {
+        mem::swap(&mut self.views, &mut self.previous_views);
+        mem::swap(&mut self.entities, &mut self.previous_entities);
         self.views.clear();
         self.entities.clear();
     }
@@ -395,20 +476,28 @@ pub fn check_visibility_ranges(
         return;
     }
 
-    // Assign an index to each view.
+    // Assign an index to each view, and look up the index it had last frame,
+    // if any.
     let mut views = vec![];
     for (view, view_transform) in view_query.iter().take(32) {
         let view_index = views.len() as u8;
         visible_entity_ranges.views.insert(view, view_index);
-        views.push((view, view_transform.translation_vec3a()));
+        let previous_view_index = visible_entity_ranges.previous_views.get(&view).copied();
+        views.push((
+            view,
+            view_transform.translation_vec3a(),
+            previous_view_index,
+        ));
     }
+    let previous_entities = &visible_entity_ranges.previous_entities;
 
     // Check each entity/view pair. Only consider entities with
     // [`VisibilityRange`] components.
     entity_query.par_iter().for_each(
         |(entity, entity_transform, maybe_model_aabb, visibility_range)| {
             let mut visibility = 0;
-            for (view_index, &(_, view_position)) in views.iter().enumerate() {
+            let previous_visibility = previous_entities.get(&entity).copied().unwrap_or_default();
+            for (view_index, &(_, view_position, previous_view_index)) in views.iter().enumerate() {
                 // If instructed to use the AABB and the model has one, use its
                 // center as the model position. Otherwise, use the model's
                 // translation.
@@ -419,7 +508,12 @@ pub fn check_visibility_ranges(
                     _ => entity_transform.translation_vec3a(),
                 };
 
-                if visibility_range.is_visible_at_all((view_position - model_position).length()) {
+                let was_visible = previous_view_index
+                    .is_some_and(|index| (previous_visibility & (1 << index)) != 0);
+                if visibility_range.is_visible_at_all_with_hysteresis(
+                    (view_position - model_position).length(),
+                    was_visible,
+                ) {
                     visibility |= 1 << view_index;
                 }
             }
@@ -499,3 +593,79 @@ pub fn write_render_visibility_ranges(
         .write_buffer(&render_device, &render_queue);
     render_visibility_ranges.buffer_dirty = false;
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use bevy_math::Vec3;
+
+    use super::*;
+
+    #[test]
+    fn visibility_range_hysteresis() {
+        let mut world = World::new();
+        world.init_resource::<VisibleEntityRanges>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(check_visibility_ranges);
+
+        let view = world
+            .spawn((Camera::default(), GlobalTransform::IDENTITY))
+            .id();
+        let entity = world
+            .spawn((
+                GlobalTransform::IDENTITY,
+                VisibilityRange::abrupt(0.0, 10.0).with_hysteresis(0.5),
+            ))
+            .id();
+
+        let mut is_in_range_at = |world: &mut World, distance: f32| {
+            *world.get_mut::<GlobalTransform>(view).unwrap() =
+                GlobalTransform::from_translation(Vec3::new(0.0, 0.0, distance));
+            schedule.run(world);
+            world
+                .resource::<VisibleEntityRanges>()
+                .entity_is_in_range_of_view(entity, view)
+        };
+
+        assert!(is_in_range_at(&mut world, 9.0));
+        // Oscillating across the boundary within the margin doesn't toggle.
+        assert!(is_in_range_at(&mut world, 10.1));
+        assert!(is_in_range_at(&mut world, 9.9));
+        assert!(is_in_range_at(&mut world, 10.4));
+        // Moving past the margin does.
+        assert!(!is_in_range_at(&mut world, 10.6));
+        assert!(!is_in_range_at(&mut world, 9.9));
+        assert!(!is_in_range_at(&mut world, 10.1));
+        assert!(!is_in_range_at(&mut world, 9.6));
+        assert!(is_in_range_at(&mut world, 9.4));
+    }
+
+    #[test]
+    fn render_visibility_range_hysteresis() {
+        let without_hysteresis = VisibilityRange::new(2.0..4.0, 10.0..12.0);
+        let visibility_range = without_hysteresis.clone().with_hysteresis(0.5);
+        let [entity, other_entity] =
+            [1, 2].map(|index| MainEntity::from(Entity::from_raw_u32(index).unwrap()));
+        let mut render_visibility_ranges = RenderVisibilityRanges::default();
+        render_visibility_ranges.insert(entity, &visibility_range);
+        render_visibility_ranges.insert(other_entity, &without_hysteresis);
+
+        // The ranges only differ in their hysteresis, but are uploaded
+        // separately.
+        assert_eq!(render_visibility_ranges.buffer().len(), 2);
+        assert_ne!(
+            render_visibility_ranges.lod_index_for_entity(entity),
+            render_visibility_ranges.lod_index_for_entity(other_entity)
+        );
+        assert_eq!(without_hysteresis.lod_range(), vec4(2.0, 4.0, 10.0, 12.0));
+        let lod_range = visibility_range.lod_range();
+        assert_eq!(lod_range, vec4(1.5, 3.5, 10.5, 12.5));
+
+        // The shader doesn't discard the entity anywhere the view keeps it in
+        // range, which is outside of `lod_range.x..lod_range.w`.
+        for distance in [1.6, 2.0, 11.9, 12.4] {
+            assert!(visibility_range.is_visible_at_all_with_hysteresis(distance, true));
+            assert!(distance >= lod_range.x && distance < lod_range.w);
+        }
+    }
+}
@@ -1,7 +1,12 @@
-use bevy_ecs::prelude::{Component, ReflectComponent};
+use bevy_ecs::{
+    prelude::{Component, Entity, Query, ReflectComponent, With},
+    system::SystemParam,
+};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use smallvec::SmallVec;
 
+use crate::camera::Camera;
+
 pub const DEFAULT_LAYERS: &RenderLayers = &RenderLayers::layer(0);
 
 /// An identifier for a rendering layer.
@@ -58,9 +63,10 @@ impl Default for RenderLayers {
 impl RenderLayers {
     /// Create a new `RenderLayers` belonging to the given layer.
     ///
-    /// This `const` constructor is limited to `size_of::<usize>()` layers.
+    /// This `const` constructor is limited to the first 64 layers, which are stored inline.
     /// If you need to support an arbitrary number of layers, use [`with`](RenderLayers::with)
-    /// or [`from_layers`](RenderLayers::from_layers).
+    /// or [`from_layers`](RenderLayers::from_layers), which move the layers to the heap when
+    /// needed.
     pub const fn layer(n: Layer) -> Self {
         let (buffer_index, bit) = Self::layer_info(n);
         assert!(
@@ -185,6 +191,13 @@ impl RenderLayers {
         self.combine_blocks(other, |a, b| a | b) // doesn't need to be shrunk, if the inputs are nonzero then the result will be too
     }
 
+    /// Returns the [layers](Layer) of `self` that aren't included in `other`.
+    ///
+    /// This corresponds to the `self & !other` operation.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine_blocks(other, |a, b| a & !b).shrink()
+    }
+
     /// Returns all [layers](Layer) included in exactly one of the instances of [`RenderLayers`].
     ///
     /// This corresponds to the "exclusive or" (XOR) operation: `self ^ other`.
@@ -247,6 +260,38 @@ impl core::ops::BitXor for RenderLayers {
     }
 }
 
+/// A [`SystemParam`] that finds the cameras whose [`RenderLayers`] intersect those of an entity.
+///
+/// This is mostly useful for debugging why an entity isn't being rendered. Only the render
+/// layers are compared: the entity may still be hidden from a returned camera by its
+/// [`Visibility`](super::Visibility), frustum culling, or the camera being inactive.
+#[derive(SystemParam)]
+pub struct VisibleToCameras<'w, 's> {
+    cameras: Query<'w, 's, (Entity, Option<&'static RenderLayers>), With<Camera>>,
+    render_layers: Query<'w, 's, Option<&'static RenderLayers>>,
+}
+
+impl VisibleToCameras<'_, '_> {
+    /// Returns every camera whose [`RenderLayers`] intersect those of the given entity.
+    ///
+    /// Entities and cameras without a [`RenderLayers`] component belong to layer `0`.
+    /// Returns an empty list if the entity doesn't exist.
+    pub fn cameras_seeing(&self, entity: Entity) -> Vec<Entity> {
+        let Ok(entity_layers) = self.render_layers.get(entity) else {
+            return Vec::new();
+        };
+        let entity_layers = entity_layers.unwrap_or_default();
+
+        self.cameras
+            .iter()
+            .filter(|(_, camera_layers)| {
+                camera_layers.unwrap_or_default().intersects(entity_layers)
+            })
+            .map(|(camera, _)| camera)
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod rendering_mask_tests {
     use super::{Layer, RenderLayers};
@@ -353,6 +398,35 @@ mod rendering_mask_tests {
         assert_eq!(RenderLayers::none() ^ MANY, MANY);
     }
 
+    #[test]
+    fn render_layer_set_operations() {
+        let none = RenderLayers::none();
+        let single = RenderLayers::layer(3);
+        let multi_word = RenderLayers::from_layers(&[3, 5, 70, 130]);
+
+        assert_eq!(none.iter().count(), 0);
+        assert_eq!(single.iter().collect::<Vec<_>>(), vec![3]);
+        assert_eq!(multi_word.iter().collect::<Vec<_>>(), vec![3, 5, 70, 130]);
+
+        assert_eq!(none.union(&single), single);
+        assert_eq!(none.intersection(&multi_word), none);
+        assert_eq!(none.difference(&multi_word), none);
+        assert_eq!(multi_word.difference(&none), multi_word);
+
+        assert_eq!(single.union(&multi_word), multi_word);
+        assert_eq!(single.intersection(&multi_word), single);
+        assert_eq!(single.difference(&multi_word), none);
+        assert_eq!(
+            multi_word.difference(&single),
+            RenderLayers::from_layers(&[5, 70, 130])
+        );
+
+        // Removing the layers of the upper memory blocks drops them.
+        let low = multi_word.difference(&RenderLayers::from_layers(&[70, 130]));
+        assert_eq!(low, RenderLayers::from_layers(&[3, 5]));
+        assert_eq!(low.bits().len(), 1);
+    }
+
     #[test]
     fn render_layer_shrink() {
         // Since it has layers greater than 64, the instance should take up two memory blocks
@@ -363,9 +437,55 @@ mod rendering_mask_tests {
         assert!(layers.0.len() == 1);
     }
 
+    #[test]
+    fn render_layers_past_inline_blocks() {
+        let low = RenderLayers::layer(0);
+        let high = RenderLayers::layer(0).with(64);
+        assert!(high.0.spilled(), "layer 64 doesn't fit inline");
+        assert_eq!(high.iter().collect::<Vec<_>>(), vec![0, 64]);
+
+        // Instances with a different number of memory blocks are compared both ways.
+        assert!(high.intersects(&RenderLayers::from_layers(&[64])));
+        assert!(RenderLayers::from_layers(&[64]).intersects(&high));
+        assert!(low.intersects(&high) && high.intersects(&low));
+        let only_high = high.clone().without(0);
+        assert!(!only_high.intersects(&low) && !low.intersects(&only_high));
+        assert!(!RenderLayers::layer(1).intersects(&RenderLayers::from_layers(&[65])));
+
+        // Removing a layer past the last memory block is a no-op.
+        assert_eq!(low.clone().without(200), low);
+        // Removing the last layer past the inline block moves the layers back inline.
+        let high = high.without(64);
+        assert_eq!(high, low);
+        assert!(!high.0.spilled());
+    }
+
     #[test]
     fn render_layer_iter_no_overflow() {
         let layers = RenderLayers::from_layers(&[63]);
         layers.iter().count();
     }
+
+    #[test]
+    fn visible_to_cameras() {
+        use super::VisibleToCameras;
+        use crate::camera::Camera;
+        use bevy_ecs::{system::SystemState, world::World};
+
+        let mut world = World::new();
+        let camera_0 = world.spawn(Camera::default()).id();
+        let camera_1 = world
+            .spawn((Camera::default(), RenderLayers::layer(1)))
+            .id();
+        let entity = world.spawn(RenderLayers::layer(1)).id();
+        let default_entity = world.spawn_empty().id();
+
+        let mut system_state = SystemState::<VisibleToCameras>::new(&mut world);
+        let visible_to_cameras = system_state.get(&world);
+        assert_eq!(vec![camera_1], visible_to_cameras.cameras_seeing(entity));
+        assert_eq!(
+            vec![camera_0],
+            visible_to_cameras.cameras_seeing(default_entity)
+        );
+    }
 }
@@ -0,0 +1,121 @@
+//! Helpers for testing [`Visibility`] propagation.
+//!
+//! These let crates that build their own hierarchies on top of [`ChildOf`] check that
+//! [`InheritedVisibility`] propagates through them as expected, without reimplementing the
+//! setup used by Bevy's own tests.
+//!
+//! This module is only available with the `visibility_testing` feature, which is intended to be
+//! enabled for `dev-dependencies` only.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_render::view::{visibility::testing::*, Visibility};
+//! let mut world = World::new();
+//!
+//! // A hidden root with a child, which has a visible grandchild.
+//! let entities = build_visibility_hierarchy(
+//!     &mut world,
+//!     &[
+//!         (Visibility::Hidden, None),
+//!         (Visibility::Inherited, Some(0)),
+//!         (Visibility::Visible, Some(1)),
+//!     ],
+//! );
+//!
+//! propagate_visibility(&mut world);
+//! assert_inherited_visibility(&world, &entities, &[false, false, true]);
+//! ```
+
+use bevy_ecs::{hierarchy::ChildOf, prelude::*, system::RunSystemOnce};
+
+use super::{visibility_propagate_system, InheritedVisibility, Visibility};
+
+/// Spawns a hierarchy of entities with the given [`Visibility`], returning them in order.
+///
+/// Each node is a [`Visibility`] and the index of its parent among the preceding nodes, or
+/// `None` for roots. Children are attached to their parents with [`ChildOf`].
+///
+/// # Panics
+///
+/// Panics if a node's parent index doesn't refer to a preceding node.
+pub fn build_visibility_hierarchy(
+    world: &mut World,
+    nodes: &[(Visibility, Option<usize>)],
+) -> Vec<Entity> {
+    build_visibility_hierarchy_with(world, nodes, |world, child, parent| {
+        world.entity_mut(child).insert(ChildOf(parent));
+    })
+}
+
+/// Spawns a hierarchy like [`build_visibility_hierarchy`], but calls `link` with each child and
+/// its parent to attach them.
+///
+/// This can be used to test relationships that maintain [`ChildOf`] themselves, for example
+/// through hooks or observers.
+///
+/// # Panics
+///
+/// Panics if a node's parent index doesn't refer to a preceding node.
+pub fn build_visibility_hierarchy_with(
+    world: &mut World,
+    nodes: &[(Visibility, Option<usize>)],
+    mut link: impl FnMut(&mut World, Entity, Entity),
+) -> Vec<Entity> {
+    let mut entities = Vec::with_capacity(nodes.len());
+    for (index, &(visibility, parent)) in nodes.iter().enumerate() {
+        let entity = world.spawn(visibility).id();
+        if let Some(parent) = parent {
+            assert!(
+                parent < index,
+                "the parent of node {index} must be one of the preceding nodes, but is node {parent}"
+            );
+            link(world, entity, entities[parent]);
+        }
+        entities.push(entity);
+    }
+    entities
+}
+
+/// Runs the system that propagates [`Visibility`] to [`InheritedVisibility`] once.
+///
+/// The system is initialized anew on every call, so every entity is treated as changed.
+/// To test change detection across several frames, add the system to a schedule with
+/// [`visibility_propagation_schedule`] instead.
+pub fn propagate_visibility(world: &mut World) {
+    world
+        .run_system_once(visibility_propagate_system)
+        .expect("visibility propagation should not fail");
+}
+
+/// Returns a [`Schedule`] running the system that propagates [`Visibility`] to
+/// [`InheritedVisibility`].
+pub fn visibility_propagation_schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.add_systems(visibility_propagate_system);
+    schedule
+}
+
+/// Asserts that the [`InheritedVisibility`] of each entity matches the expected visibility.
+///
+/// # Panics
+///
+/// Panics if the lengths of `entities` and `expected` differ, if an entity has no
+/// [`InheritedVisibility`], or if any visibility doesn't match.
+#[track_caller]
+pub fn assert_inherited_visibility(world: &World, entities: &[Entity], expected: &[bool]) {
+    assert_eq!(
+        entities.len(),
+        expected.len(),
+        "expected a visibility for every entity"
+    );
+    for (index, (&entity, &expected)) in entities.iter().zip(expected).enumerate() {
+        let visible = world
+            .get::<InheritedVisibility>(entity)
+            .unwrap_or_else(|| panic!("node {index} ({entity}) has no `InheritedVisibility`"))
+            .get();
+        assert_eq!(
+            expected, visible,
+            "node {index} ({entity}) should have an inherited visibility of {expected}"
+        );
+    }
+}
@@ -15,6 +15,7 @@ mod mesh2d;
 mod picking_backend;
 mod render;
 mod sprite;
+mod texture_atlas_animation;
 mod texture_slice;
 
 /// The sprite prelude.
@@ -29,6 +30,7 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         sprite::{Sprite, SpriteImageMode},
+        texture_atlas_animation::TextureAtlasAnimation,
         texture_slice::{BorderRect, SliceScaleMode, TextureSlice, TextureSlicer},
         ColorMaterial, MeshMaterial2d, ScalingMode,
     };
@@ -39,6 +41,7 @@ pub use mesh2d::*;
 pub use picking_backend::*;
 pub use render::*;
 pub use sprite::*;
+pub use texture_atlas_animation::*;
 pub use texture_slice::*;
 
 use bevy_app::prelude::*;
@@ -100,11 +103,13 @@ impl Plugin for SpritePlugin {
             .register_type::<TextureSlicer>()
             .register_type::<Anchor>()
             .register_type::<Mesh2d>()
+            .register_type::<TextureAtlasAnimation>()
             .add_plugins((Mesh2dRenderPlugin, ColorMaterialPlugin))
             .add_systems(
                 PostUpdate,
                 (
                     calculate_bounds_2d.in_set(VisibilitySystems::CalculateBounds),
+                    animate_sprite_texture_atlases.before(SpriteSystems::ComputeSlices),
                     (
                         compute_slices_on_asset_event.before(AssetEventSystems),
                         compute_slices_on_sprite_change,
@@ -0,0 +1,194 @@
+use core::{ops::Range, time::Duration};
+
+use bevy_ecs::{
+    component::Component,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_reflect::Reflect;
+use bevy_time::{Time, Timer, TimerMode};
+
+use crate::Sprite;
+
+/// Plays a [`TextureAtlas`](bevy_image::TextureAtlas) animation, by advancing its index through
+/// a range of frames at a fixed rate.
+///
+/// When added to an entity with a [`Sprite`], the index of the sprite's texture atlas is updated
+/// by [`animate_sprite_texture_atlases`]. UI images are animated the same way by `bevy_ui`.
+/// Entities without a texture atlas aren't affected.
+///
+/// If the current index isn't part of [`frames`](Self::frames), the animation starts over from
+/// the first frame.
+///
+/// ```
+/// # use bevy_sprite::TextureAtlasAnimation;
+/// // Play the frames 4 to 7 of the atlas at 10 frames per second, over and over.
+/// let walk = TextureAtlasAnimation::new(4..8, 10.0, true);
+/// ```
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct TextureAtlasAnimation {
+    /// The atlas indices of the frames of the animation, played in order.
+    pub frames: Range<usize>,
+    /// Whether the animation starts over from the first frame after the last one, instead of
+    /// stopping on the last frame.
+    pub repeat: bool,
+    fps: f32,
+    timer: Timer,
+}
+
+impl TextureAtlasAnimation {
+    /// Creates an animation that plays `frames` at `fps` frames per second, either looping or
+    /// stopping on the last frame depending on `repeat`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` isn't strictly positive and finite.
+    pub fn new(frames: Range<usize>, fps: f32, repeat: bool) -> Self {
+        Self {
+            frames,
+            repeat,
+            fps,
+            timer: Self::frame_timer(fps),
+        }
+    }
+
+    /// Returns the number of frames shown per second.
+    pub fn fps(&self) -> f32 {
+        self.fps
+    }
+
+    /// Changes the number of frames shown per second, restarting the current frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` isn't strictly positive and finite.
+    pub fn set_fps(&mut self, fps: f32) {
+        self.fps = fps;
+        self.timer = Self::frame_timer(fps);
+    }
+
+    fn frame_timer(fps: f32) -> Timer {
+        assert!(
+            fps > 0.0 && fps.is_finite(),
+            "the frame rate of a texture atlas animation must be positive and finite, got {fps}"
+        );
+        Timer::new(
+            Duration::from_secs_f64(1.0 / f64::from(fps)),
+            TimerMode::Repeating,
+        )
+    }
+
+    /// Advances the animation by `delta`, and returns the atlas index to show next, given the
+    /// atlas index that is currently shown.
+    ///
+    /// Several frames are skipped if `delta` is longer than a frame. If [`frames`](Self::frames)
+    /// is empty, `index` is returned unchanged.
+    pub fn advance(&mut self, delta: Duration, index: usize) -> usize {
+        if self.frames.is_empty() {
+            return index;
+        }
+        let index = if self.frames.contains(&index) {
+            index
+        } else {
+            self.timer.reset();
+            self.frames.start
+        };
+
+        self.timer.tick(delta);
+        let frame = index - self.frames.start + self.timer.times_finished_this_tick() as usize;
+        let frame_count = self.frames.len();
+        let frame = if self.repeat {
+            frame % frame_count
+        } else {
+            frame.min(frame_count - 1)
+        };
+        self.frames.start + frame
+    }
+
+    /// Returns `true` if the animation doesn't repeat, and `index` is its last frame.
+    pub fn is_finished(&self, index: usize) -> bool {
+        !self.repeat && index + 1 == self.frames.end
+    }
+}
+
+/// Updates the texture atlas index of [`Sprite`]s with a [`TextureAtlasAnimation`].
+pub fn animate_sprite_texture_atlases(
+    time: Res<Time>,
+    mut sprites: Query<(&mut TextureAtlasAnimation, &mut Sprite)>,
+) {
+    for (mut animation, mut sprite) in &mut sprites {
+        let Some(index) = sprite.texture_atlas.as_ref().map(|atlas| atlas.index) else {
+            continue;
+        };
+        let next_index = animation.advance(time.delta(), index);
+        // Avoid marking the sprite as changed when the frame stays the same.
+        if next_index != index {
+            if let Some(atlas) = &mut sprite.texture_atlas {
+                atlas.index = next_index;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_image::TextureAtlas;
+
+    fn indices(animation: TextureAtlasAnimation, start: usize, steps: usize) -> Vec<usize> {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let sprite = world
+            .spawn((
+                animation,
+                Sprite {
+                    texture_atlas: Some(TextureAtlas::default().with_index(start)),
+                    ..Default::default()
+                },
+            ))
+            .id();
+
+        (0..steps)
+            .map(|_| {
+                world
+                    .resource_mut::<Time>()
+                    .advance_by(Duration::from_millis(100));
+                world
+                    .run_system_once(animate_sprite_texture_atlases)
+                    .unwrap();
+                let sprite = world.get::<Sprite>(sprite).unwrap();
+                sprite.texture_atlas.as_ref().unwrap().index
+            })
+            .collect()
+    }
+
+    #[test]
+    fn repeating_animation_loops() {
+        let animation = TextureAtlasAnimation::new(2..5, 10.0, true);
+        assert_eq!(indices(animation, 2, 7), [3, 4, 2, 3, 4, 2, 3]);
+    }
+
+    #[test]
+    fn animation_stops_on_last_frame() {
+        let animation = TextureAtlasAnimation::new(2..5, 10.0, false);
+        assert!(animation.is_finished(4));
+        assert!(!animation.is_finished(3));
+        assert_eq!(indices(animation, 2, 5), [3, 4, 4, 4, 4]);
+    }
+
+    #[test]
+    fn animation_skips_frames_on_long_deltas() {
+        // At 20 frames per second, each 100ms step shows two frames.
+        let animation = TextureAtlasAnimation::new(0..5, 20.0, true);
+        assert_eq!(indices(animation, 0, 4), [2, 4, 1, 3]);
+    }
+
+    #[test]
+    fn animation_starts_from_first_frame() {
+        // The index starts out of the range, so the animation jumps to its first frame.
+        let animation = TextureAtlasAnimation::new(10..13, 5.0, true);
+        assert_eq!(indices(animation, 0, 5), [10, 11, 11, 12, 12]);
+    }
+}
@@ -6,6 +6,12 @@ use bevy_ecs::{entity::Entity, hierarchy::ChildOf, system::EntityCommands, world
 
 /// Collection of methods similar to the built-in parenting methods on [`EntityWorldMut`] and [`EntityCommands`], but preserving each
 /// entity's [`GlobalTransform`].
+///
+/// The new [`Transform`] is computed from the [`GlobalTransform`]s last computed by transform
+/// propagation, which runs in [`PostUpdate`](bevy_app::PostUpdate). If the entity or its new parent
+/// was spawned or moved since then, their [`GlobalTransform`]s are out of date, and so is the
+/// resulting [`Transform`]. If either entity doesn't have a [`GlobalTransform`], the [`Transform`]
+/// is left unchanged.
 pub trait BuildChildrenTransformExt {
     /// Change this entity's parent while preserving this entity's [`GlobalTransform`]
     /// by updating its [`Transform`].
@@ -78,3 +84,74 @@ impl BuildChildrenTransformExt for EntityWorldMut<'_> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{prelude::*, world::CommandQueue};
+    use bevy_math::{Quat, Vec3};
+    use bevy_tasks::{ComputeTaskPool, TaskPool};
+
+    use super::BuildChildrenTransformExt;
+    use crate::{prelude::*, systems::*};
+
+    fn propagate(world: &mut World) {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                mark_dirty_trees,
+                sync_simple_transforms,
+                propagate_parent_transforms,
+            )
+                .chain(),
+        );
+        schedule.run(world);
+    }
+
+    #[test]
+    fn reparent_in_place_keeps_world_position() {
+        let mut world = World::new();
+        let old_parent = world
+            .spawn(Transform::from_xyz(1.0, 2.0, 3.0).with_scale(Vec3::splat(2.0)))
+            .id();
+        let new_parent = world
+            .spawn(
+                Transform::from_xyz(-4.0, 0.0, 1.0)
+                    .with_rotation(Quat::from_rotation_y(1.0))
+                    .with_scale(Vec3::splat(0.5)),
+            )
+            .id();
+        let child = world
+            .spawn((Transform::from_xyz(0.5, 0.5, 0.5), ChildOf(old_parent)))
+            .id();
+        propagate(&mut world);
+        let global_transform = *world.get::<GlobalTransform>(child).unwrap();
+
+        let mut command_queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut command_queue, &world);
+        commands.entity(child).set_parent_in_place(new_parent);
+        command_queue.apply(&mut world);
+        propagate(&mut world);
+
+        assert_eq!(world.get::<ChildOf>(child), Some(&ChildOf(new_parent)));
+        assert!(world
+            .get::<GlobalTransform>(child)
+            .unwrap()
+            .affine()
+            .abs_diff_eq(global_transform.affine(), 1e-5));
+
+        world.entity_mut(child).remove_parent_in_place();
+        propagate(&mut world);
+
+        assert!(world.get::<ChildOf>(child).is_none());
+        let transform = world.get::<Transform>(child).unwrap();
+        assert!(transform
+            .translation
+            .abs_diff_eq(global_transform.translation(), 1e-5));
+        assert!(world
+            .get::<GlobalTransform>(child)
+            .unwrap()
+            .affine()
+            .abs_diff_eq(global_transform.affine(), 1e-5));
+    }
+}
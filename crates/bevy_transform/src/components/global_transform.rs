@@ -1,11 +1,20 @@
-use core::ops::Mul;
+use core::{
+    fmt,
+    ops::{Mul, Range},
+};
 
 use super::Transform;
-use bevy_math::{ops, Affine3A, Dir3, Isometry3d, Mat4, Quat, Vec3, Vec3A};
+use bevy_math::{ops, Affine3A, Dir3, Isometry3d, Mat4, Quat, Ray3d, Vec3, Vec3A};
 use derive_more::derive::From;
 
 #[cfg(all(feature = "bevy_reflect", feature = "serialize"))]
-use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy_reflect::{
+    serde::{
+        DeserializeWithRegistry, ReflectDeserializeWithRegistry, ReflectSerializeWithRegistry,
+        SerializeWithRegistry,
+    },
+    TypeRegistry,
+};
 
 #[cfg(feature = "bevy-support")]
 use bevy_ecs::{component::Component, hierarchy::validate_parent_has_component};
@@ -45,6 +54,14 @@ use {
 /// - [`transform`][transform_example]
 ///
 /// [transform_example]: https://github.com/bevyengine/bevy/blob/latest/examples/transforms/transform.rs
+///
+/// # Serialization
+///
+/// With the `serialize` feature, [`GlobalTransform`] implements serde's traits as its raw affine
+/// matrix. The reflection serializers, which are used for scenes, present it in its decomposed
+/// form instead, as the translation, rotation and scale of the equivalent [`Transform`], which
+/// keeps scene files readable. Any shear in the affine transform is lost when it goes through
+/// reflection serialization.
 #[derive(Debug, PartialEq, Clone, Copy, From)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
@@ -59,10 +76,30 @@ use {
 )]
 #[cfg_attr(
     all(feature = "bevy_reflect", feature = "serialize"),
-    reflect(Serialize, Deserialize)
+    reflect(SerializeWithRegistry, DeserializeWithRegistry)
 )]
 pub struct GlobalTransform(Affine3A);
 
+#[cfg(all(feature = "bevy_reflect", feature = "serialize"))]
+impl SerializeWithRegistry for GlobalTransform {
+    fn serialize<S>(&self, serializer: S, _registry: &TypeRegistry) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(&self.compute_transform(), serializer)
+    }
+}
+
+#[cfg(all(feature = "bevy_reflect", feature = "serialize"))]
+impl<'de> DeserializeWithRegistry<'de> for GlobalTransform {
+    fn deserialize<D>(deserializer: D, _registry: &TypeRegistry) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        <Transform as serde::Deserialize>::deserialize(deserializer).map(GlobalTransform::from)
+    }
+}
+
 macro_rules! impl_local_axis {
     ($pos_name: ident, $neg_name: ident, $axis: ident) => {
         #[doc=core::concat!("Return the local ", core::stringify!($pos_name), " vector (", core::stringify!($axis) ,").")]
@@ -208,6 +245,14 @@ impl GlobalTransform {
     impl_local_axis!(up, down, Y);
     impl_local_axis!(back, forward, Z);
 
+    /// Returns `true` if, and only if, all elements of the underlying affine transformation are
+    /// finite. If any of them is `NaN`, positive or negative infinity, this will return `false`.
+    #[inline]
+    #[must_use]
+    pub fn is_finite(&self) -> bool {
+        self.0.is_finite()
+    }
+
     /// Get the translation as a [`Vec3`].
     #[inline]
     pub fn translation(&self) -> Vec3 {
@@ -274,7 +319,7 @@ impl GlobalTransform {
     /// # use bevy_math::Vec3;
     /// let global_point = Vec3::new(2., 4., 6.);
     /// let global_transform = GlobalTransform::from_xyz(1., 2., 3.);
-    /// let local_point = global_transform.affine().inverse().transform_point3(global_point);
+    /// let local_point = global_transform.inverse_transform_point(global_point);
     /// assert_eq!(local_point, Vec3::new(1., 2., 3.))
     /// ```
     ///
@@ -294,12 +339,105 @@ impl GlobalTransform {
         self.0.transform_point3(point)
     }
 
+    /// Transforms the given world-space `point` into the local space of this transform, undoing
+    /// [`GlobalTransform::transform_point`].
+    ///
+    /// The point is transformed by the inverse of the [affine](Self::affine) transformation. If the
+    /// transform is singular, for example because its scale is zero along an axis, it can't be
+    /// inverted, and the returned point isn't finite.
+    #[inline]
+    pub fn inverse_transform_point(&self, point: Vec3) -> Vec3 {
+        self.0.inverse().transform_point3(point)
+    }
+
+    /// Transforms the given world-space `ray` into the local space of this transform, like
+    /// [`GlobalTransform::inverse_transform_point`] does for points.
+    ///
+    /// The direction of the returned ray is normalized, so distances along it are measured in
+    /// local units rather than world units when the transform is scaled.
+    ///
+    /// Returns `None` if the transform is singular, for example because its scale is zero along
+    /// an axis.
+    #[inline]
+    pub fn inverse_transform_ray(&self, ray: Ray3d) -> Option<Ray3d> {
+        let inverse = self.0.inverse();
+        let origin = inverse.transform_point3(ray.origin);
+        let direction = Dir3::new(inverse.transform_vector3(*ray.direction)).ok()?;
+        origin.is_finite().then_some(Ray3d { origin, direction })
+    }
+
     /// Multiplies `self` with `transform` component by component, returning the
     /// resulting [`GlobalTransform`]
     #[inline]
     pub fn mul_transform(&self, transform: Transform) -> Self {
         Self(self.0 * transform.compute_affine())
     }
+
+    /// Interpolates between `self` and `other` like [`Transform::lerp`], after decomposing both
+    /// into a scale, a rotation and a translation.
+    ///
+    /// `self` is returned when `t` is `0.0`, and `other` when `t` is `1.0`, up to the precision
+    /// of the decomposition. Values of `t` outside of this range extrapolate. As for
+    /// [`GlobalTransform::compute_transform`], both transforms are expected to be
+    /// non-degenerate and without shearing.
+    #[inline]
+    #[must_use]
+    pub fn lerp(&self, other: &GlobalTransform, t: f32) -> GlobalTransform {
+        self.compute_transform()
+            .lerp(&other.compute_transform(), t)
+            .into()
+    }
+
+    /// Returns the view transform for a slice of this view's depth range, such as a shadow
+    /// cascade, along with the depth range of the slice in that view.
+    ///
+    /// `near` and `far` are world-space distances along [`forward`](Self::forward) from this
+    /// transform's origin that bound the slice. The returned transform is this one moved forward
+    /// to the middle of the slice, keeping its rotation and scale, so it stays a rigid view
+    /// transform. The depth of the slice is returned separately, as the distances along the
+    /// local forward axis of the returned transform from its near plane to its far plane. These
+    /// are symmetric around zero, and can be used as the `near` and `far` of an orthographic
+    /// projection for the slice. Successive slices that share their split distances line up end
+    /// to end, each starting where the previous one ends.
+    ///
+    /// Only the depth of the view is fitted: the lateral extent of the slice depends on the
+    /// projection, and is left to the caller.
+    ///
+    /// ```
+    /// # use bevy_transform::prelude::GlobalTransform;
+    /// # use bevy_math::Vec3;
+    /// let view = GlobalTransform::from_xyz(0., 0., 10.);
+    /// let (cascade, depth) = view.cascade_split_transform(4., 12.);
+    /// assert_eq!(cascade.translation(), Vec3::new(0., 0., 2.));
+    /// assert_eq!(cascade.scale(), Vec3::ONE);
+    /// assert_eq!(depth, -4.0..4.0);
+    /// assert_eq!(cascade.transform_point(Vec3::NEG_Z * depth.end), Vec3::new(0., 0., -2.));
+    /// ```
+    #[inline]
+    pub fn cascade_split_transform(&self, near: f32, far: f32) -> (GlobalTransform, Range<f32>) {
+        debug_assert!(
+            near <= far,
+            "the near distance of a cascade must not be greater than its far distance"
+        );
+        let forward = Vec3A::from(*self.forward());
+        let half_depth = 0.5 * (far - near);
+        let mut affine = self.0;
+        affine.translation += forward * (near + half_depth);
+        // The local forward axis may be scaled, so convert the depth to local units.
+        let local_half_depth = half_depth / self.0.matrix3.z_axis.length();
+        (Self(affine), -local_half_depth..local_half_depth)
+    }
+}
+
+/// Formats the transform decomposed like [`Transform`]'s [`Display`](fmt::Display) does.
+///
+/// The decomposition can be lossy, for example for transforms with shear. Use [`Debug`]
+/// to print the exact affine matrix instead.
+impl fmt::Display for GlobalTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (scale, rotation, translation) = self.to_scale_rotation_translation();
+        super::transform::fmt_trs(f, translation, rotation, scale)
+    }
 }
 
 impl Default for GlobalTransform {
@@ -380,6 +518,7 @@ mod test {
             retransformed,
         );
     }
+
     #[test]
     fn reparented_usecase() {
         let t1 = GlobalTransform::from(Transform {
@@ -403,6 +542,146 @@ mod test {
         );
     }
 
+    #[test]
+    fn cascade_split_transforms_nest_in_depth() {
+        let view = GlobalTransform::from(
+            Transform::from_xyz(3.0, -2.0, 5.0)
+                .looking_to(Vec3::new(1.0, -0.5, -1.0), Vec3::Y)
+                .with_scale(Vec3::splat(2.0)),
+        );
+        let splits = [0.0, 4.0, 16.0, 64.0];
+
+        // The world-space point at the given depth in front of the view.
+        let at_depth = |depth: f32| view.translation() + *view.forward() * depth;
+
+        let cascades = [0, 1, 2].map(|i| view.cascade_split_transform(splits[i], splits[i + 1]));
+        for (i, (cascade, depth)) in cascades.iter().enumerate() {
+            // Only the translation changes.
+            assert!(cascade
+                .affine()
+                .matrix3
+                .abs_diff_eq(view.affine().matrix3, 1e-6));
+
+            // The slice spans the depth range along the local forward axis, from its near plane
+            // to its far plane.
+            let local = |point: Vec3| cascade.affine().inverse().transform_point3(point);
+            assert!((-local(at_depth(splits[i])).z - depth.start).abs() < 1e-4);
+            assert!((-local(at_depth(splits[i + 1])).z - depth.end).abs() < 1e-4);
+            let middle = 0.5 * (splits[i] + splits[i + 1]);
+            assert!(local(at_depth(middle)).abs_diff_eq(Vec3::ZERO, 1e-4));
+        }
+
+        // Each cascade starts where the previous one ends.
+        for i in 1..cascades.len() {
+            let (previous, previous_depth) = &cascades[i - 1];
+            let (cascade, depth) = &cascades[i];
+            let previous_far = previous.transform_point(Vec3::NEG_Z * previous_depth.end);
+            let near = cascade.transform_point(Vec3::NEG_Z * depth.start);
+            assert!(previous_far.abs_diff_eq(near, 1e-4));
+            assert!(near.abs_diff_eq(at_depth(splits[i]), 1e-4));
+        }
+    }
+
+    #[test]
+    fn display_decomposed() {
+        let global = GlobalTransform::from(Transform::from_xyz(4.0, 5.5, -6.0));
+        let pretty = alloc::format!("{global:.1}");
+        assert!(pretty.contains("translation: (4.0, 5.5, -6.0)"));
+
+        // `Debug` still prints the matrix.
+        assert!(alloc::format!("{global:?}").contains("matrix3"));
+    }
+
+    #[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+    #[test]
+    fn reflect_serialize_decomposed_round_trip() {
+        use bevy_reflect::{
+            serde::{TypedReflectDeserializer, TypedReflectSerializer},
+            FromReflect, GetTypeRegistration, TypeRegistry,
+        };
+        use serde::de::DeserializeSeed;
+
+        let mut registry = TypeRegistry::default();
+        registry.add_registration(GlobalTransform::get_type_registration());
+
+        let transform = GlobalTransform::from(Transform {
+            translation: Vec3::new(1.0, -2.5, 30.0),
+            rotation: Quat::from_euler(XYZ, 0.3, 1.2, -0.7),
+            scale: Vec3::new(2.0, 0.5, 1.5),
+        });
+
+        let serializer = TypedReflectSerializer::new(&transform, &registry);
+        let output = ron::to_string(&serializer).unwrap();
+        assert!(output.contains("translation"), "{output}");
+        assert!(output.contains("rotation"), "{output}");
+        assert!(output.contains("scale"), "{output}");
+
+        let registration = registry
+            .get(core::any::TypeId::of::<GlobalTransform>())
+            .unwrap();
+        let deserializer = TypedReflectDeserializer::new(registration, &registry);
+        let mut ron_deserializer = ron::de::Deserializer::from_str(&output).unwrap();
+        let reflected = deserializer.deserialize(&mut ron_deserializer).unwrap();
+        let round_tripped = GlobalTransform::from_reflect(reflected.as_partial_reflect()).unwrap();
+
+        assert!(round_tripped.affine().abs_diff_eq(transform.affine(), 1e-5));
+
+        // Serde itself still sees the raw matrix.
+        let output = ron::to_string(&transform).unwrap();
+        assert!(!output.contains("translation"), "{output}");
+        let round_tripped: GlobalTransform = ron::from_str(&output).unwrap();
+        assert_eq!(round_tripped, transform);
+    }
+
+    #[test]
+    fn lerp() {
+        let a = Transform::from_xyz(1.0, 2.0, 3.0)
+            .with_rotation(Quat::from_rotation_x(0.3))
+            .with_scale(Vec3::splat(2.0));
+        let b = Transform::from_xyz(-3.0, 0.0, 5.0).with_rotation(Quat::from_rotation_y(2.0));
+        let (global_a, global_b) = (GlobalTransform::from(a), GlobalTransform::from(b));
+
+        assert!(global_a
+            .lerp(&global_b, 0.0)
+            .affine()
+            .abs_diff_eq(global_a.affine(), 1e-5));
+        assert!(global_a
+            .lerp(&global_b, 1.0)
+            .affine()
+            .abs_diff_eq(global_b.affine(), 1e-5));
+        let mid = global_a.lerp(&global_b, 0.5);
+        let expected = GlobalTransform::from(a.lerp(&b, 0.5));
+        assert!(mid.affine().abs_diff_eq(expected.affine(), 1e-5));
+    }
+
+    #[test]
+    fn inverse_transform_point_and_ray() {
+        let global_transform = GlobalTransform::from(
+            Transform::from_xyz(1.0, -2.0, 3.0)
+                .with_rotation(Quat::from_euler(XYZ, 0.3, -1.2, 2.0))
+                .with_scale(Vec3::new(0.5, 3.0, -2.0)),
+        );
+        for point in [Vec3::ZERO, Vec3::new(4.0, 5.0, -6.0), Vec3::splat(-100.0)] {
+            let world = global_transform.transform_point(point);
+            let local = global_transform.inverse_transform_point(world);
+            assert!(local.abs_diff_eq(point, 1e-4), "{local} != {point}");
+        }
+
+        let local_ray = Ray3d::new(Vec3::new(1.0, 2.0, 3.0), Dir3::X);
+        let world_ray = Ray3d::new(
+            global_transform.transform_point(local_ray.origin),
+            Dir3::new(global_transform.affine().transform_vector3(Vec3::X)).unwrap(),
+        );
+        let ray = global_transform.inverse_transform_ray(world_ray).unwrap();
+        assert!(ray.origin.abs_diff_eq(local_ray.origin, 1e-4));
+        assert!(ray.direction.abs_diff_eq(Vec3::X, 1e-5));
+
+        // A transform with a zero scale can't be inverted.
+        let flat = GlobalTransform::from_scale(Vec3::new(1.0, 0.0, 1.0));
+        assert!(!flat.inverse_transform_point(Vec3::ONE).is_finite());
+        assert!(flat.inverse_transform_ray(local_ray).is_none());
+    }
+
     #[test]
     fn scale() {
         let test_values = [-42.42, 0., 42.42];
@@ -1,6 +1,6 @@
 use super::GlobalTransform;
-use bevy_math::{Affine3A, Dir3, Isometry3d, Mat3, Mat4, Quat, Vec3};
-use core::ops::Mul;
+use bevy_math::{ops, Affine2, Affine3A, Dir3, EulerRot, Isometry3d, Mat3, Mat4, Quat, Vec3};
+use core::{fmt, ops::Mul};
 
 #[cfg(feature = "bevy-support")]
 use bevy_ecs::component::Component;
@@ -171,6 +171,23 @@ impl Transform {
         }
     }
 
+    /// Creates a new [`Transform`] in the XY plane that is equivalent to the given 2D affine
+    /// transformation.
+    ///
+    /// The translation will have a `z` of 0, the rotation will be around the Z axis, and the
+    /// scale will have a `z` of 1. The affine transformation must not contain shearing, or the
+    /// output will be invalid.
+    #[inline]
+    pub fn from_2d(affine: Affine2) -> Self {
+        let (scale, angle, translation) = affine.to_scale_angle_translation();
+
+        Transform {
+            translation: translation.extend(0.0),
+            rotation: Quat::from_rotation_z(angle),
+            scale: scale.extend(1.0),
+        }
+    }
+
     /// Returns this [`Transform`] with a new rotation so that [`Transform::forward`]
     /// points towards the `target` position and [`Transform::up`] points towards `up`.
     ///
@@ -189,7 +206,7 @@ impl Transform {
     /// points in the given `direction` and [`Transform::up`] points towards `up`.
     ///
     /// In some cases it's not possible to construct a rotation. Another axis will be picked in those cases:
-    /// * if `direction` fails converting to `Dir3` (e.g if it is `Vec3::ZERO`), `Dir3::Z` is used instead
+    /// * if `direction` fails converting to `Dir3` (e.g if it is `Vec3::ZERO`), `Dir3::NEG_Z` is used instead
     /// * if `up` fails converting to `Dir3`, `Dir3::Y` is used instead
     /// * if `direction` is parallel with `up`, an orthogonal vector is used as the "right" direction
     #[inline]
@@ -199,6 +216,19 @@ impl Transform {
         self
     }
 
+    /// Returns this [`Transform`] with a new rotation so that [`Transform::forward`] points
+    /// towards the `target` position, turning only around `up` and keeping [`Transform::up`]
+    /// aligned with `up`.
+    ///
+    /// See [`Transform::look_at_yaw_only`] for how targets above or below the transform are
+    /// handled.
+    #[inline]
+    #[must_use]
+    pub fn looking_at_yaw_only(mut self, target: Vec3, up: Dir3) -> Self {
+        self.look_at_yaw_only(target, up);
+        self
+    }
+
     /// Rotates this [`Transform`] so that the `main_axis` vector, reinterpreted in local coordinates, points
     /// in the given `main_direction`, while `secondary_axis` points towards `secondary_direction`.
     /// For example, if a spaceship model has its nose pointing in the X-direction in its own local coordinates
@@ -439,6 +469,25 @@ impl Transform {
         self.translation = point + rotation * (self.translation - point);
     }
 
+    /// Moves this [`Transform`] to the closest point on the line through `origin` in the direction of
+    /// `axis`, leaving its rotation and scale unchanged.
+    ///
+    /// This is useful to keep things on rails, like the player of a side-scroller, after moving them
+    /// freely. If this [`Transform`] has a parent, the `origin` and `axis` are relative to the
+    /// [`Transform`] of the parent.
+    ///
+    /// ```
+    /// # use bevy_math::{Dir3, Vec3};
+    /// # use bevy_transform::components::Transform;
+    /// let mut transform = Transform::from_xyz(4.0, 2.0, -1.0);
+    /// transform.constrain_to_axis(Dir3::X, Vec3::new(0.0, 1.0, 0.0));
+    /// assert_eq!(transform.translation, Vec3::new(4.0, 1.0, 0.0));
+    /// ```
+    #[inline]
+    pub fn constrain_to_axis(&mut self, axis: Dir3, origin: Vec3) {
+        self.translation = origin + (self.translation - origin).project_onto_normalized(*axis);
+    }
+
     /// Rotates this [`Transform`] around a `point` in space.
     ///
     /// If this [`Transform`] has a parent, the `point` is relative to the [`Transform`] of the parent.
@@ -460,6 +509,32 @@ impl Transform {
         self.look_to(target - self.translation, up);
     }
 
+    /// Rotates this [`Transform`] around `up` so that [`Transform::forward`] faces the `target`
+    /// position, while [`Transform::up`] points exactly in the `up` direction.
+    ///
+    /// Unlike [`Transform::look_at`], this only turns the transform horizontally, without pitching
+    /// or rolling it towards a target that is above or below it. This keeps characters that walk on
+    /// the ground upright.
+    ///
+    /// If the `target` is directly above or below the transform translation, the transform keeps
+    /// facing its current horizontal direction.
+    ///
+    /// ```
+    /// # use bevy_math::{Dir3, Vec3};
+    /// # use bevy_transform::components::Transform;
+    /// let mut transform = Transform::IDENTITY;
+    /// transform.look_at_yaw_only(Vec3::new(1.0, 10.0, 0.0), Dir3::Y);
+    /// assert!(transform.forward().abs_diff_eq(Vec3::X, 1e-6));
+    /// assert!(transform.up().abs_diff_eq(Vec3::Y, 1e-6));
+    /// ```
+    #[inline]
+    pub fn look_at_yaw_only(&mut self, target: Vec3, up: Dir3) {
+        let direction = Dir3::new((target - self.translation).reject_from_normalized(*up))
+            .or_else(|_| Dir3::new(self.forward().reject_from_normalized(*up)))
+            .unwrap_or_else(|_| Dir3::new_unchecked(up.any_orthonormal_vector()));
+        self.look_to(direction, up);
+    }
+
     /// Rotates this [`Transform`] so that [`Transform::forward`] points in the given `direction`
     /// and [`Transform::up`] points towards `up`.
     ///
@@ -560,6 +635,51 @@ impl Transform {
         };
     }
 
+    /// Returns the angle in radians, between `0` and `π`, of the shortest rotation that turns
+    /// this [`Transform`]'s rotation into `target_rotation`.
+    ///
+    /// This is useful to tell how far off an orientation is from a target, for example to check
+    /// whether a turret is aimed closely enough to fire. See [`Transform::axis_difference`] for
+    /// the axis of that rotation.
+    ///
+    /// ```
+    /// # use bevy_transform::prelude::Transform;
+    /// # use bevy_math::Quat;
+    /// # use core::f32::consts::FRAC_PI_2;
+    /// let turret = Transform::from_rotation(Quat::from_rotation_y(0.25));
+    /// let target = Quat::from_rotation_y(0.25 + FRAC_PI_2);
+    /// assert!((turret.angular_difference(target) - FRAC_PI_2).abs() < 1e-6);
+    /// ```
+    #[inline]
+    pub fn angular_difference(&self, target_rotation: Quat) -> f32 {
+        self.rotation_difference(target_rotation).to_axis_angle().1
+    }
+
+    /// Returns the axis of the shortest rotation that turns this [`Transform`]'s rotation into
+    /// `target_rotation`, or `None` if both rotations are the same.
+    ///
+    /// Like the axis given to [`Transform::rotate_axis`], it's relative to the rotation of the
+    /// parent, if any: rotating this [`Transform`] with [`Transform::rotate_axis`] around this
+    /// axis by [`Transform::angular_difference`] turns it to `target_rotation`.
+    #[inline]
+    pub fn axis_difference(&self, target_rotation: Quat) -> Option<Dir3> {
+        Dir3::new(self.rotation_difference(target_rotation).xyz()).ok()
+    }
+
+    /// Returns the rotation from this [`Transform`]'s rotation to `target_rotation`, taking the
+    /// shortest way around.
+    #[inline]
+    fn rotation_difference(&self, target_rotation: Quat) -> Quat {
+        let difference = target_rotation * self.rotation.inverse();
+        // `q` and `-q` are the same rotation, but the one with a negative `w` goes the long way
+        // around, by an angle over `π`.
+        if difference.w < 0.0 {
+            -difference
+        } else {
+            difference
+        }
+    }
+
     /// Multiplies `self` with `transform` component by component, returning the
     /// resulting [`Transform`]
     #[inline]
@@ -610,6 +730,112 @@ impl Transform {
     pub fn to_isometry(&self) -> Isometry3d {
         Isometry3d::new(self.translation, self.rotation)
     }
+
+    /// Get the 2D affine transformation defined by this transform in the XY plane,
+    /// ignoring the `z` component of the scale.
+    ///
+    /// Returns `None` if the transform does not stay within the XY plane, meaning its
+    /// translation has a non-zero `z` or its rotation isn't around the Z axis.
+    #[inline]
+    pub fn to_2d(&self) -> Option<Affine2> {
+        const EPSILON: f32 = 1e-6;
+        if self.translation.z != 0.0
+            || self.rotation.x.abs() > EPSILON
+            || self.rotation.y.abs() > EPSILON
+        {
+            return None;
+        }
+
+        let angle = 2.0 * ops::atan2(self.rotation.z, self.rotation.w);
+        Some(Affine2::from_scale_angle_translation(
+            self.scale.truncate(),
+            angle,
+            self.translation.truncate(),
+        ))
+    }
+
+    /// Samples a keyframed animation at time `t`.
+    ///
+    /// `keys` holds the time of each keyframe and its transform, and must be sorted by time.
+    /// The two keyframes surrounding `t` are blended, linearly interpolating their translation
+    /// and scale and spherically interpolating their rotation.
+    ///
+    /// If `t` is outside the range of the keyframes' times, the result depends on `looping`:
+    /// - If `false`, the animation is held at its first keyframe before it starts, and at its
+    ///   last keyframe after it ends.
+    /// - If `true`, the animation repeats, with `t` wrapped into the range from the first
+    ///   keyframe's time to the last one's. The last keyframe is blended back into the first
+    ///   one instantly, so it should usually match the first one to loop smoothly.
+    ///
+    /// A single keyframe is returned as is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` is empty.
+    ///
+    /// ```
+    /// # use bevy_transform::prelude::Transform;
+    /// # use bevy_math::Vec3;
+    /// let keys = [
+    ///     (0.0, Transform::from_xyz(0.0, 0.0, 0.0)),
+    ///     (2.0, Transform::from_xyz(4.0, 0.0, 0.0)),
+    /// ];
+    /// let sample = Transform::sample_keyframes(&keys, 0.5, false);
+    /// assert_eq!(sample.translation, Vec3::new(1.0, 0.0, 0.0));
+    /// ```
+    pub fn sample_keyframes(keys: &[(f32, Transform)], t: f32, looping: bool) -> Transform {
+        let (Some(&(start, first)), Some(&(end, last))) = (keys.first(), keys.last()) else {
+            panic!("at least one keyframe is required to sample an animation");
+        };
+        debug_assert!(
+            keys.windows(2).all(|pair| pair[0].0 <= pair[1].0),
+            "keyframes must be sorted by time"
+        );
+
+        let duration = end - start;
+        let t = if looping && duration > 0.0 {
+            start + ops::rem_euclid(t - start, duration)
+        } else {
+            t
+        };
+        if t <= start {
+            return first;
+        }
+        if t >= end {
+            return last;
+        }
+
+        // The first keyframe after `t`, which exists as `t` is before the last keyframe.
+        let next = keys.partition_point(|&(time, _)| time <= t);
+        let (from_time, from) = keys[next - 1];
+        let (to_time, to) = keys[next];
+        from.lerp(&to, (t - from_time) / (to_time - from_time))
+    }
+
+    /// Interpolates between `self` and `other`, linearly for the translation and scale, and with
+    /// a spherical linear interpolation along the shortest path for the rotation.
+    ///
+    /// `self` is returned when `t` is `0.0`, and `other` when `t` is `1.0`. Values of `t` outside
+    /// of this range extrapolate past either transform, continuing the motion at the same rate.
+    ///
+    /// ```
+    /// # use bevy_transform::prelude::Transform;
+    /// # use bevy_math::{Quat, Vec3};
+    /// let a = Transform::from_xyz(0.0, 0.0, 0.0);
+    /// let b = Transform::from_xyz(2.0, 0.0, 0.0).with_rotation(Quat::from_rotation_y(1.0));
+    /// let mid = a.lerp(&b, 0.5);
+    /// assert_eq!(mid.translation, Vec3::new(1.0, 0.0, 0.0));
+    /// assert!(mid.rotation.abs_diff_eq(Quat::from_rotation_y(0.5), 1e-6));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn lerp(&self, other: &Transform, t: f32) -> Transform {
+        Transform {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.slerp(other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
 }
 
 impl Default for Transform {
@@ -651,6 +877,53 @@ impl Mul<Vec3> for Transform {
     }
 }
 
+/// Formats the transform as its translation, its rotation as XYZ Euler angles in degrees,
+/// and its scale. Formatting options, such as the precision, apply to every component.
+///
+/// ```
+/// # use bevy_transform::prelude::Transform;
+/// let transform = Transform::from_xyz(1.0, 2.0, 3.0);
+/// assert_eq!(
+///     format!("{transform:.1}"),
+///     "translation: (1.0, 2.0, 3.0), rotation: (0.0°, 0.0°, 0.0°), scale: (1.0, 1.0, 1.0)",
+/// );
+/// ```
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_trs(f, self.translation, self.rotation, self.scale)
+    }
+}
+
+/// Writes a decomposed transform in the format of [`Transform`]'s [`Display`](fmt::Display).
+pub(super) fn fmt_trs(
+    f: &mut fmt::Formatter<'_>,
+    translation: Vec3,
+    rotation: Quat,
+    scale: Vec3,
+) -> fmt::Result {
+    fn fmt_vec3(f: &mut fmt::Formatter<'_>, v: Vec3, unit: &str) -> fmt::Result {
+        match f.precision() {
+            Some(p) => write!(
+                f,
+                "({:.p$}{unit}, {:.p$}{unit}, {:.p$}{unit})",
+                v.x, v.y, v.z
+            ),
+            None => write!(f, "({}{unit}, {}{unit}, {}{unit})", v.x, v.y, v.z),
+        }
+    }
+
+    let (x, y, z) = rotation.to_euler(EulerRot::XYZ);
+    // Adding zero turns the negative zeros that `to_euler` can return into positive ones.
+    let euler = Vec3::new(x.to_degrees(), y.to_degrees(), z.to_degrees()) + Vec3::ZERO;
+
+    f.write_str("translation: ")?;
+    fmt_vec3(f, translation, "")?;
+    f.write_str(", rotation: ")?;
+    fmt_vec3(f, euler, "°")?;
+    f.write_str(", scale: ")?;
+    fmt_vec3(f, scale, "")
+}
+
 /// An optimization for transform propagation. This ZST marker component uses change detection to
 /// mark all entities of the hierarchy as "dirty" if any of their descendants have a changed
 /// `Transform`. If this component is *not* marked `is_changed()`, propagation will halt.
@@ -667,3 +940,395 @@ impl Mul<Vec3> for Transform {
     reflect(Serialize, Deserialize)
 )]
 pub struct TransformTreeChanged;
+
+/// A marker component that stops transform propagation from reaching the descendants of an entity.
+///
+/// The [`GlobalTransform`] of the marked entity itself is still updated by propagation, but the
+/// [`GlobalTransform`]s of its descendants aren't, even if their [`Transform`]s change or they are
+/// spawned. This avoids visiting large static hierarchies, such as baked scenery, every time
+/// something changes above them.
+///
+/// Call [`propagate_descendant_transforms`] on the marked entity to update its descendants once,
+/// for example after moving the scenery or spawning it.
+///
+/// [`propagate_descendant_transforms`]: crate::systems::propagate_descendant_transforms
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bevy-support", derive(Component))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Default, PartialEq, Debug)
+)]
+#[cfg_attr(
+    all(feature = "bevy_reflect", feature = "serialize"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct NoTransformPropagation;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Vec2;
+    use core::f32::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn sample_keyframes() {
+        let keys = [
+            (1.0, Transform::from_xyz(0.0, 0.0, 0.0)),
+            (
+                2.0,
+                Transform::from_xyz(2.0, 0.0, 0.0).with_rotation(Quat::from_rotation_y(PI / 2.0)),
+            ),
+            (
+                4.0,
+                Transform::from_xyz(2.0, 4.0, 0.0).with_scale(Vec3::splat(3.0)),
+            ),
+        ];
+
+        // Sampling at a keyframe's time returns that keyframe.
+        for (time, key) in keys {
+            assert_eq!(Transform::sample_keyframes(&keys, time, false), key);
+        }
+
+        // Between keyframes, their transforms are blended.
+        let sample = Transform::sample_keyframes(&keys, 1.5, false);
+        assert_eq!(sample.translation, Vec3::new(1.0, 0.0, 0.0));
+        assert!(sample
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_y(PI / 4.0), 1e-6));
+        assert_eq!(sample.scale, Vec3::ONE);
+        let sample = Transform::sample_keyframes(&keys, 3.0, false);
+        assert_eq!(sample.translation, Vec3::new(2.0, 2.0, 0.0));
+        assert_eq!(sample.scale, Vec3::splat(2.0));
+
+        // Without looping, the animation is held at its ends.
+        assert_eq!(Transform::sample_keyframes(&keys, -5.0, false), keys[0].1);
+        assert_eq!(Transform::sample_keyframes(&keys, 10.0, false), keys[2].1);
+
+        // With looping, the animation repeats in both directions.
+        let looped = Transform::sample_keyframes(&keys, 7.5, true);
+        assert_eq!(looped, Transform::sample_keyframes(&keys, 1.5, false));
+        let looped = Transform::sample_keyframes(&keys, -3.0, true);
+        assert_eq!(looped, Transform::sample_keyframes(&keys, 3.0, false));
+
+        // A single keyframe is returned as is.
+        let single = [(1.0, Transform::from_xyz(1.0, 2.0, 3.0))];
+        assert_eq!(Transform::sample_keyframes(&single, 0.0, true), single[0].1);
+        assert_eq!(
+            Transform::sample_keyframes(&single, 5.0, false),
+            single[0].1
+        );
+    }
+
+    #[test]
+    fn display_decomposed() {
+        let transform = Transform::from_xyz(1.5, -2.0, 30.25)
+            .with_rotation(Quat::from_rotation_y(PI / 4.0))
+            .with_scale(Vec3::splat(2.0));
+
+        let pretty = alloc::format!("{transform:.2}");
+        assert!(pretty.contains("translation: (1.50, -2.00, 30.25)"));
+        assert!(pretty.contains("rotation: (0.00°, 45.00°, 0.00°)"));
+        assert!(pretty.contains("scale: (2.00, 2.00, 2.00)"));
+
+        // `Debug` still prints the exact fields.
+        assert!(alloc::format!("{transform:?}").starts_with("Transform {"));
+    }
+
+    #[test]
+    fn round_trip_2d() {
+        let affine = Affine2::from_scale_angle_translation(
+            Vec2::new(2.0, 0.5),
+            PI / 3.0,
+            Vec2::new(-4.0, 7.5),
+        );
+        let transform = Transform::from_2d(affine);
+        assert_eq!(0.0, transform.translation.z);
+        assert_eq!(1.0, transform.scale.z);
+
+        let round_trip = transform.to_2d().unwrap();
+        assert!(round_trip.abs_diff_eq(affine, 1e-5));
+
+        let point = Vec2::new(3.0, -1.0);
+        assert!(transform
+            .transform_point(point.extend(0.0))
+            .truncate()
+            .abs_diff_eq(affine.transform_point2(point), 1e-5));
+    }
+
+    #[test]
+    fn to_2d_out_of_plane() {
+        assert!(Transform::from_xyz(1.0, 2.0, 3.0).to_2d().is_none());
+        assert!(Transform::from_rotation(Quat::from_rotation_x(0.5))
+            .to_2d()
+            .is_none());
+        assert!(Transform::from_scale(Vec3::new(1.0, 2.0, 3.0))
+            .to_2d()
+            .is_some());
+    }
+
+    #[test]
+    fn rotate_around_non_origin_pivot() {
+        let pivot = Vec3::new(1.0, 0.0, 2.0);
+        let mut transform = Transform::from_xyz(3.0, 1.0, 2.0);
+        transform.rotate_around(pivot, Quat::from_rotation_y(PI));
+
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(-1.0, 1.0, 2.0), 1e-5));
+        assert!(transform.forward().abs_diff_eq(Vec3::Z, 1e-5));
+        assert!(transform.right().abs_diff_eq(Vec3::NEG_X, 1e-5));
+
+        let mut transform = Transform::from_xyz(3.0, 1.0, 2.0);
+        transform.rotate_around(pivot, Quat::from_rotation_x(PI));
+
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(3.0, -1.0, 2.0), 1e-5));
+        assert!(transform.up().abs_diff_eq(Vec3::NEG_Y, 1e-5));
+        assert!(transform.forward().abs_diff_eq(Vec3::Z, 1e-5));
+    }
+
+    #[test]
+    fn look_at_yaw_only() {
+        let mut transform = Transform::from_xyz(1.0, 2.0, 3.0);
+
+        // Targets above and below only turn the transform horizontally.
+        for target in [Vec3::new(1.0, 50.0, -1.0), Vec3::new(1.0, -50.0, -1.0)] {
+            transform.look_at_yaw_only(target, Dir3::Y);
+            assert!(transform.forward().abs_diff_eq(Vec3::NEG_Z, 1e-5));
+            assert!(transform.up().abs_diff_eq(Vec3::Y, 1e-5));
+        }
+
+        transform.look_at_yaw_only(Vec3::new(5.0, -7.0, 3.0), Dir3::Y);
+        assert!(transform.forward().abs_diff_eq(Vec3::X, 1e-5));
+        assert!(transform.up().abs_diff_eq(Vec3::Y, 1e-5));
+
+        // A target straight overhead keeps the current heading, and rights a tilted transform.
+        transform.rotate_local_x(0.5);
+        transform.look_at_yaw_only(Vec3::new(1.0, 10.0, 3.0), Dir3::Y);
+        assert!(transform.forward().abs_diff_eq(Vec3::X, 1e-5));
+        assert!(transform.up().abs_diff_eq(Vec3::Y, 1e-5));
+
+        // Other up directions work too.
+        transform.look_at_yaw_only(Vec3::new(9.0, 5.0, 3.0), Dir3::X);
+        assert!(transform.forward().abs_diff_eq(Vec3::Y, 1e-5));
+        assert!(transform.up().abs_diff_eq(Vec3::X, 1e-5));
+    }
+
+    #[test]
+    fn looking_to_and_yaw_only() {
+        let transform = Transform::IDENTITY.looking_to(Vec3::new(1.0, 0.0, 1.0), Vec3::Y);
+        assert!(transform
+            .forward()
+            .abs_diff_eq(Vec3::new(1.0, 0.0, 1.0).normalize(), 1e-5));
+        assert!(transform.up().abs_diff_eq(Vec3::Y, 1e-5));
+
+        // Degenerate directions fall back to valid rotations instead of NaNs.
+        for (direction, up) in [
+            (Vec3::ZERO, Vec3::Y),
+            (Vec3::Y, Vec3::Y),
+            (Vec3::NEG_Y, Vec3::Y),
+            (Vec3::X, Vec3::ZERO),
+        ] {
+            let transform = Transform::IDENTITY.looking_to(direction, up);
+            assert!(transform.rotation.is_finite());
+            assert!(transform.rotation.is_normalized());
+        }
+        let transform = Transform::IDENTITY.looking_to(Vec3::ZERO, Vec3::Y);
+        assert!(transform.forward().abs_diff_eq(Vec3::NEG_Z, 1e-5));
+        let transform = Transform::IDENTITY.looking_to(Vec3::Y, Vec3::Y);
+        assert!(transform.forward().abs_diff_eq(Vec3::Y, 1e-5));
+
+        // The yaw-only variant never rolls or pitches, wherever the target is.
+        for target in [
+            Vec3::new(4.0, 3.0, -2.0),
+            Vec3::new(-1.0, -8.0, 0.5),
+            Vec3::new(0.0, 10.0, 0.0),
+        ] {
+            let transform = Transform::IDENTITY.looking_at_yaw_only(target, Dir3::Y);
+            assert!(transform.rotation.is_finite());
+            assert!(transform.up().abs_diff_eq(Vec3::Y, 1e-5));
+            assert!(transform.right().y.abs() < 1e-5);
+            assert!(transform.forward().y.abs() < 1e-5);
+        }
+        let transform = Transform::IDENTITY.looking_at_yaw_only(Vec3::new(4.0, 3.0, 0.0), Dir3::Y);
+        assert!(transform.forward().abs_diff_eq(Vec3::X, 1e-5));
+    }
+
+    #[test]
+    fn lerp() {
+        let a = Transform::from_xyz(1.0, 2.0, 3.0)
+            .with_rotation(Quat::from_rotation_x(0.3))
+            .with_scale(Vec3::splat(2.0));
+        let b = Transform::from_xyz(-3.0, 0.0, 5.0)
+            .with_rotation(Quat::from_rotation_y(2.0))
+            .with_scale(Vec3::new(1.0, 4.0, 2.0));
+
+        assert_eq!(a.lerp(&b, 0.0), a);
+        let end = a.lerp(&b, 1.0);
+        assert!(end.translation.abs_diff_eq(b.translation, 1e-6));
+        assert!(end.rotation.abs_diff_eq(b.rotation, 1e-6));
+        assert!(end.scale.abs_diff_eq(b.scale, 1e-6));
+
+        let mid = a.lerp(&b, 0.5);
+        assert_eq!(mid.translation, Vec3::new(-1.0, 1.0, 4.0));
+        assert_eq!(mid.scale, Vec3::new(1.5, 3.0, 2.0));
+        // The rotation is halfway along the arc between both rotations.
+        assert!(mid
+            .rotation
+            .abs_diff_eq(a.rotation.slerp(b.rotation, 0.5), 1e-6));
+        let half_angle = a.rotation.angle_between(b.rotation) / 2.0;
+        assert!((a.rotation.angle_between(mid.rotation) - half_angle).abs() < 1e-5);
+        assert!((mid.rotation.angle_between(b.rotation) - half_angle).abs() < 1e-5);
+
+        // Values of `t` outside of `[0, 1]` extrapolate.
+        let past = a.lerp(&b, 2.0);
+        assert!(past
+            .translation
+            .abs_diff_eq(Vec3::new(-7.0, -2.0, 7.0), 1e-5));
+        assert!(past.is_finite());
+
+        // Nearly identical rotations don't produce NaNs.
+        let c = a.with_rotation(a.rotation * Quat::from_rotation_z(1e-7));
+        for t in [-1.0, 0.25, 0.5, 3.0] {
+            let transform = a.lerp(&c, t);
+            assert!(transform.is_finite());
+            assert!(transform.rotation.abs_diff_eq(a.rotation, 1e-5));
+        }
+    }
+
+    #[test]
+    fn align() {
+        let is_orthonormal = |transform: &Transform| {
+            let matrix = Mat3::from_quat(transform.rotation);
+            transform.rotation.is_normalized()
+                && (matrix.transpose() * matrix).abs_diff_eq(Mat3::IDENTITY, 1e-5)
+                && (matrix.determinant() - 1.0).abs() < 1e-5
+        };
+        let dir = |x, y, z| Dir3::new(Vec3::new(x, y, z)).unwrap();
+
+        let cases = [
+            (Dir3::X, dir(1.0, 2.0, 3.0), Dir3::Y, dir(-1.0, 0.5, 0.0)),
+            (
+                Dir3::Y,
+                dir(0.2, 1.0, -0.3),
+                Dir3::NEG_Z,
+                dir(0.0, 0.0, 1.0),
+            ),
+            (
+                dir(1.0, 1.0, 0.0),
+                Dir3::NEG_Y,
+                Dir3::Z,
+                dir(3.0, -1.0, 2.0),
+            ),
+        ];
+        for (main_axis, main_direction, secondary_axis, secondary_direction) in cases {
+            let transform = Transform::IDENTITY.aligned_by(
+                main_axis,
+                main_direction,
+                secondary_axis,
+                secondary_direction,
+            );
+            assert!(is_orthonormal(&transform));
+            // The main axis is mapped exactly.
+            assert!((transform.rotation * main_axis).abs_diff_eq(*main_direction, 1e-5));
+            // The secondary axis is turned as close as possible to its target: into the
+            // half-plane spanned by both directions, on the side of the secondary direction.
+            let secondary_image = transform.rotation * secondary_axis;
+            let normal = main_direction.cross(*secondary_direction).normalize();
+            assert!(secondary_image.dot(normal).abs() < 1e-5);
+            let secondary_ortho = secondary_direction.reject_from_normalized(*main_direction);
+            assert!(secondary_image.dot(secondary_ortho) > 0.0);
+        }
+
+        // Parallel secondary targets fall back to mapping the main axis only.
+        let transform = Transform::IDENTITY.aligned_by(Dir3::X, Dir3::Y, Dir3::Y, Dir3::NEG_Y);
+        assert!(is_orthonormal(&transform));
+        assert!(transform.rotation.is_finite());
+        assert!((transform.rotation * Vec3::X).abs_diff_eq(Vec3::Y, 1e-5));
+        assert!(transform
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_arc(Vec3::X, Vec3::Y), 1e-5));
+    }
+
+    #[test]
+    fn constrain_to_axis() {
+        let origin = Vec3::new(1.0, -2.0, 3.0);
+
+        let mut transform = Transform::from_xyz(5.0, 7.0, -4.0).with_scale(Vec3::splat(2.0));
+        transform.rotate_y(1.0);
+        let rotation = transform.rotation;
+        transform.constrain_to_axis(Dir3::X, origin);
+        assert_eq!(transform.translation, Vec3::new(5.0, -2.0, 3.0));
+        assert_eq!(transform.rotation, rotation);
+        assert_eq!(transform.scale, Vec3::splat(2.0));
+
+        // Moving backwards along the axis works too.
+        transform.translation = Vec3::new(-8.0, 0.5, 0.5);
+        transform.constrain_to_axis(Dir3::NEG_X, origin);
+        assert_eq!(transform.translation, Vec3::new(-8.0, -2.0, 3.0));
+
+        // So do diagonal axes.
+        let mut transform = Transform::from_xyz(2.0, 0.0, 2.0);
+        transform.constrain_to_axis(Dir3::new(Vec3::new(1.0, 1.0, 0.0)).unwrap(), Vec3::ZERO);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, 1.0, 0.0), 1e-6));
+    }
+
+    #[test]
+    fn angular_difference() {
+        let transform = Transform::from_rotation(Quat::from_euler(EulerRot::YXZ, 0.5, -0.3, 1.2));
+
+        // Identical orientations, including the same rotation as a negated quaternion.
+        assert_eq!(transform.angular_difference(transform.rotation), 0.0);
+        assert_eq!(transform.axis_difference(transform.rotation), None);
+        assert!(transform.angular_difference(-transform.rotation) < 1e-6);
+
+        // Opposite orientations.
+        let opposite = Quat::from_rotation_x(PI) * transform.rotation;
+        assert!((transform.angular_difference(opposite) - PI).abs() < 1e-5);
+        let axis = transform.axis_difference(opposite).unwrap();
+        assert!(axis.dot(Vec3::X).abs() > 1.0 - 1e-5);
+
+        // A quarter turn, in both directions.
+        let quarter_turn = Quat::from_rotation_z(FRAC_PI_2) * transform.rotation;
+        assert!((transform.angular_difference(quarter_turn) - FRAC_PI_2).abs() < 1e-5);
+        assert!(transform
+            .axis_difference(quarter_turn)
+            .unwrap()
+            .abs_diff_eq(Vec3::Z, 1e-5));
+        let reverse = Transform::from_rotation(quarter_turn);
+        assert!((reverse.angular_difference(transform.rotation) - FRAC_PI_2).abs() < 1e-5);
+        assert!(reverse
+            .axis_difference(transform.rotation)
+            .unwrap()
+            .abs_diff_eq(Vec3::NEG_Z, 1e-5));
+
+        // Rotating by the difference reaches the target.
+        let mut turned = transform;
+        turned.rotate_axis(
+            transform.axis_difference(quarter_turn).unwrap(),
+            transform.angular_difference(quarter_turn),
+        );
+        assert!(turned.rotation.abs_diff_eq(quarter_turn, 1e-5));
+    }
+
+    #[test]
+    fn non_finite_transforms() {
+        let transform = Transform::from_xyz(1.0, 2.0, 3.0);
+        assert!(transform.is_finite());
+        assert!(GlobalTransform::from(transform).is_finite());
+
+        for transform in [
+            transform.with_translation(Vec3::new(f32::NAN, 0.0, 0.0)),
+            transform.with_rotation(Quat::from_xyzw(0.0, f32::NAN, 0.0, 1.0)),
+            transform.with_scale(Vec3::new(1.0, f32::INFINITY, 1.0)),
+        ] {
+            assert!(!transform.is_finite());
+            assert!(!GlobalTransform::from(transform).is_finite());
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::components::Transform;
+use bevy_math::{ops, Quat, Vec3};
+
+/// Rotates a chain of two bones so that its end reaches `target`, bending towards `pole`.
+///
+/// `root` is the transform of the first joint, such as a shoulder or hip, and `mid` is the
+/// transform of the second joint, such as an elbow or knee, relative to `root`. `lengths`
+/// holds the lengths of the first bone, from `root` to `mid`, which is normally the length of
+/// `mid.translation`, and of the second bone, from `mid` to the end of the chain. Both `target`
+/// and `pole` are given in the space of `root`'s parent.
+///
+/// The bones are assumed to point along `mid.translation` in the space of their joint, so the
+/// chain is straight when both rotations are the identity. Scale is ignored. Only the rotations
+/// of `root` and `mid` are changed, each by the smallest rotation that moves its bone into place,
+/// which keeps any twist around the bones.
+///
+/// # Reachability
+///
+/// If `target` is farther from `root` than the length of the whole chain, the chain is
+/// stretched out straight towards it. If it is closer than the difference between the lengths
+/// of the bones, the chain is folded as far as possible while pointing towards it. In both
+/// cases, the end of the chain ends up as close to `target` as it can get.
+///
+/// If `target` lies on the line through `root` and `pole`, or `pole` is at `root`, the
+/// direction the chain bends in is chosen arbitrarily.
+pub fn two_bone_ik(
+    root: &mut Transform,
+    mid: &mut Transform,
+    lengths: (f32, f32),
+    target: Vec3,
+    pole: Vec3,
+) {
+    let (upper, lower) = lengths;
+    let Some(bone_axis) = mid.translation.try_normalize() else {
+        return;
+    };
+
+    let to_target = target - root.translation;
+    let direction = to_target
+        .try_normalize()
+        .unwrap_or_else(|| root.rotation * bone_axis);
+    let distance = to_target
+        .length()
+        .clamp((upper - lower).abs(), upper + lower);
+
+    // The direction the middle joint is pushed out in, perpendicular to the chain.
+    let to_pole = pole - root.translation;
+    let bend = to_pole
+        .reject_from_normalized(direction)
+        .try_normalize()
+        .unwrap_or_else(|| direction.any_orthonormal_vector());
+
+    // The law of cosines gives the angle between the first bone and the target direction.
+    let cos_root = if upper * distance > 0.0 {
+        ((upper * upper + distance * distance - lower * lower) / (2.0 * upper * distance))
+            .clamp(-1.0, 1.0)
+    } else {
+        1.0
+    };
+    let sin_root = ops::sqrt(1.0 - cos_root * cos_root);
+    let upper_direction = cos_root * direction + sin_root * bend;
+    let mid_position = root.translation + upper * upper_direction;
+    let end_position = root.translation + distance * direction;
+
+    // Swing the first bone into place.
+    root.rotation =
+        Quat::from_rotation_arc(root.rotation * bone_axis, upper_direction) * root.rotation;
+
+    // Swing the second bone into place, in world space, then express it relative to `root`.
+    let mid_rotation = root.rotation * mid.rotation;
+    let Some(lower_direction) = (end_position - mid_position).try_normalize() else {
+        return;
+    };
+    let mid_rotation =
+        Quat::from_rotation_arc(mid_rotation * bone_axis, lower_direction) * mid_rotation;
+    mid.rotation = (root.rotation.inverse() * mid_rotation).normalize();
+    root.rotation = root.rotation.normalize();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the positions of the middle joint and the end of the chain.
+    fn chain_positions(root: &Transform, mid: &Transform, lower: f32) -> (Vec3, Vec3) {
+        let mid_position = root.translation + root.rotation * mid.translation;
+        let end_position =
+            mid_position + root.rotation * mid.rotation * (mid.translation.normalize() * lower);
+        (mid_position, end_position)
+    }
+
+    #[test]
+    fn reachable_target() {
+        let mut root = Transform::from_xyz(1.0, 2.0, 3.0);
+        let mut mid = Transform::from_xyz(0.0, 1.0, 0.0);
+        let target = Vec3::new(2.0, 3.0, 3.0);
+        let pole = Vec3::new(1.0, 2.0, 10.0);
+
+        two_bone_ik(&mut root, &mut mid, (1.0, 1.0), target, pole);
+
+        let (mid_position, end_position) = chain_positions(&root, &mid, 1.0);
+        assert!(end_position.abs_diff_eq(target, 1e-5), "{end_position}");
+        assert!(((mid_position - root.translation).length() - 1.0).abs() < 1e-5);
+
+        // The chain bends towards the pole.
+        assert!(mid_position.z > root.translation.z + 0.5);
+    }
+
+    #[test]
+    fn over_extended_target() {
+        let mut root = Transform::IDENTITY;
+        let mut mid = Transform::from_xyz(0.0, 1.0, 0.0);
+        let target = Vec3::new(10.0, 0.0, 0.0);
+
+        two_bone_ik(&mut root, &mut mid, (1.0, 1.5), target, Vec3::Z);
+
+        // The chain is stretched out straight towards the target.
+        let (mid_position, end_position) = chain_positions(&root, &mid, 1.5);
+        assert!(mid_position.abs_diff_eq(Vec3::X, 1e-5), "{mid_position}");
+        assert!(
+            end_position.abs_diff_eq(Vec3::X * 2.5, 1e-5),
+            "{end_position}"
+        );
+        assert!(mid.rotation.abs_diff_eq(Quat::IDENTITY, 1e-5));
+    }
+}
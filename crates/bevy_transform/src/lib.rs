@@ -20,6 +20,9 @@ pub mod components;
 /// Transform related traits
 pub mod traits;
 
+/// Inverse kinematics helpers
+pub mod ik;
+
 /// Transform related plugins
 #[cfg(feature = "bevy-support")]
 pub mod plugins;
@@ -22,6 +22,7 @@ impl Plugin for TransformPlugin {
         #[cfg(feature = "bevy_reflect")]
         app.register_type::<crate::components::Transform>()
             .register_type::<crate::components::TransformTreeChanged>()
+            .register_type::<crate::components::NoTransformPropagation>()
             .register_type::<crate::components::GlobalTransform>();
 
         app
@@ -1,4 +1,5 @@
-use crate::components::{GlobalTransform, Transform, TransformTreeChanged};
+use crate::components::{GlobalTransform, NoTransformPropagation, Transform, TransformTreeChanged};
+use alloc::vec::Vec;
 use bevy_ecs::prelude::*;
 #[cfg(feature = "std")]
 pub use parallel::propagate_parent_transforms;
@@ -40,20 +41,67 @@ pub fn sync_simple_transforms(
     }
 }
 
+/// Logs a warning for every entity whose [`Transform`] or [`GlobalTransform`] isn't finite.
+///
+/// A `NaN` or infinite transform, for example from a physics simulation going wrong, breaks the
+/// rendering and culling of the entity and everything it's propagated to without any error.
+/// This system is meant to help find where such transforms come from while debugging, and isn't
+/// added by the [`TransformPlugin`](crate::TransformPlugin). Add it to the [`PostUpdate`] schedule
+/// after [`TransformSystems::Propagate`](crate::TransformSystems::Propagate) to check every frame:
+///
+/// ```
+/// # use bevy_app::{App, PostUpdate};
+/// # use bevy_ecs::schedule::IntoScheduleConfigs;
+/// # use bevy_transform::{systems::warn_non_finite_transforms, TransformSystems};
+/// # let mut app = App::new();
+/// app.add_systems(
+///     PostUpdate,
+///     warn_non_finite_transforms.after(TransformSystems::Propagate),
+/// );
+/// ```
+///
+/// [`PostUpdate`]: bevy_app::PostUpdate
+#[cfg(feature = "std")]
+pub fn warn_non_finite_transforms(
+    query: Query<(Entity, Option<&Transform>, Option<&GlobalTransform>)>,
+) {
+    for (entity, transform, global_transform) in &query {
+        if let Some(transform) = transform.filter(|transform| !transform.is_finite()) {
+            bevy_log::warn!("{entity} has a non-finite `Transform`: {transform:?}");
+        }
+        if let Some(global_transform) =
+            global_transform.filter(|global_transform| !global_transform.is_finite())
+        {
+            bevy_log::warn!("{entity} has a non-finite `GlobalTransform`: {global_transform:?}");
+        }
+    }
+}
+
 /// Optimization for static scenes. Propagates a "dirty bit" up the hierarchy towards ancestors.
 /// Transform propagation can ignore entire subtrees of the hierarchy if it encounters an entity
 /// without the dirty bit.
+///
+/// Changes below an entity with [`NoTransformPropagation`] aren't propagated past it, as they
+/// wouldn't be propagated back down either.
 pub fn mark_dirty_trees(
     changed_transforms: Query<
         Entity,
         Or<(Changed<Transform>, Changed<ChildOf>, Added<GlobalTransform>)>,
     >,
     mut orphaned: RemovedComponents<ChildOf>,
-    mut transforms: Query<(Option<&ChildOf>, &mut TransformTreeChanged)>,
+    mut transforms: Query<(
+        Option<&ChildOf>,
+        &mut TransformTreeChanged,
+        Has<NoTransformPropagation>,
+    )>,
 ) {
     for entity in changed_transforms.iter().chain(orphaned.read()) {
         let mut next = entity;
-        while let Ok((child_of, mut tree)) = transforms.get_mut(next) {
+        while let Ok((child_of, mut tree, no_propagation)) = transforms.get_mut(next) {
+            if no_propagation && next != entity {
+                // The change is below an entity that doesn't propagate to its descendants.
+                break;
+            }
             if tree.is_changed() && !tree.is_added() {
                 // If the component was changed, this part of the tree has already been processed.
                 // Ignore this if the change was caused by the component being added.
@@ -69,6 +117,60 @@ pub fn mark_dirty_trees(
     }
 }
 
+/// Updates the [`GlobalTransform`]s of all descendants of an entity from its current
+/// [`GlobalTransform`], including the descendants of an entity with [`NoTransformPropagation`].
+///
+/// Descendants are updated the same way transform propagation does, so the traversal stops at
+/// nested entities with [`NoTransformPropagation`], after updating their [`GlobalTransform`].
+/// The [`GlobalTransform`] of the entity itself is left as is.
+///
+/// This is an [`EntityCommand`], which can be queued on the entity:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_transform::{prelude::*, systems::propagate_descendant_transforms};
+/// fn move_scenery(mut commands: Commands, scenery: Query<Entity, With<NoTransformPropagation>>) {
+///     for scenery in &scenery {
+///         commands
+///             .entity(scenery)
+///             .insert(Transform::from_xyz(0.0, 0.0, 10.0))
+///             .queue(propagate_descendant_transforms);
+///     }
+/// }
+/// ```
+///
+/// Note that the [`GlobalTransform`] of the entity is only updated by transform propagation, so
+/// the descendants of an entity whose [`Transform`] changed in this frame are only up to date if
+/// this runs after [`TransformSystems::Propagate`](crate::TransformSystems::Propagate).
+pub fn propagate_descendant_transforms(mut entity: EntityWorldMut) {
+    let root = entity.id();
+    entity.world_scope(|world| {
+        let mut parents = Vec::from([root]);
+        let mut children: Vec<Entity> = Vec::new();
+        while let Some(parent) = parents.pop() {
+            let Some(&parent_transform) = world.get::<GlobalTransform>(parent) else {
+                continue;
+            };
+            children.clear();
+            children.extend(world.get::<Children>(parent).into_iter().flatten());
+            for &child in &children {
+                let Ok(mut child_entity) = world.get_entity_mut(child) else {
+                    continue;
+                };
+                let Some(&transform) = child_entity.get::<Transform>() else {
+                    continue;
+                };
+                if let Some(mut global_transform) = child_entity.get_mut::<GlobalTransform>() {
+                    global_transform.set_if_neq(parent_transform.mul_transform(transform));
+                }
+                if !child_entity.contains::<NoTransformPropagation>() {
+                    parents.push(child);
+                }
+            }
+        }
+    });
+}
+
 // TODO: This serial implementation isn't actually serial, it parallelizes across the roots.
 // Additionally, this couples "no_std" with "single_threaded" when these two features should be
 // independent.
@@ -98,12 +200,23 @@ mod serial {
     /// [`mark_dirty_trees`](super::mark_dirty_trees).
     pub fn propagate_parent_transforms(
         mut root_query: Query<
-            (Entity, &Children, Ref<Transform>, &mut GlobalTransform),
+            (
+                Entity,
+                &Children,
+                Ref<Transform>,
+                &mut GlobalTransform,
+                Has<NoTransformPropagation>,
+            ),
             Without<ChildOf>,
         >,
         mut orphaned: RemovedComponents<ChildOf>,
         transform_query: Query<
-            (Ref<Transform>, &mut GlobalTransform, Option<&Children>),
+            (
+                Ref<Transform>,
+                &mut GlobalTransform,
+                Option<&Children>,
+                Has<NoTransformPropagation>,
+            ),
             With<ChildOf>,
         >,
         child_query: Query<(Entity, Ref<ChildOf>), With<GlobalTransform>>,
@@ -113,11 +226,14 @@ mod serial {
         orphaned_entities.extend(orphaned.read());
         orphaned_entities.sort_unstable();
         root_query.par_iter_mut().for_each(
-        |(entity, children, transform, mut global_transform)| {
+        |(entity, children, transform, mut global_transform, no_propagation)| {
             let changed = transform.is_changed() || global_transform.is_added() || orphaned_entities.binary_search(&entity).is_ok();
             if changed {
                 *global_transform = GlobalTransform::from(*transform);
             }
+            if no_propagation {
+                return;
+            }
 
             for (child, child_of) in child_query.iter_many(children) {
                 assert_eq!(
@@ -171,7 +287,12 @@ mod serial {
     unsafe fn propagate_recursive(
         parent: &GlobalTransform,
         transform_query: &Query<
-            (Ref<Transform>, &mut GlobalTransform, Option<&Children>),
+            (
+                Ref<Transform>,
+                &mut GlobalTransform,
+                Option<&Children>,
+                Has<NoTransformPropagation>,
+            ),
             With<ChildOf>,
         >,
         child_query: &Query<(Entity, Ref<ChildOf>), With<GlobalTransform>>,
@@ -179,7 +300,7 @@ mod serial {
         mut changed: bool,
     ) {
         let (global_matrix, children) = {
-            let Ok((transform, mut global_transform, children)) =
+            let Ok((transform, mut global_transform, children, no_propagation)) =
             // SAFETY: This call cannot create aliased mutable references.
             //   - The top level iteration parallelizes on the roots of the hierarchy.
             //   - The caller ensures that each child has one and only one unique parent throughout
@@ -215,7 +336,7 @@ mod serial {
             if changed {
                 *global_transform = parent.mul_transform(*transform);
             }
-            (global_transform, children)
+            (global_transform, children.filter(|_| !no_propagation))
         };
 
         let Some(children) = children else { return };
@@ -269,7 +390,13 @@ mod parallel {
     pub fn propagate_parent_transforms(
         mut queue: Local<WorkQueue>,
         mut roots: Query<
-            (Entity, Ref<Transform>, &mut GlobalTransform, &Children),
+            (
+                Entity,
+                Ref<Transform>,
+                &mut GlobalTransform,
+                &Children,
+                Has<NoTransformPropagation>,
+            ),
             (Without<ChildOf>, Changed<TransformTreeChanged>),
         >,
         nodes: NodeQuery,
@@ -277,8 +404,11 @@ mod parallel {
         // Process roots in parallel, seeding the work queue
         roots.par_iter_mut().for_each_init(
             || queue.local_queue.borrow_local_mut(),
-            |outbox, (parent, transform, mut parent_transform, children)| {
+            |outbox, (parent, transform, mut parent_transform, children, no_propagation)| {
                 *parent_transform = GlobalTransform::from(*transform);
+                if no_propagation {
+                    return;
+                }
 
                 // SAFETY: the parent entities passed into this function are taken from iterating
                 // over the root entity query. Queries iterate over disjoint entities, preventing
@@ -377,7 +507,7 @@ mod parallel {
                 // the hierarchy, guaranteeing unique access.
                 #[expect(unsafe_code, reason = "Mutating disjoint entities in parallel")]
                 unsafe {
-                    let (_, (_, p_global_transform, _), (p_children, _)) =
+                    let (_, (_, p_global_transform, _), (p_children, _, _)) =
                         nodes.get_unchecked(parent).unwrap();
                     propagate_descendants_unchecked(
                         parent,
@@ -447,7 +577,11 @@ mod parallel {
 
             let mut last_child = None;
             let new_children = children_iter.filter_map(
-                |(child, (transform, mut global_transform, tree), (children, child_of))| {
+                |(
+                    child,
+                    (transform, mut global_transform, tree),
+                    (children, child_of, no_propagation),
+                )| {
                     if !tree.is_changed() && !p_global_transform.is_changed() {
                         // Static scene optimization
                         return None;
@@ -458,8 +592,9 @@ mod parallel {
                     // the GlobalTransform is unchanged, at the cost of an added equality check.
                     global_transform.set_if_neq(p_global_transform.mul_transform(*transform));
 
-                    children.map(|children| {
-                        // Only continue propagation if the entity has children.
+                    children.filter(|_| !no_propagation).map(|children| {
+                        // Only continue propagation if the entity has children, and doesn't stop
+                        // propagation to them.
                         last_child = Some((child, global_transform, children));
                         child
                     })
@@ -499,7 +634,11 @@ mod parallel {
                 Mut<'static, GlobalTransform>,
                 Ref<'static, TransformTreeChanged>,
             ),
-            (Option<Read<Children>>, Read<ChildOf>),
+            (
+                Option<Read<Children>>,
+                Read<ChildOf>,
+                Has<NoTransformPropagation>,
+            ),
         ),
     >;
 
@@ -953,4 +1092,70 @@ mod test {
             *world.entity(child).get::<GlobalTransform>().unwrap()
         );
     }
+
+    #[test]
+    fn no_transform_propagation_prunes_descendants() {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+        let mut world = World::default();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                mark_dirty_trees,
+                sync_simple_transforms,
+                propagate_parent_transforms,
+            )
+                .chain(),
+        );
+
+        let root = world.spawn(Transform::from_xyz(1.0, 0.0, 0.0)).id();
+        let scenery = world
+            .spawn((
+                Transform::from_xyz(0.0, 1.0, 0.0),
+                NoTransformPropagation,
+                ChildOf(root),
+            ))
+            .id();
+        let child = world
+            .spawn((Transform::from_xyz(0.0, 0.0, 1.0), ChildOf(scenery)))
+            .id();
+        let grandchild = world
+            .spawn((Transform::from_xyz(0.0, 0.0, 1.0), ChildOf(child)))
+            .id();
+        let translation =
+            |world: &World, entity| world.get::<GlobalTransform>(entity).unwrap().translation();
+
+        // The marked entity is updated, but not its descendants.
+        schedule.run(&mut world);
+        assert_eq!(translation(&world, scenery), vec3(1.0, 1.0, 0.0));
+        assert_eq!(translation(&world, child), Vec3::ZERO);
+        assert_eq!(translation(&world, grandchild), Vec3::ZERO);
+
+        // Until they are updated manually.
+        propagate_descendant_transforms(world.entity_mut(scenery));
+        assert_eq!(translation(&world, child), vec3(1.0, 1.0, 1.0));
+        assert_eq!(translation(&world, grandchild), vec3(1.0, 1.0, 2.0));
+
+        // Neither changes elsewhere nor changes to the descendants reach them.
+        world.clear_trackers();
+        world.spawn(Transform::from_xyz(5.0, 5.0, 5.0));
+        world.get_mut::<Transform>(root).unwrap().translation.x = 3.0;
+        world.get_mut::<Transform>(child).unwrap().translation.z = 4.0;
+        schedule.run(&mut world);
+        assert_eq!(translation(&world, scenery), vec3(3.0, 1.0, 0.0));
+        assert_eq!(translation(&world, child), vec3(1.0, 1.0, 1.0));
+        assert_eq!(translation(&world, grandchild), vec3(1.0, 1.0, 2.0));
+        assert!(!world
+            .entity(grandchild)
+            .get_ref::<GlobalTransform>()
+            .unwrap()
+            .is_changed());
+
+        // Nested markers stop manual propagation too, after updating the marked entity.
+        world.entity_mut(child).insert(NoTransformPropagation);
+        propagate_descendant_transforms(world.entity_mut(scenery));
+        assert_eq!(translation(&world, child), vec3(3.0, 1.0, 4.0));
+        assert_eq!(translation(&world, grandchild), vec3(1.0, 1.0, 2.0));
+        propagate_descendant_transforms(world.entity_mut(child));
+        assert_eq!(translation(&world, grandchild), vec3(3.0, 1.0, 5.0));
+    }
 }
@@ -1,4 +1,7 @@
-use crate::{CalculatedClip, ComputedNode, ComputedNodeTarget, ResolvedBorderRadius, UiStack};
+use crate::{
+    physical_to_layout_transform, CalculatedClip, ComputedNode, ComputedNodeTarget,
+    ResolvedBorderRadius, UiStack,
+};
 use bevy_ecs::{
     change_detection::DetectChangesMut,
     entity::{ContainsEntity, Entity},
@@ -8,7 +11,7 @@ use bevy_ecs::{
     system::{Local, Query, Res},
 };
 use bevy_input::{mouse::MouseButton, touch::Touches, ButtonInput};
-use bevy_math::{Rect, Vec2};
+use bevy_math::{Affine2, Mat2, Rect, Vec2};
 use bevy_platform::collections::HashMap;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{camera::NormalizedRenderTarget, prelude::Camera, view::InheritedVisibility};
@@ -70,6 +73,9 @@ impl Default for Interaction {
 /// A component storing the position of the mouse relative to the node, (0., 0.) being the top-left corner and (1., 1.) being the bottom-right
 /// If the mouse is not over the node, the value will go beyond the range of (0., 0.) to (1., 1.)
 ///
+/// The position takes the node's [`GlobalTransform`] into account, so it stays relative to the
+/// node's corners when the node is rotated or scaled.
+///
 /// It can be used alongside [`Interaction`] to get the position of the press.
 ///
 /// The component is updated when it is in the same entity with [`Node`](crate::Node).
@@ -194,10 +200,7 @@ pub fn ui_focus_system(
             };
             let window = windows.get(window_ref.entity()).ok()?;
 
-            let viewport_position = camera
-                .physical_viewport_rect()
-                .map(|rect| rect.min.as_vec2())
-                .unwrap_or_default();
+            let to_layout = physical_to_layout_transform(camera);
             window
                 .physical_cursor_position()
                 .or_else(|| {
@@ -205,7 +208,7 @@ pub fn ui_focus_system(
                         .first_pressed_position()
                         .map(|pos| pos * window.scale_factor())
                 })
-                .map(|cursor_position| (entity, cursor_position - viewport_position))
+                .map(|cursor_position| (entity, to_layout.transform_point2(cursor_position)))
         })
         .collect();
 
@@ -251,11 +254,7 @@ pub fn ui_focus_system(
             // (0., 0.) is the top-left corner, (1., 1.) is the bottom-right corner
             // Coordinates are relative to the entire node, not just the visible region.
             let relative_cursor_position = cursor_position.and_then(|cursor_position| {
-                // ensure node size is non-zero in all dimensions, otherwise relative position will be
-                // +/-inf. if the node is hidden, the visible rect min/max will also be -inf leading to
-                // false positives for mouse_over (#12395)
-                (node_rect.size().cmpgt(Vec2::ZERO).all())
-                    .then_some((*cursor_position - node_rect.min) / node_rect.size())
+                relative_position(node.global_transform, node_rect.size(), *cursor_position)
             });
 
             // If the current cursor position is within the bounds of the node's visible area, consider it for
@@ -266,9 +265,9 @@ pub fn ui_focus_system(
             };
 
             let contains_cursor = relative_cursor_position_component.mouse_over()
-                && cursor_position.is_some_and(|point| {
+                && relative_cursor_position.is_some_and(|position| {
                     pick_rounded_rect(
-                        *point - node_rect.center(),
+                        (position - 0.5) * node_rect.size(),
                         node_rect.size(),
                         node.node.border_radius,
                     )
@@ -335,6 +334,39 @@ pub fn ui_focus_system(
     }
 }
 
+/// Returns the position of `point` relative to a node with the given transform and size, with
+/// (0., 0.) being the top-left corner and (1., 1.) being the bottom-right corner of the node.
+///
+/// The point is mapped through the inverse of the node's transform, so the position is relative
+/// to the node as it's drawn, even when it's rotated or scaled.
+///
+/// Returns `None` if the node has no area, or if its transform collapses it to a line or a point,
+/// as the position is undefined then. It also returns `None` if the node is flipped by a negative
+/// scale, as its corners no longer match the corners of the node as it's laid out.
+pub(crate) fn relative_position(
+    global_transform: &GlobalTransform,
+    size: Vec2,
+    point: Vec2,
+) -> Option<Vec2> {
+    // Ensure node size is non-zero in all dimensions, otherwise relative position will be
+    // +/-inf. If the node is hidden, the visible rect min/max will also be -inf leading to
+    // false positives for mouse_over (#12395)
+    if !size.cmpgt(Vec2::ZERO).all() {
+        return None;
+    }
+    // UI nodes are drawn in the XY plane, so only the 2D part of the transform matters.
+    let affine = global_transform.affine();
+    let transform = Affine2::from_mat2_translation(
+        Mat2::from_cols(affine.x_axis.truncate(), affine.y_axis.truncate()),
+        affine.translation.truncate(),
+    );
+    if transform.matrix2.determinant() <= 0.0 {
+        return None;
+    }
+    let local = transform.inverse().transform_point2(point);
+    Some(local / size + 0.5)
+}
+
 // Returns true if `point` (relative to the rectangle's center) is within the bounds of a rounded rectangle with
 // the given size and border radius.
 //
@@ -357,3 +389,75 @@ pub(crate) fn pick_rounded_rect(
     let m = q.max_element().min(0.);
     l + m - r < 0.
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::{Quat, Vec3};
+    use bevy_transform::components::Transform;
+    use core::f32::consts::FRAC_PI_4;
+
+    #[test]
+    fn relative_position_of_rotated_node() {
+        let center = Vec2::new(100., 100.);
+        let size = Vec2::new(100., 50.);
+        let rotation = Quat::from_rotation_z(FRAC_PI_4);
+        let transform = GlobalTransform::from(
+            Transform::from_translation(center.extend(0.)).with_rotation(rotation),
+        );
+
+        // Points given relative to the node's center, along its rotated axes.
+        let world_point = |local: Vec2| center + (rotation * local.extend(0.)).truncate();
+        let assert_relative_position = |local: Vec2, expected: Vec2| {
+            let position = relative_position(&transform, size, world_point(local)).unwrap();
+            assert!(position.abs_diff_eq(expected, 1e-5), "{position}");
+        };
+        assert_relative_position(Vec2::ZERO, Vec2::splat(0.5));
+        assert_relative_position(Vec2::new(25., 0.), Vec2::new(0.75, 0.5));
+        assert_relative_position(Vec2::new(-50., -25.), Vec2::ZERO);
+        assert_relative_position(Vec2::new(50., 25.), Vec2::ONE);
+
+        // This point is within the node's axis-aligned bounds, but outside of the rotated node.
+        let position = relative_position(&transform, size, center + Vec2::new(45., -20.)).unwrap();
+        assert!(position.x > 0. && position.x < 1.);
+        assert!(position.y < 0.);
+    }
+
+    #[test]
+    fn relative_position_of_scaled_node() {
+        let transform = GlobalTransform::from(
+            Transform::from_xyz(100., 100., 0.).with_scale(Vec3::new(2., 0.5, 1.)),
+        );
+        let size = Vec2::new(100., 100.);
+        let position = relative_position(&transform, size, Vec2::new(150., 110.)).unwrap();
+        assert!(position.abs_diff_eq(Vec2::new(0.75, 0.7), 1e-5));
+
+        // Flipped nodes have no relative position, so the mouse is never over them.
+        let flipped = GlobalTransform::from(
+            Transform::from_xyz(100., 100., 0.).with_scale(Vec3::new(-1., 1., 1.)),
+        );
+        let mouse_over = |transform: &GlobalTransform| {
+            RelativeCursorPosition {
+                normalized_visible_node_rect: Rect::new(0., 0., 1., 1.),
+                normalized: relative_position(transform, size, Vec2::new(125., 100.)),
+            }
+            .mouse_over()
+        };
+        assert_eq!(
+            relative_position(&flipped, size, Vec2::new(125., 100.)),
+            None
+        );
+        assert!(!mouse_over(&flipped));
+        assert!(mouse_over(&transform));
+
+        // Nodes without area have no relative position.
+        let collapsed = GlobalTransform::from(
+            Transform::from_xyz(100., 100., 0.).with_scale(Vec3::new(0., 1., 1.)),
+        );
+        assert_eq!(relative_position(&collapsed, size, Vec2::splat(100.)), None);
+        assert_eq!(
+            relative_position(&transform, Vec2::new(0., 100.), Vec2::splat(100.)),
+            None
+        );
+    }
+}
@@ -9,25 +9,41 @@ use crate::layout::ui_surface::UiSurface;
 
 /// Prints a debug representation of the computed layout of the UI layout tree for each window.
 pub fn print_ui_layout_tree(ui_surface: &UiSurface) {
-    let taffy_to_entity: HashMap<NodeId, Entity> = ui_surface
+    let taffy_to_entity = taffy_to_entity(ui_surface);
+    for (&entity, &viewport_node) in &ui_surface.root_entity_to_viewport_node {
+        let out = layout_tree_string(ui_surface, &taffy_to_entity, entity, viewport_node);
+        tracing::info!("Layout tree for camera entity: {entity}\n{out}");
+    }
+}
+
+/// Maps each taffy node of a UI node entity back to its entity.
+pub(super) fn taffy_to_entity(ui_surface: &UiSurface) -> HashMap<NodeId, Entity> {
+    ui_surface
         .entity_to_taffy
         .iter()
         .map(|(entity, node)| (node.id, *entity))
-        .collect();
-    for (&entity, &viewport_node) in &ui_surface.root_entity_to_viewport_node {
-        let mut out = String::new();
-        print_node(
-            ui_surface,
-            &taffy_to_entity,
-            entity,
-            viewport_node,
-            false,
-            String::new(),
-            &mut out,
-        );
+        .collect()
+}
 
-        tracing::info!("Layout tree for camera entity: {entity}\n{out}");
-    }
+/// Returns a debug representation of the computed layout of the tree under the implicit
+/// viewport node of the given UI root entity.
+pub(super) fn layout_tree_string(
+    ui_surface: &UiSurface,
+    taffy_to_entity: &HashMap<NodeId, Entity>,
+    ui_root_entity: Entity,
+    viewport_node: NodeId,
+) -> String {
+    let mut out = String::new();
+    print_node(
+        ui_surface,
+        taffy_to_entity,
+        ui_root_entity,
+        viewport_node,
+        false,
+        String::new(),
+        &mut out,
+    );
+    out
 }
 
 /// Recursively navigates the layout tree printing each node's information.
@@ -1,7 +1,7 @@
 use crate::{
     experimental::{UiChildren, UiRootNodes},
     BorderRadius, ComputedNode, ComputedNodeTarget, ContentSize, Display, LayoutConfig, Node,
-    Outline, OverflowAxis, ScrollPosition, Val,
+    Outline, OverflowAxis, ScrollPosition, UiLayoutRounding, Val,
 };
 use bevy_ecs::{
     change_detection::{DetectChanges, DetectChangesMut},
@@ -9,15 +9,17 @@ use bevy_ecs::{
     hierarchy::{ChildOf, Children},
     query::With,
     removal_detection::RemovedComponents,
-    system::{Commands, Query, ResMut},
+    resource::Resource,
+    system::{Commands, Query, Res, ResMut},
     world::Ref,
 };
-use bevy_math::Vec2;
+use bevy_math::{Affine2, Vec2};
+use bevy_render::camera::Camera;
 use bevy_sprite::BorderRect;
 use bevy_transform::components::Transform;
 use thiserror::Error;
 use tracing::warn;
-use ui_surface::UiSurface;
+pub use ui_surface::UiSurface;
 
 use bevy_text::ComputedTextBlock;
 
@@ -68,10 +70,44 @@ pub enum LayoutError {
     TaffyError(taffy::TaffyError),
 }
 
+/// The errors [`ui_layout_system`] ran into the last time it ran, with the UI node [`Entity`]
+/// that caused each of them.
+///
+/// A node with an error keeps its previous layout, and the rest of the UI is laid out as usual.
+#[derive(Resource, Debug, Default)]
+pub struct UiLayoutErrors(pub Vec<(Entity, LayoutError)>);
+
+/// Returns the transform from physical coordinates on the render target of `camera`, such as
+/// [`Window::physical_cursor_position`](bevy_window::Window::physical_cursor_position), to the
+/// layout coordinates of the UI it renders.
+///
+/// [`ui_layout_system`] lays out the UI in physical pixels, with the top-left corner of the
+/// camera's viewport as the origin, so hit testing against [`ComputedNode`]s has to remove the
+/// offset of the viewport first. Use [`Affine2::inverse`] to map layout coordinates back to the
+/// render target.
+pub fn physical_to_layout_transform(camera: &Camera) -> Affine2 {
+    let viewport_position = camera
+        .physical_viewport_rect()
+        .map(|rect| rect.min.as_vec2())
+        .unwrap_or_default();
+    Affine2::from_translation(-viewport_position)
+}
+
+/// Applies changes to the [`UiLayoutRounding`] resource to the [`UiSurface`].
+pub fn update_ui_layout_rounding(
+    rounding: Res<UiLayoutRounding>,
+    mut ui_surface: ResMut<UiSurface>,
+) {
+    if rounding.is_changed() {
+        ui_surface.set_rounding(rounding.0);
+    }
+}
+
 /// Updates the UI's layout tree, computes the new layout geometry and then updates the sizes and transforms of all the UI nodes.
 pub fn ui_layout_system(
     mut commands: Commands,
     mut ui_surface: ResMut<UiSurface>,
+    mut layout_errors: ResMut<UiLayoutErrors>,
     ui_root_node_query: UiRootNodes,
     mut node_query: Query<(
         Entity,
@@ -96,6 +132,11 @@ pub fn ui_layout_system(
     mut removed_content_sizes: RemovedComponents<ContentSize>,
     mut removed_nodes: RemovedComponents<Node>,
 ) {
+    // Only clear the errors if there are any, so that change detection doesn't trigger every frame.
+    if !layout_errors.0.is_empty() {
+        layout_errors.0.clear();
+    }
+
     // When a `ContentSize` component is removed from an entity, we need to remove the measure from the corresponding taffy node.
     for entity in removed_content_sizes.read() {
         ui_surface.try_remove_node_context(entity);
@@ -141,7 +182,10 @@ with UI components as a child of an entity without UI components, your UI layout
             }
 
             if ui_children.is_changed(entity) {
-                ui_surface.update_children(entity, ui_children.iter_ui_children(entity));
+                // Errors are reported when the children are re-synced below.
+                ui_surface
+                    .update_children(entity, ui_children.iter_ui_children(entity))
+                    .ok();
             }
         });
 
@@ -155,25 +199,35 @@ with UI components as a child of an entity without UI components, your UI layout
     // Re-sync changed children: avoid layout glitches caused by removed nodes that are still set as a child of another node
     computed_node_query.iter().for_each(|(entity, _)| {
         if ui_children.is_changed(entity) {
-            ui_surface.update_children(entity, ui_children.iter_ui_children(entity));
+            if let Err(error) =
+                ui_surface.update_children(entity, ui_children.iter_ui_children(entity))
+            {
+                warn!("Failed to update the UI layout children of {entity}: {error}");
+                layout_errors.0.push((entity, error));
+            }
         }
     });
 
     for ui_root_entity in ui_root_node_query.iter() {
         let (_, _, _, computed_target) = node_query.get(ui_root_entity).unwrap();
 
-        ui_surface.compute_layout(
+        if let Err(error) = ui_surface.compute_layout(
             ui_root_entity,
             computed_target.physical_size,
             &mut buffer_query,
             &mut font_system,
-        );
+        ) {
+            warn!("Failed to compute the UI layout of {ui_root_entity}: {error}");
+            layout_errors.0.push((ui_root_entity, error));
+            continue;
+        }
 
+        let use_rounding = ui_surface.rounding();
         update_uinode_geometry_recursive(
             &mut commands,
             ui_root_entity,
             &mut ui_surface,
-            true,
+            use_rounding,
             None,
             &mut node_transform_query,
             &ui_children,
@@ -348,11 +402,14 @@ mod tests {
 
     use bevy_asset::{AssetEvent, Assets};
     use bevy_core_pipeline::core_2d::Camera2d;
-    use bevy_ecs::{prelude::*, system::RunSystemOnce};
+    use bevy_ecs::{entity::EntityHashSet, prelude::*, system::RunSystemOnce};
     use bevy_image::Image;
     use bevy_math::{Rect, UVec2, Vec2};
     use bevy_platform::collections::HashMap;
-    use bevy_render::{camera::ManualTextureViews, prelude::Camera};
+    use bevy_render::{
+        camera::{ManualTextureViews, Viewport},
+        prelude::Camera,
+    };
     use bevy_transform::systems::mark_dirty_trees;
     use bevy_transform::{
         prelude::GlobalTransform,
@@ -365,8 +422,9 @@ mod tests {
     };
 
     use crate::{
-        layout::ui_surface::UiSurface, prelude::*, ui_layout_system,
-        update::update_ui_context_system, ContentSize, LayoutContext,
+        layout::ui_surface::UiSurface, physical_to_layout_transform, prelude::*, ui_layout_system,
+        update::update_ui_context_system, update_ui_layout_rounding, ContentSize, FixedMeasure,
+        LayoutContext, NodeMeasure, UiLayoutErrors, UiLayoutRounding,
     };
 
     // these window dimensions are easy to convert to and from percentage values
@@ -377,6 +435,8 @@ mod tests {
         let mut world = World::new();
         world.init_resource::<UiScale>();
         world.init_resource::<UiSurface>();
+        world.init_resource::<UiLayoutErrors>();
+        world.init_resource::<UiLayoutRounding>();
         world.init_resource::<Events<WindowScaleFactorChanged>>();
         world.init_resource::<Events<WindowResized>>();
         // Required for the camera system
@@ -408,6 +468,7 @@ mod tests {
                 bevy_render::camera::camera_system,
                 update_ui_context_system,
                 ApplyDeferred,
+                update_ui_layout_rounding,
                 ui_layout_system,
                 mark_dirty_trees,
                 sync_simple_transforms,
@@ -977,6 +1038,7 @@ mod tests {
         let mut world = World::new();
         world.init_resource::<UiScale>();
         world.init_resource::<UiSurface>();
+        world.init_resource::<UiLayoutErrors>();
         world.init_resource::<Events<WindowScaleFactorChanged>>();
         world.init_resource::<Events<WindowResized>>();
         // Required for the camera system
@@ -1033,6 +1095,36 @@ mod tests {
         ui_schedule.run(&mut world);
     }
 
+    #[test]
+    fn physical_to_layout_transform_removes_viewport_offset() {
+        let camera = Camera {
+            viewport: Some(Viewport {
+                physical_position: UVec2::new(200, 100),
+                physical_size: UVec2::new(600, 400),
+                ..default()
+            }),
+            ..default()
+        };
+        let to_layout = physical_to_layout_transform(&camera);
+        let to_physical = to_layout.inverse();
+
+        // The corners of the viewport are the corners of the layout.
+        for (physical, layout) in [
+            (Vec2::new(200., 100.), Vec2::ZERO),
+            (Vec2::new(800., 500.), Vec2::new(600., 400.)),
+        ] {
+            assert_eq!(to_layout.transform_point2(physical), layout);
+            assert_eq!(to_physical.transform_point2(layout), physical);
+        }
+
+        // Without a viewport, the layout covers the whole render target.
+        let to_layout = physical_to_layout_transform(&Camera::default());
+        assert_eq!(
+            to_layout.transform_point2(Vec2::new(30., 40.)),
+            Vec2::new(30., 40.)
+        );
+    }
+
     #[test]
     fn test_ui_surface_compute_camera_layout() {
         use bevy_ecs::prelude::ResMut;
@@ -1058,12 +1150,14 @@ mod tests {
                 None,
             );
 
-            ui_surface.compute_layout(
-                params.root_node_entity,
-                UVec2::new(800, 600),
-                &mut computed_text_block_query,
-                &mut font_system,
-            );
+            ui_surface
+                .compute_layout(
+                    params.root_node_entity,
+                    UVec2::new(800, 600),
+                    &mut computed_text_block_query,
+                    &mut font_system,
+                )
+                .unwrap();
         }
 
         let _ = world.run_system_once_with(test_system, TestSystemParam { root_node_entity });
@@ -1074,6 +1168,171 @@ mod tests {
         assert!(ui_surface.taffy.layout(taffy_node.id).is_ok());
     }
 
+    #[test]
+    fn ui_layout_rounding() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+        world.insert_resource(UiLayoutRounding(false));
+
+        // Three nodes sharing a width that doesn't divide evenly between them.
+        let children = [(); 3].map(|_| {
+            world
+                .spawn(Node {
+                    flex_grow: 1.,
+                    height: Val::Px(10.5),
+                    ..default()
+                })
+                .id()
+        });
+        world
+            .spawn(Node {
+                width: Val::Px(100.),
+                ..default()
+            })
+            .add_children(&children);
+
+        let sizes = |world: &mut World| {
+            children.map(|child| world.get::<ComputedNode>(child).unwrap().size())
+        };
+
+        ui_schedule.run(&mut world);
+        for size in sizes(&mut world) {
+            assert!((size.x - 100. / 3.).abs() < 1e-4, "{size}");
+            assert_eq!(size.y, 10.5);
+        }
+
+        world.resource_mut::<UiLayoutRounding>().0 = true;
+        ui_schedule.run(&mut world);
+        let sizes = sizes(&mut world);
+        for size in sizes {
+            assert_eq!(size, size.round(), "{size}");
+        }
+        assert_eq!(sizes.iter().map(|size| size.x).sum::<f32>(), 100.);
+    }
+
+    #[test]
+    fn ui_surface_changed_nodes() {
+        let (mut world, ..) = setup_ui_test_world();
+
+        fn layout_system(
+            first_child_width: In<f32>,
+            mut ui_surface: ResMut<UiSurface>,
+            mut computed_text_block_query: Query<&mut bevy_text::ComputedTextBlock>,
+            mut font_system: ResMut<bevy_text::CosmicFontSystem>,
+        ) -> EntityHashSet {
+            let root = Entity::from_raw_u32(1).unwrap();
+            let children = [2, 3].map(|index| Entity::from_raw_u32(index).unwrap());
+            ui_surface.upsert_node(
+                &LayoutContext::TEST_CONTEXT,
+                root,
+                &Node {
+                    width: Val::Px(200.),
+                    height: Val::Px(200.),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                None,
+            );
+            for (child, width) in children.into_iter().zip([*first_child_width, 100.]) {
+                ui_surface.upsert_node(
+                    &LayoutContext::TEST_CONTEXT,
+                    child,
+                    &Node {
+                        width: Val::Px(width),
+                        height: Val::Px(50.),
+                        ..default()
+                    },
+                    None,
+                );
+            }
+            ui_surface
+                .update_children(root, children.into_iter())
+                .unwrap();
+            ui_surface
+                .compute_layout(
+                    root,
+                    UVec2::new(800, 600),
+                    &mut computed_text_block_query,
+                    &mut font_system,
+                )
+                .unwrap();
+
+            ui_surface.changed_nodes().collect()
+        }
+
+        let root = Entity::from_raw_u32(1).unwrap();
+        let first_child = Entity::from_raw_u32(2).unwrap();
+        let second_child = Entity::from_raw_u32(3).unwrap();
+
+        // Nothing is tracked by default.
+        let changed_nodes = world.run_system_once_with(layout_system, 100.).unwrap();
+        assert!(changed_nodes.is_empty());
+
+        // Every node is reported the first time it's laid out after enabling tracking.
+        world
+            .resource_mut::<UiSurface>()
+            .set_changed_node_tracking(true);
+        let changed_nodes = world.run_system_once_with(layout_system, 100.).unwrap();
+        assert_eq!(
+            changed_nodes,
+            EntityHashSet::from_iter([root, first_child, second_child])
+        );
+
+        // Nothing moves when the layout is computed again.
+        let changed_nodes = world.run_system_once_with(layout_system, 100.).unwrap();
+        assert!(changed_nodes.is_empty());
+
+        // Only the resized child changes. It's stacked above the second child, and is narrower
+        // than it, so neither the second child nor the content size of the root change.
+        let changed_nodes = world.run_system_once_with(layout_system, 50.).unwrap();
+        assert_eq!(changed_nodes, EntityHashSet::from_iter([first_child]));
+    }
+
+    #[test]
+    fn measure_content_sizes_of_fixed_size_leaf() {
+        let (mut world, ..) = setup_ui_test_world();
+
+        fn measure_system(
+            mut ui_surface: ResMut<UiSurface>,
+            mut computed_text_block_query: Query<&mut bevy_text::ComputedTextBlock>,
+            mut font_system: ResMut<bevy_text::CosmicFontSystem>,
+        ) -> [Option<(Vec2, Vec2)>; 3] {
+            let fixed = Entity::from_raw_u32(1).unwrap();
+            let measured = Entity::from_raw_u32(2).unwrap();
+            let missing = Entity::from_raw_u32(3).unwrap();
+            ui_surface.upsert_node(
+                &LayoutContext::TEST_CONTEXT,
+                fixed,
+                &Node {
+                    width: Val::Px(40.),
+                    height: Val::Px(30.),
+                    ..default()
+                },
+                None,
+            );
+            ui_surface.upsert_node(
+                &LayoutContext::TEST_CONTEXT,
+                measured,
+                &Node::default(),
+                Some(NodeMeasure::Fixed(FixedMeasure {
+                    size: Vec2::new(25., 15.),
+                })),
+            );
+
+            [fixed, measured, missing].map(|entity| {
+                ui_surface.measure_content_sizes(
+                    entity,
+                    &mut computed_text_block_query,
+                    &mut font_system,
+                )
+            })
+        }
+
+        let [fixed, measured, missing] = world.run_system_once(measure_system).unwrap();
+        assert_eq!(fixed, Some((Vec2::new(40., 30.), Vec2::new(40., 30.))));
+        assert_eq!(measured, Some((Vec2::new(25., 15.), Vec2::new(25., 15.))));
+        assert_eq!(missing, None);
+    }
+
     #[test]
     fn no_viewport_node_leak_on_root_despawned() {
         let (mut world, mut ui_schedule) = setup_ui_test_world();
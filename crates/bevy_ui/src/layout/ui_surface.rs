@@ -1,10 +1,10 @@
 use core::fmt;
 
-use bevy_platform::collections::hash_map::Entry;
-use taffy::TaffyTree;
+use bevy_platform::collections::{hash_map::Entry, HashMap};
+use taffy::{TaffyTree, TraversePartialTree};
 
 use bevy_ecs::{
-    entity::{Entity, EntityHashMap},
+    entity::{Entity, EntityHashMap, EntityHashSet},
     prelude::Resource,
 };
 use bevy_math::{UVec2, Vec2};
@@ -30,12 +30,22 @@ impl From<taffy::NodeId> for LayoutNode {
     }
 }
 
+/// The Taffy layout tree of the UI node entities, which [`ui_layout_system`](super::ui_layout_system)
+/// keeps in sync with their [`Node`]s.
 #[derive(Resource)]
 pub struct UiSurface {
     pub root_entity_to_viewport_node: EntityHashMap<taffy::NodeId>,
     pub(super) entity_to_taffy: EntityHashMap<LayoutNode>,
     pub(super) taffy: TaffyTree<NodeMeasure>,
     taffy_children_scratch: Vec<taffy::NodeId>,
+    /// The entities that [`UiSurface::upsert_nodes`] has to insert, with their converted styles.
+    new_nodes_scratch: Vec<(Entity, taffy::Style, Option<NodeMeasure>)>,
+    /// The UI node entity of each taffy node, with the unrounded layout computed for it the last
+    /// time its UI root was laid out, or `None` if it hasn't been laid out yet.
+    computed_layouts: HashMap<taffy::NodeId, (Entity, Option<taffy::Layout>)>,
+    changed_nodes: EntityHashSet,
+    track_changed_nodes: bool,
+    use_rounding: bool,
 }
 
 fn _assert_send_sync_ui_surface_impl_safe() {
@@ -62,6 +72,11 @@ impl Default for UiSurface {
             entity_to_taffy: Default::default(),
             taffy,
             taffy_children_scratch: Vec::new(),
+            new_nodes_scratch: Vec::new(),
+            computed_layouts: Default::default(),
+            changed_nodes: Default::default(),
+            track_changed_nodes: false,
+            use_rounding: true,
         }
     }
 }
@@ -74,41 +89,84 @@ impl UiSurface {
         layout_context: &LayoutContext,
         entity: Entity,
         node: &Node,
-        mut new_node_context: Option<NodeMeasure>,
+        new_node_context: Option<NodeMeasure>,
     ) {
-        let taffy = &mut self.taffy;
-
         match self.entity_to_taffy.entry(entity) {
             Entry::Occupied(entry) => {
-                let taffy_node = *entry.get();
-                let has_measure = if new_node_context.is_some() {
-                    taffy
-                        .set_node_context(taffy_node.id, new_node_context)
-                        .unwrap();
-                    true
-                } else {
-                    taffy.get_node_context(taffy_node.id).is_some()
-                };
-
-                taffy
-                    .set_style(
-                        taffy_node.id,
-                        convert::from_node(node, layout_context, has_measure),
-                    )
-                    .unwrap();
+                update_taffy_node(
+                    &mut self.taffy,
+                    entry.get().id,
+                    |has_measure| convert::from_node(node, layout_context, has_measure),
+                    new_node_context,
+                );
             }
             Entry::Vacant(entry) => {
-                let taffy_node = if let Some(measure) = new_node_context.take() {
-                    taffy.new_leaf_with_context(
-                        convert::from_node(node, layout_context, true),
-                        measure,
-                    )
-                } else {
-                    taffy.new_leaf(convert::from_node(node, layout_context, false))
-                };
-                entry.insert(taffy_node.unwrap().into());
+                let style = convert::from_node(node, layout_context, new_node_context.is_some());
+                let taffy_node = new_taffy_leaf(&mut self.taffy, style, new_node_context);
+                self.computed_layouts.insert(taffy_node, (entity, None));
+                entry.insert(taffy_node.into());
+            }
+        }
+    }
+
+    /// Calls [`UiSurface::upsert_node`] for each of the given UI node entities, which share the
+    /// same [`LayoutContext`].
+    ///
+    /// Existing nodes are updated right away. New ones are collected in a scratch buffer that is
+    /// reused between calls, so that the maps from entities to Taffy nodes are grown once for all
+    /// of them, and each entity is only looked up once unless it's new.
+    pub fn upsert_nodes<'a>(
+        &mut self,
+        layout_context: &LayoutContext,
+        nodes: impl Iterator<Item = (Entity, &'a Node, Option<NodeMeasure>)>,
+    ) {
+        let mut new_nodes = core::mem::take(&mut self.new_nodes_scratch);
+        new_nodes.clear();
+
+        for (entity, node, node_context) in nodes {
+            match self.entity_to_taffy.get(&entity) {
+                Some(taffy_node) => update_taffy_node(
+                    &mut self.taffy,
+                    taffy_node.id,
+                    |has_measure| convert::from_node(node, layout_context, has_measure),
+                    node_context,
+                ),
+                None => {
+                    let style = convert::from_node(node, layout_context, node_context.is_some());
+                    new_nodes.push((entity, style, node_context));
+                }
+            }
+        }
+
+        self.entity_to_taffy.reserve(new_nodes.len());
+        self.computed_layouts.reserve(new_nodes.len());
+        for (entity, mut style, node_context) in new_nodes.drain(..) {
+            match self.entity_to_taffy.entry(entity) {
+                // The entity is listed more than once, and was inserted earlier in this batch.
+                Entry::Occupied(entry) => {
+                    update_taffy_node(
+                        &mut self.taffy,
+                        entry.get().id,
+                        |has_measure| {
+                            // Match `convert::from_node`, which ignores the border of leaves
+                            // with a measure.
+                            if has_measure {
+                                style.border = taffy::Rect::zero();
+                            }
+                            style
+                        },
+                        node_context,
+                    );
+                }
+                Entry::Vacant(entry) => {
+                    let taffy_node = new_taffy_leaf(&mut self.taffy, style, node_context);
+                    self.computed_layouts.insert(taffy_node, (entity, None));
+                    entry.insert(taffy_node.into());
+                }
             }
         }
+
+        self.new_nodes_scratch = new_nodes;
     }
 
     /// Update the `MeasureFunc` of the taffy node corresponding to the given [`Entity`] if the node exists.
@@ -120,8 +178,20 @@ impl UiSurface {
     }
 
     /// Update the children of the taffy node corresponding to the given [`Entity`].
-    pub fn update_children(&mut self, entity: Entity, children: impl Iterator<Item = Entity>) {
+    ///
+    /// Returns [`LayoutError::InvalidHierarchy`] if the entity or any of the children has no taffy
+    /// node. Children without a taffy node are left out, but the others are still set.
+    pub fn update_children(
+        &mut self,
+        entity: Entity,
+        children: impl Iterator<Item = Entity>,
+    ) -> Result<(), LayoutError> {
+        let Some(taffy_node) = self.entity_to_taffy.get(&entity).copied() else {
+            return Err(LayoutError::InvalidHierarchy);
+        };
+
         self.taffy_children_scratch.clear();
+        let mut missing_child = false;
 
         for child in children {
             if let Some(taffy_node) = self.entity_to_taffy.get_mut(&child) {
@@ -129,13 +199,20 @@ impl UiSurface {
                 if let Some(viewport_id) = taffy_node.viewport_id.take() {
                     self.taffy.remove(viewport_id).ok();
                 }
+            } else {
+                missing_child = true;
             }
         }
 
-        let taffy_node = self.entity_to_taffy.get(&entity).unwrap();
         self.taffy
             .set_children(taffy_node.id, &self.taffy_children_scratch)
-            .unwrap();
+            .map_err(LayoutError::TaffyError)?;
+
+        if missing_child {
+            Err(LayoutError::InvalidHierarchy)
+        } else {
+            Ok(())
+        }
     }
 
     /// Removes children from the entity's taffy node if it exists. Does nothing otherwise.
@@ -153,12 +230,18 @@ impl UiSurface {
     }
 
     /// Gets or inserts an implicit taffy viewport node corresponding to the given UI root entity
-    pub fn get_or_insert_taffy_viewport_node(&mut self, ui_root_entity: Entity) -> taffy::NodeId {
-        *self
-            .root_entity_to_viewport_node
-            .entry(ui_root_entity)
-            .or_insert_with(|| {
-                let root_node = self.entity_to_taffy.get_mut(&ui_root_entity).unwrap();
+    ///
+    /// Returns [`LayoutError::InvalidHierarchy`] if the UI root entity has no taffy node.
+    pub fn get_or_insert_taffy_viewport_node(
+        &mut self,
+        ui_root_entity: Entity,
+    ) -> Result<taffy::NodeId, LayoutError> {
+        match self.root_entity_to_viewport_node.entry(ui_root_entity) {
+            Entry::Occupied(entry) => Ok(*entry.get()),
+            Entry::Vacant(entry) => {
+                let Some(root_node) = self.entity_to_taffy.get_mut(&ui_root_entity) else {
+                    return Err(LayoutError::InvalidHierarchy);
+                };
                 let implicit_root = self
                     .taffy
                     .new_leaf(taffy::style::Style {
@@ -173,11 +256,14 @@ impl UiSurface {
                         justify_items: Some(taffy::style::JustifyItems::Start),
                         ..default()
                     })
-                    .unwrap();
-                self.taffy.add_child(implicit_root, root_node.id).unwrap();
+                    .map_err(LayoutError::TaffyError)?;
+                self.taffy
+                    .add_child(implicit_root, root_node.id)
+                    .map_err(LayoutError::TaffyError)?;
                 root_node.viewport_id = Some(implicit_root);
-                implicit_root
-            })
+                Ok(*entry.insert(implicit_root))
+            }
+        }
     }
 
     /// Compute the layout for the given implicit taffy viewport node
@@ -187,8 +273,8 @@ impl UiSurface {
         render_target_resolution: UVec2,
         buffer_query: &'a mut bevy_ecs::prelude::Query<&mut bevy_text::ComputedTextBlock>,
         font_system: &'a mut CosmicFontSystem,
-    ) {
-        let implicit_viewport_node = self.get_or_insert_taffy_viewport_node(ui_root_entity);
+    ) -> Result<(), LayoutError> {
+        let implicit_viewport_node = self.get_or_insert_taffy_viewport_node(ui_root_entity)?;
 
         let available_space = taffy::geometry::Size {
             width: taffy::style::AvailableSpace::Definite(render_target_resolution.x as f32),
@@ -199,42 +285,178 @@ impl UiSurface {
             .compute_layout_with_measure(
                 implicit_viewport_node,
                 available_space,
-                |known_dimensions: taffy::Size<Option<f32>>,
-                 available_space: taffy::Size<taffy::AvailableSpace>,
-                 _node_id: taffy::NodeId,
-                 context: Option<&mut NodeMeasure>,
-                 style: &taffy::Style|
-                 -> taffy::Size<f32> {
-                    context
-                        .map(|ctx| {
-                            let buffer = get_text_buffer(
-                                crate::widget::TextMeasure::needs_buffer(
-                                    known_dimensions.height,
-                                    available_space.width,
-                                ),
-                                ctx,
-                                buffer_query,
-                            );
-                            let size = ctx.measure(
-                                MeasureArgs {
-                                    width: known_dimensions.width,
-                                    height: known_dimensions.height,
-                                    available_width: available_space.width,
-                                    available_height: available_space.height,
-                                    font_system,
-                                    buffer,
-                                },
-                                style,
-                            );
-                            taffy::Size {
-                                width: size.x,
-                                height: size.y,
-                            }
-                        })
-                        .unwrap_or(taffy::Size::ZERO)
+                |known_dimensions, available_space, _node_id, context, style| {
+                    measure_node(
+                        known_dimensions,
+                        available_space,
+                        context,
+                        style,
+                        buffer_query,
+                        font_system,
+                    )
                 },
             )
-            .unwrap();
+            .map_err(LayoutError::TaffyError)?;
+
+        self.update_changed_nodes(ui_root_entity);
+        Ok(())
+    }
+
+    /// Compares the layout of each node under the given UI root entity to the one computed for it
+    /// the previous time, and updates the set of [`UiSurface::changed_nodes`] to match.
+    ///
+    /// This does nothing unless [changed node tracking](UiSurface::set_changed_node_tracking) is
+    /// enabled.
+    fn update_changed_nodes(&mut self, ui_root_entity: Entity) {
+        if !self.track_changed_nodes {
+            return;
+        }
+        let Some(root_node) = self.entity_to_taffy.get(&ui_root_entity).copied() else {
+            return;
+        };
+
+        // Compare unrounded layouts, like the ones returned by `get_layout`, so that changes
+        // smaller than a pixel are noticed too.
+        self.taffy.disable_rounding();
+        let stack = &mut self.taffy_children_scratch;
+        stack.clear();
+        stack.push(root_node.id);
+        while let Some(taffy_node) = stack.pop() {
+            stack.extend(self.taffy.child_ids(taffy_node));
+            let (Some((entity, computed_layout)), Ok(layout)) = (
+                self.computed_layouts.get_mut(&taffy_node),
+                self.taffy.layout(taffy_node),
+            ) else {
+                continue;
+            };
+            if computed_layout.as_ref() == Some(layout) {
+                self.changed_nodes.remove(&*entity);
+            } else {
+                *computed_layout = Some(*layout);
+                self.changed_nodes.insert(*entity);
+            }
+        }
+        self.taffy.enable_rounding();
+    }
+
+    /// Returns the UI node entities whose layout changed the last time [`UiSurface::compute_layout`]
+    /// was called for their UI root, including nodes that were laid out for the first time.
+    ///
+    /// Systems can use this to only update the nodes that Taffy actually moved or resized.
+    /// This is always empty unless [changed node tracking](UiSurface::set_changed_node_tracking)
+    /// is enabled.
+    pub fn changed_nodes(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.changed_nodes.iter().copied()
+    }
+
+    /// Returns whether the nodes whose layout changed are tracked, see
+    /// [`UiSurface::changed_nodes`]. This is `false` by default.
+    pub fn changed_node_tracking(&self) -> bool {
+        self.track_changed_nodes
+    }
+
+    /// Sets whether the nodes whose layout changed are tracked, see [`UiSurface::changed_nodes`].
+    ///
+    /// Tracking walks every node of a UI root each time [`UiSurface::compute_layout`] is called
+    /// for it. After enabling it, every node is reported as changed the next time it's laid out.
+    pub fn set_changed_node_tracking(&mut self, enabled: bool) {
+        if self.track_changed_nodes == enabled {
+            return;
+        }
+        self.track_changed_nodes = enabled;
+        if !enabled {
+            self.changed_nodes.clear();
+            for (_, computed_layout) in self.computed_layouts.values_mut() {
+                *computed_layout = None;
+            }
+        }
+    }
+
+    /// Computes the min-content and max-content size of the given UI node entity, in physical
+    /// pixels, returning `None` if it has no taffy node.
+    ///
+    /// The min-content size is the smallest size the node can have without its content
+    /// overflowing, for example by wrapping text at every opportunity. The max-content size is
+    /// the size the node takes when it's given unlimited space.
+    ///
+    /// Measuring the node overwrites the layouts of the node and its descendants, so it's marked
+    /// as dirty to have the next call to [`UiSurface::compute_layout`] fix them. Layouts shouldn't
+    /// be read with [`UiSurface::get_layout`] between the two.
+    pub fn measure_content_sizes<'a>(
+        &mut self,
+        entity: Entity,
+        buffer_query: &'a mut bevy_ecs::prelude::Query<&mut bevy_text::ComputedTextBlock>,
+        font_system: &'a mut CosmicFontSystem,
+    ) -> Option<(Vec2, Vec2)> {
+        let node = self.entity_to_taffy.get(&entity)?.id;
+
+        let mut measure = |space: taffy::AvailableSpace| {
+            self.taffy
+                .compute_layout_with_measure(
+                    node,
+                    taffy::Size {
+                        width: space,
+                        height: space,
+                    },
+                    |known_dimensions, available_space, _node_id, context, style| {
+                        measure_node(
+                            known_dimensions,
+                            available_space,
+                            context,
+                            style,
+                            buffer_query,
+                            font_system,
+                        )
+                    },
+                )
+                .ok()?;
+            let size = self.taffy.layout(node).ok()?.size;
+            Some(Vec2::new(size.width, size.height))
+        };
+        let min_content = measure(taffy::AvailableSpace::MinContent);
+        let max_content = measure(taffy::AvailableSpace::MaxContent);
+
+        self.taffy.mark_dirty(node).ok();
+        min_content.zip(max_content)
+    }
+
+    /// Returns a debug representation of the computed layout of the UI node tree under the given
+    /// UI root entity, or `None` if it isn't a UI root with an implicit viewport node.
+    ///
+    /// Each line holds a node's display mode, its position and size as resolved by the last layout
+    /// computation, and its [`Entity`], and children are indented below their parent.
+    #[cfg(feature = "bevy_ui_debug")]
+    pub fn debug_tree(&self, ui_root_entity: Entity) -> Option<String> {
+        let viewport_node = *self.root_entity_to_viewport_node.get(&ui_root_entity)?;
+        Some(super::debug::layout_tree_string(
+            self,
+            &super::debug::taffy_to_entity(self),
+            ui_root_entity,
+            viewport_node,
+        ))
+    }
+
+    /// Returns whether the layouts of nodes without a [`LayoutConfig`](crate::LayoutConfig) are
+    /// rounded to whole pixels. This is `true` by default.
+    pub fn rounding(&self) -> bool {
+        self.use_rounding
+    }
+
+    /// Sets whether the layouts of nodes without a [`LayoutConfig`](crate::LayoutConfig) are
+    /// rounded to whole pixels, like [`UiLayoutRounding`](crate::UiLayoutRounding) does.
+    ///
+    /// Changing the setting marks every node as dirty, so that the next call to
+    /// [`UiSurface::compute_layout`] lays out every UI root again and reports all of their nodes as
+    /// [changed](UiSurface::changed_nodes).
+    pub fn set_rounding(&mut self, enabled: bool) {
+        if self.use_rounding == enabled {
+            return;
+        }
+        self.use_rounding = enabled;
+        for (taffy_node, (_, computed_layout)) in &mut self.computed_layouts {
+            self.taffy.mark_dirty(*taffy_node).ok();
+            *computed_layout = None;
+        }
     }
 
     /// Removes each entity from the internal map and then removes their associated nodes from taffy
@@ -242,6 +464,8 @@ impl UiSurface {
         for entity in entities {
             if let Some(node) = self.entity_to_taffy.remove(&entity) {
                 self.taffy.remove(node.id).unwrap();
+                self.computed_layouts.remove(&node.id);
+                self.changed_nodes.remove(&entity);
                 if let Some(viewport_node) = node.viewport_id {
                     self.taffy.remove(viewport_node).ok();
                 }
@@ -283,6 +507,44 @@ impl UiSurface {
     }
 }
 
+/// Measures the content of a taffy node with the given [`NodeMeasure`] context, if any.
+fn measure_node(
+    known_dimensions: taffy::Size<Option<f32>>,
+    available_space: taffy::Size<taffy::AvailableSpace>,
+    context: Option<&mut NodeMeasure>,
+    style: &taffy::Style,
+    buffer_query: &mut bevy_ecs::prelude::Query<&mut bevy_text::ComputedTextBlock>,
+    font_system: &mut CosmicFontSystem,
+) -> taffy::Size<f32> {
+    context
+        .map(|ctx| {
+            let buffer = get_text_buffer(
+                crate::widget::TextMeasure::needs_buffer(
+                    known_dimensions.height,
+                    available_space.width,
+                ),
+                ctx,
+                buffer_query,
+            );
+            let size = ctx.measure(
+                MeasureArgs {
+                    width: known_dimensions.width,
+                    height: known_dimensions.height,
+                    available_width: available_space.width,
+                    available_height: available_space.height,
+                    font_system,
+                    buffer,
+                },
+                style,
+            );
+            taffy::Size {
+                width: size.x,
+                height: size.y,
+            }
+        })
+        .unwrap_or(taffy::Size::ZERO)
+}
+
 pub fn get_text_buffer<'a>(
     needs_buffer: bool,
     ctx: &mut NodeMeasure,
@@ -301,12 +563,44 @@ pub fn get_text_buffer<'a>(
     Some(computed.into_inner())
 }
 
+/// Updates the style of an existing Taffy node, and its measure if a new one is given.
+///
+/// `style` is called with whether the node has a measure after the update.
+fn update_taffy_node(
+    taffy: &mut TaffyTree<NodeMeasure>,
+    taffy_node: taffy::NodeId,
+    style: impl FnOnce(bool) -> taffy::Style,
+    new_node_context: Option<NodeMeasure>,
+) {
+    let has_measure = if new_node_context.is_some() {
+        taffy
+            .set_node_context(taffy_node, new_node_context)
+            .unwrap();
+        true
+    } else {
+        taffy.get_node_context(taffy_node).is_some()
+    };
+    taffy.set_style(taffy_node, style(has_measure)).unwrap();
+}
+
+/// Inserts a new Taffy leaf with the given style and measure.
+fn new_taffy_leaf(
+    taffy: &mut TaffyTree<NodeMeasure>,
+    style: taffy::Style,
+    node_context: Option<NodeMeasure>,
+) -> taffy::NodeId {
+    match node_context {
+        Some(measure) => taffy.new_leaf_with_context(style, measure),
+        None => taffy.new_leaf(style),
+    }
+    .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{ContentSize, FixedMeasure};
+    use crate::{ContentSize, FixedMeasure, UiRect, Val};
     use bevy_math::Vec2;
-    use taffy::TraversePartialTree;
 
     #[test]
     fn test_initialization() {
@@ -335,7 +629,9 @@ mod tests {
         assert_eq!(ui_surface.taffy.total_node_count(), 1);
 
         // assign root node to camera
-        ui_surface.get_or_insert_taffy_viewport_node(root_node_entity);
+        ui_surface
+            .get_or_insert_taffy_viewport_node(root_node_entity)
+            .unwrap();
 
         // each root node will create 2 taffy nodes
         assert_eq!(ui_surface.taffy.total_node_count(), 2);
@@ -347,6 +643,118 @@ mod tests {
         assert_eq!(ui_surface.taffy.total_node_count(), 2);
     }
 
+    #[test]
+    fn test_upsert_nodes() {
+        let [first, second, third] = [1, 2, 3].map(|index| Entity::from_raw_u32(index).unwrap());
+        let node = Node::default();
+        let bordered_node = Node {
+            width: Val::Px(10.0),
+            border: UiRect::all(Val::Px(2.0)),
+            ..default()
+        };
+        let half_height_node = Node {
+            height: Val::Percent(50.0),
+            ..default()
+        };
+        let measure = || Some(NodeMeasure::Fixed(FixedMeasure { size: Vec2::ONE }));
+        // The second entity is listed twice, so that a batch that inserts and then updates the
+        // same node is covered too.
+        let items = || {
+            [
+                (first, &node, None),
+                (second, &bordered_node, measure()),
+                (third, &half_height_node, None),
+                (second, &bordered_node, None),
+            ]
+        };
+
+        let mut batched = UiSurface::default();
+        let mut looped = UiSurface::default();
+        // Upsert twice, so that both inserting and updating nodes are covered.
+        for _ in 0..2 {
+            batched.upsert_nodes(&LayoutContext::TEST_CONTEXT, items().into_iter());
+            for (entity, node, measure) in items() {
+                looped.upsert_node(&LayoutContext::TEST_CONTEXT, entity, node, measure);
+            }
+        }
+
+        assert_eq!(
+            batched.taffy.total_node_count(),
+            looped.taffy.total_node_count()
+        );
+        assert_eq!(
+            batched.computed_layouts.len(),
+            looped.computed_layouts.len()
+        );
+        for entity in [first, second, third] {
+            let batched_node = batched.entity_to_taffy[&entity].id;
+            let looped_node = looped.entity_to_taffy[&entity].id;
+            assert_eq!(
+                batched.taffy.style(batched_node).unwrap(),
+                looped.taffy.style(looped_node).unwrap()
+            );
+            assert_eq!(
+                batched.taffy.get_node_context(batched_node).is_some(),
+                looped.taffy.get_node_context(looped_node).is_some()
+            );
+            assert_eq!(
+                batched.computed_layouts[&batched_node].0,
+                looped.computed_layouts[&looped_node].0
+            );
+        }
+    }
+
+    #[cfg(feature = "bevy_ui_debug")]
+    #[test]
+    fn test_debug_tree() {
+        use taffy::prelude::TaffyMaxContent;
+
+        let mut ui_surface = UiSurface::default();
+        let root_node_entity = Entity::from_raw_u32(1).unwrap();
+        let child_entity = Entity::from_raw_u32(2).unwrap();
+
+        let root_node = Node {
+            width: Val::Px(100.0),
+            height: Val::Px(50.0),
+            ..default()
+        };
+        let child_node = Node {
+            width: Val::Px(20.0),
+            height: Val::Px(10.0),
+            ..default()
+        };
+        ui_surface.upsert_node(
+            &LayoutContext::TEST_CONTEXT,
+            root_node_entity,
+            &root_node,
+            None,
+        );
+        ui_surface.upsert_node(
+            &LayoutContext::TEST_CONTEXT,
+            child_entity,
+            &child_node,
+            None,
+        );
+        ui_surface
+            .update_children(root_node_entity, [child_entity].into_iter())
+            .unwrap();
+
+        assert!(ui_surface.debug_tree(root_node_entity).is_none());
+
+        let viewport_node = ui_surface
+            .get_or_insert_taffy_viewport_node(root_node_entity)
+            .unwrap();
+        ui_surface
+            .taffy
+            .compute_layout(viewport_node, taffy::Size::MAX_CONTENT)
+            .unwrap();
+
+        let tree = ui_surface.debug_tree(root_node_entity).unwrap();
+        assert!(tree.contains("width: 100  height: 50"), "{tree}");
+        assert!(tree.contains("width: 20   height: 10"), "{tree}");
+        assert!(tree.contains(&format!("({child_entity})")), "{tree}");
+    }
+
     #[test]
     fn test_remove_entities() {
         let mut ui_surface = UiSurface::default();
@@ -355,7 +763,9 @@ mod tests {
 
         ui_surface.upsert_node(&LayoutContext::TEST_CONTEXT, root_node_entity, &node, None);
 
-        ui_surface.get_or_insert_taffy_viewport_node(root_node_entity);
+        ui_surface
+            .get_or_insert_taffy_viewport_node(root_node_entity)
+            .unwrap();
 
         assert!(ui_surface.entity_to_taffy.contains_key(&root_node_entity));
 
@@ -388,13 +798,74 @@ mod tests {
         ui_surface.upsert_node(&LayoutContext::TEST_CONTEXT, root_node_entity, &node, None);
         ui_surface.upsert_node(&LayoutContext::TEST_CONTEXT, child_entity, &node, None);
 
-        ui_surface.update_children(root_node_entity, vec![child_entity].into_iter());
+        ui_surface
+            .update_children(root_node_entity, vec![child_entity].into_iter())
+            .unwrap();
 
         let parent_node = *ui_surface.entity_to_taffy.get(&root_node_entity).unwrap();
         let child_node = *ui_surface.entity_to_taffy.get(&child_entity).unwrap();
         assert_eq!(ui_surface.taffy.parent(child_node.id), Some(parent_node.id));
     }
 
+    #[test]
+    fn test_update_children_with_missing_child() {
+        let mut ui_surface = UiSurface::default();
+        let root_node_entity = Entity::from_raw_u32(1).unwrap();
+        let child_entity = Entity::from_raw_u32(2).unwrap();
+        let missing_entity = Entity::from_raw_u32(3).unwrap();
+        let node = Node::default();
+
+        ui_surface.upsert_node(&LayoutContext::TEST_CONTEXT, root_node_entity, &node, None);
+        ui_surface.upsert_node(&LayoutContext::TEST_CONTEXT, child_entity, &node, None);
+
+        // A child without a taffy node is an error, but the other children are still set.
+        assert!(matches!(
+            ui_surface
+                .update_children(root_node_entity, [missing_entity, child_entity].into_iter()),
+            Err(LayoutError::InvalidHierarchy)
+        ));
+        let parent_node = *ui_surface.entity_to_taffy.get(&root_node_entity).unwrap();
+        let child_node = *ui_surface.entity_to_taffy.get(&child_entity).unwrap();
+        assert_eq!(
+            ui_surface.taffy.children(parent_node.id).unwrap(),
+            vec![child_node.id]
+        );
+
+        // So is a parent without a taffy node.
+        assert!(matches!(
+            ui_surface.update_children(missing_entity, [child_entity].into_iter()),
+            Err(LayoutError::InvalidHierarchy)
+        ));
+        assert!(matches!(
+            ui_surface.get_or_insert_taffy_viewport_node(missing_entity),
+            Err(LayoutError::InvalidHierarchy)
+        ));
+    }
+
+    #[test]
+    fn test_update_children_of_unknown_parent() {
+        let mut ui_surface = UiSurface::default();
+        let parent_entity = Entity::from_raw_u32(1).unwrap();
+        let child_entity = Entity::from_raw_u32(2).unwrap();
+        ui_surface.upsert_node(
+            &LayoutContext::TEST_CONTEXT,
+            child_entity,
+            &Node::default(),
+            None,
+        );
+        let node_count = ui_surface.taffy.total_node_count();
+
+        // The parent was despawned, or never had a taffy node: nothing changes.
+        assert!(matches!(
+            ui_surface.update_children(parent_entity, [child_entity].into_iter()),
+            Err(LayoutError::InvalidHierarchy)
+        ));
+        assert!(!ui_surface.entity_to_taffy.contains_key(&parent_entity));
+        assert_eq!(ui_surface.taffy.total_node_count(), node_count);
+        let child_node = *ui_surface.entity_to_taffy.get(&child_entity).unwrap();
+        assert_eq!(ui_surface.taffy.parent(child_node.id), None);
+    }
+
     #[expect(
         unreachable_code,
         reason = "Certain pieces of code tested here cause the test to fail if made reachable; see #16362 for progress on fixing this"
@@ -418,7 +889,9 @@ mod tests {
             .add_child(root_taffy_node.id, child_taffy.id)
             .unwrap();
 
-        ui_surface.get_or_insert_taffy_viewport_node(root_node_entity);
+        ui_surface
+            .get_or_insert_taffy_viewport_node(root_node_entity)
+            .unwrap();
 
         assert_eq!(
             ui_surface.taffy.parent(child_taffy.id),
@@ -436,7 +909,9 @@ mod tests {
         );
 
         // clear camera's root nodes
-        ui_surface.get_or_insert_taffy_viewport_node(root_node_entity);
+        ui_surface
+            .get_or_insert_taffy_viewport_node(root_node_entity)
+            .unwrap();
 
         return; // TODO: can't pass the test if we continue - not implemented (remove allow(unreachable_code))
 
@@ -452,7 +927,9 @@ mod tests {
         );
 
         // re-associate root node with viewport node
-        ui_surface.get_or_insert_taffy_viewport_node(root_node_entity);
+        ui_surface
+            .get_or_insert_taffy_viewport_node(root_node_entity)
+            .unwrap();
 
         let child_taffy = ui_surface.entity_to_taffy.get(&child_entity).unwrap();
         let root_taffy_children = ui_surface.taffy.children(root_taffy_node.id).unwrap();
@@ -75,7 +75,6 @@ use bevy_ecs::prelude::*;
 use bevy_input::InputSystems;
 use bevy_render::{camera::CameraUpdateSystems, RenderApp};
 use bevy_transform::TransformSystems;
-use layout::ui_surface::UiSurface;
 use stack::ui_stack_system;
 pub use stack::UiStack;
 use update::{update_clipping_system, update_ui_context_system};
@@ -138,6 +137,23 @@ impl Default for UiScale {
     }
 }
 
+/// Whether the layouts of UI nodes are rounded to whole physical pixels.
+///
+/// Rounding avoids blurry edges and sub-pixel seams between nodes, which matter most for pixel-art
+/// UIs, but makes nodes move in one-pixel steps when animated. Nodes with a [`LayoutConfig`]
+/// use their own setting instead, which their descendants inherit.
+///
+/// This is `true` by default.
+#[derive(Debug, Reflect, Resource, Deref, DerefMut)]
+#[reflect(Resource, Debug, Default)]
+pub struct UiLayoutRounding(pub bool);
+
+impl Default for UiLayoutRounding {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
 // Marks systems that can be ambiguous with [`widget::text_system`] if the `bevy_text` feature is enabled.
 // See https://github.com/bevyengine/bevy/pull/11391 for more details.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
@@ -149,6 +165,8 @@ struct AmbiguousWithUpdateText2dLayout;
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UiSurface>()
+            .init_resource::<UiLayoutErrors>()
+            .init_resource::<UiLayoutRounding>()
             .init_resource::<UiScale>()
             .init_resource::<UiStack>()
             .register_type::<BackgroundColor>()
@@ -166,6 +184,7 @@ impl Plugin for UiPlugin {
             .register_type::<ViewportNode>()
             .register_type::<UiRect>()
             .register_type::<UiScale>()
+            .register_type::<UiLayoutRounding>()
             .register_type::<BorderColor>()
             .register_type::<BorderRadius>()
             .register_type::<BoxShadow>()
@@ -213,6 +232,10 @@ impl Plugin for UiPlugin {
             PostUpdate,
             (
                 update_ui_context_system.in_set(UiSystems::Prepare),
+                widget::animate_image_node_texture_atlases.in_set(UiSystems::Prepare),
+                update_ui_layout_rounding
+                    .in_set(UiSystems::Layout)
+                    .before(ui_layout_system),
                 ui_layout_system_config,
                 ui_stack_system
                     .in_set(UiSystems::Stack)
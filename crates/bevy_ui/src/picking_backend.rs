@@ -24,7 +24,7 @@
 
 #![deny(missing_docs)]
 
-use crate::{focus::pick_rounded_rect, prelude::*, UiStack};
+use crate::{focus::pick_rounded_rect, physical_to_layout_transform, prelude::*, UiStack};
 use bevy_app::prelude::*;
 use bevy_ecs::{prelude::*, query::QueryData};
 use bevy_math::{Rect, Vec2};
@@ -137,11 +137,9 @@ pub fn ui_picking(
             let Ok((_, camera_data, _)) = camera_query.get(camera) else {
                 continue;
             };
-            let mut pointer_pos =
-                pointer_location.position * camera_data.target_scaling_factor().unwrap_or(1.);
-            if let Some(viewport) = camera_data.physical_viewport_rect() {
-                pointer_pos -= viewport.min.as_vec2();
-            }
+            let pointer_pos = physical_to_layout_transform(camera_data).transform_point2(
+                pointer_location.position * camera_data.target_scaling_factor().unwrap_or(1.),
+            );
             pointer_pos_by_camera
                 .entry(camera)
                 .or_default()
@@ -6,7 +6,8 @@ use bevy_image::prelude::*;
 use bevy_math::{Rect, UVec2, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::texture::TRANSPARENT_IMAGE_HANDLE;
-use bevy_sprite::TextureSlicer;
+use bevy_sprite::{TextureAtlasAnimation, TextureSlicer};
+use bevy_time::Time;
 use taffy::{MaybeMath, MaybeResolve};
 
 /// A UI Node that renders an image.
@@ -250,6 +251,25 @@ impl Measure for ImageMeasure {
     }
 }
 
+/// Updates the texture atlas index of [`ImageNode`]s with a [`TextureAtlasAnimation`].
+pub fn animate_image_node_texture_atlases(
+    time: Res<Time>,
+    mut query: Query<(&mut TextureAtlasAnimation, &mut ImageNode)>,
+) {
+    for (mut animation, mut image) in &mut query {
+        let Some(index) = image.texture_atlas.as_ref().map(|atlas| atlas.index) else {
+            continue;
+        };
+        let next_index = animation.advance(time.delta(), index);
+        // Avoid marking the image as changed when the frame stays the same.
+        if next_index != index {
+            if let Some(atlas) = &mut image.texture_atlas {
+                atlas.index = next_index;
+            }
+        }
+    }
+}
+
 type UpdateImageFilter = (With<Node>, Without<crate::prelude::Text>);
 
 /// Updates content size of the node based on the image provided
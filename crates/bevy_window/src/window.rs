@@ -638,6 +638,35 @@ impl Window {
     pub fn set_physical_cursor_position(&mut self, position: Option<DVec2>) {
         self.internal.physical_cursor_position = position;
     }
+
+    /// Locks the cursor to the window and hides it, or releases it and shows it again.
+    ///
+    /// This is the usual setup for first-person controls, where mouse motion turns the camera
+    /// instead of moving a cursor. Locking sets [`CursorOptions::grab_mode`] to
+    /// [`CursorGrabMode::Locked`], or to [`CursorGrabMode::Confined`] on platforms that don't
+    /// support it, and hides the cursor. Unlocking sets it back to [`CursorGrabMode::None`] and
+    /// shows the cursor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **`Windows`** doesn't support [`CursorGrabMode::Locked`], so the cursor is confined instead.
+    /// - **`X11`** doesn't support [`CursorGrabMode::Locked`] either, but **`Wayland`** does. Since
+    ///   the display server is only known at runtime, the grab mode is still set to
+    ///   [`CursorGrabMode::Locked`], and the windowing backend falls back to confining the cursor.
+    pub fn set_cursor_locked(&mut self, locked: bool) {
+        self.set_cursor_locked_with_support(locked, cfg!(not(target_os = "windows")));
+    }
+
+    /// Implementation of [`Window::set_cursor_locked`], with whether the platform supports
+    /// [`CursorGrabMode::Locked`] passed in so that both cases can be tested.
+    fn set_cursor_locked_with_support(&mut self, locked: bool, locked_supported: bool) {
+        self.cursor_options.grab_mode = match (locked, locked_supported) {
+            (false, _) => CursorGrabMode::None,
+            (true, true) => CursorGrabMode::Locked,
+            (true, false) => CursorGrabMode::Confined,
+        };
+        self.cursor_options.visible = !locked;
+    }
 }
 
 /// The size limits on a [`Window`].
@@ -1534,4 +1563,27 @@ mod tests {
         window.set_physical_cursor_position(Some(DVec2::new(400., 600.)));
         assert!(window.physical_cursor_position().is_none());
     }
+
+    #[test]
+    fn set_cursor_locked() {
+        for (locked_supported, grab_mode) in [
+            (true, CursorGrabMode::Locked),
+            (false, CursorGrabMode::Confined),
+        ] {
+            let mut window = Window::default();
+
+            window.set_cursor_locked_with_support(true, locked_supported);
+            assert_eq!(window.cursor_options.grab_mode, grab_mode);
+            assert!(!window.cursor_options.visible);
+
+            // Locking again changes nothing.
+            window.set_cursor_locked_with_support(true, locked_supported);
+            assert_eq!(window.cursor_options.grab_mode, grab_mode);
+            assert!(!window.cursor_options.visible);
+
+            window.set_cursor_locked_with_support(false, locked_supported);
+            assert_eq!(window.cursor_options.grab_mode, CursorGrabMode::None);
+            assert!(window.cursor_options.visible);
+        }
+    }
 }
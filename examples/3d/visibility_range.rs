@@ -24,31 +24,16 @@ const MIN_ZOOM_DISTANCE: f32 = 0.5;
 
 // The visibility ranges for high-poly and low-poly models respectively, when
 // both models are being shown.
-static NORMAL_VISIBILITY_RANGE_HIGH_POLY: VisibilityRange = VisibilityRange {
-    start_margin: 0.0..0.0,
-    end_margin: 3.0..4.0,
-    use_aabb: false,
-};
-static NORMAL_VISIBILITY_RANGE_LOW_POLY: VisibilityRange = VisibilityRange {
-    start_margin: 3.0..4.0,
-    end_margin: 8.0..9.0,
-    use_aabb: false,
-};
+static NORMAL_VISIBILITY_RANGE_HIGH_POLY: VisibilityRange =
+    VisibilityRange::new(0.0..0.0, 3.0..4.0);
+static NORMAL_VISIBILITY_RANGE_LOW_POLY: VisibilityRange = VisibilityRange::new(3.0..4.0, 8.0..9.0);
 
 // A visibility model that we use to always show a model (until the camera is so
 // far zoomed out that it's culled entirely).
-static SINGLE_MODEL_VISIBILITY_RANGE: VisibilityRange = VisibilityRange {
-    start_margin: 0.0..0.0,
-    end_margin: 8.0..9.0,
-    use_aabb: false,
-};
+static SINGLE_MODEL_VISIBILITY_RANGE: VisibilityRange = VisibilityRange::new(0.0..0.0, 8.0..9.0);
 
 // A visibility range that we use to completely hide a model.
-static INVISIBLE_VISIBILITY_RANGE: VisibilityRange = VisibilityRange {
-    start_margin: 0.0..0.0,
-    end_margin: 0.0..0.0,
-    use_aabb: false,
-};
+static INVISIBLE_VISIBILITY_RANGE: VisibilityRange = VisibilityRange::new(0.0..0.0, 0.0..0.0);
 
 // Allows us to identify the main model.
 #[derive(Component, Debug, Clone, Copy, PartialEq)]
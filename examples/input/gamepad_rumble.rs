@@ -2,7 +2,9 @@
 //! pressed.
 
 use bevy::{
-    input::gamepad::{Gamepad, GamepadRumbleIntensity, GamepadRumbleRequest},
+    input::gamepad::{
+        Gamepad, GamepadRumbleEnvelope, GamepadRumbleIntensity, GamepadRumbleRequest,
+    },
     prelude::*,
 };
 use core::time::Duration;
@@ -27,6 +29,7 @@ fn gamepad_system(
                 gamepad: entity,
                 intensity: GamepadRumbleIntensity::strong_motor(0.1),
                 duration: Duration::from_secs(5),
+                envelope: None,
             });
         }
 
@@ -36,6 +39,7 @@ fn gamepad_system(
                 gamepad: entity,
                 duration: Duration::from_secs(5),
                 intensity: GamepadRumbleIntensity::MAX,
+                envelope: None,
             });
         }
 
@@ -45,11 +49,14 @@ fn gamepad_system(
                 gamepad: entity,
                 duration: Duration::from_secs_f32(0.5),
                 intensity: GamepadRumbleIntensity::weak_motor(0.25),
+                envelope: None,
             });
         }
 
         if gamepad.just_pressed(GamepadButton::West) {
-            info!("West face button: custom rumble intensity for 5 second");
+            info!(
+                "West face button: custom rumble intensity for 5 seconds, ramping up over 1 second and fading out over 2 seconds"
+            );
             rumble_requests.write(GamepadRumbleRequest::Add {
                 gamepad: entity,
                 intensity: GamepadRumbleIntensity {
@@ -59,6 +66,10 @@ fn gamepad_system(
                     weak_motor: 0.25,
                 },
                 duration: Duration::from_secs(5),
+                envelope: Some(GamepadRumbleEnvelope::new(
+                    Duration::from_secs(1),
+                    Duration::from_secs(2),
+                )),
             });
         }
 
@@ -1,6 +1,6 @@
 //! Demonstrates how to grab and hide the mouse cursor.
 
-use bevy::{prelude::*, window::CursorGrabMode};
+use bevy::prelude::*;
 
 fn main() {
     App::new()
@@ -17,12 +17,10 @@ fn grab_mouse(
     key: Res<ButtonInput<KeyCode>>,
 ) {
     if mouse.just_pressed(MouseButton::Left) {
-        window.cursor_options.visible = false;
-        window.cursor_options.grab_mode = CursorGrabMode::Locked;
+        window.set_cursor_locked(true);
     }
 
     if key.just_pressed(KeyCode::Escape) {
-        window.cursor_options.visible = true;
-        window.cursor_options.grab_mode = CursorGrabMode::None;
+        window.set_cursor_locked(false);
     }
 }